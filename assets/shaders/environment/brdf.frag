@@ -0,0 +1,44 @@
+#version 150
+
+#include "importance_sample.glsl"
+
+// Scale and bias to the Fresnel reflectance at normal incidence that give the environment's
+// specular reflection, by cosine of the view angle (x) and roughness (y). This is the second
+// half of Karis' split-sum approximation.
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+const int SAMPLES = 256;
+
+void main() {
+    float n_dot_v = max(v_tex_coords.x, 1e-3);
+    float roughness = v_tex_coords.y;
+    vec3 v = vec3(sqrt(1.0 - n_dot_v * n_dot_v), 0.0, n_dot_v);
+    vec3 normal = vec3(0.0, 0.0, 1.0);
+
+    // Schlick-GGX with the remapping for image-based lighting
+    float k = roughness * roughness / 2.0;
+    float g_v = n_dot_v / (n_dot_v * (1.0 - k) + k);
+
+    float scale = 0.0;
+    float bias = 0.0;
+    for (int i = 0; i < SAMPLES; i++) {
+        vec3 h = importance_sample_ggx(hammersley(i, SAMPLES), normal, roughness);
+        vec3 l = normalize(2.0 * dot(v, h) * h - v);
+        float n_dot_l = max(l.z, 0.0);
+        if (n_dot_l <= 0.0) {
+            continue;
+        }
+        float n_dot_h = max(h.z, 0.0);
+        float v_dot_h = max(dot(v, h), 0.0);
+
+        float g = g_v * n_dot_l / (n_dot_l * (1.0 - k) + k);
+        float g_vis = g * v_dot_h / (n_dot_h * n_dot_v);
+        float fresnel = pow(1.0 - v_dot_h, 5.0);
+        scale += (1.0 - fresnel) * g_vis;
+        bias += fresnel * g_vis;
+    }
+    color = vec4(scale / float(SAMPLES), bias / float(SAMPLES), 0.0, 1.0);
+}
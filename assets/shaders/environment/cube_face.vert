@@ -0,0 +1,32 @@
+#version 150
+
+// Draws one face of a cubemap with a single triangle covering the viewport, giving each
+// fragment the direction from the cube's center through its texel
+
+// 0 to 5: +X, -X, +Y, -Y, +Z, -Z, the order of OpenGL's cubemap faces
+uniform int face;
+
+out vec3 v_direction;
+
+void main() {
+    vec2 ndc = vec2(float((gl_VertexID & 1) * 4 - 1), float((gl_VertexID & 2) * 2 - 1));
+    gl_Position = vec4(ndc, 0.0, 1.0);
+
+    // Texel (s, t) of a face is at ((x + 1) / 2, (y + 1) / 2); these are the directions OpenGL
+    // looks such a texel up with
+    float x = ndc.x;
+    float y = ndc.y;
+    if (face == 0) {
+        v_direction = vec3(1.0, -y, -x);
+    } else if (face == 1) {
+        v_direction = vec3(-1.0, -y, x);
+    } else if (face == 2) {
+        v_direction = vec3(x, 1.0, y);
+    } else if (face == 3) {
+        v_direction = vec3(x, -1.0, -y);
+    } else if (face == 4) {
+        v_direction = vec3(x, -y, 1.0);
+    } else {
+        v_direction = vec3(-x, -y, -1.0);
+    }
+}
@@ -0,0 +1,21 @@
+#version 150
+
+// Looks a cubemap texel up in an equirectangular panorama
+
+in vec3 v_direction;
+
+out vec4 color;
+
+uniform sampler2D panorama;
+// Mip level of the panorama with about as many texels per radian as the face being drawn
+uniform float lod;
+
+const float PI = 3.14159265359;
+
+void main() {
+    vec3 d = normalize(v_direction);
+    // +Z is the middle of the image and +X a quarter turn to the right of it; the texture's
+    // bottom row is the bottom of the image
+    vec2 uv = vec2(0.5 + atan(d.x, d.z) / (2.0 * PI), 0.5 + asin(clamp(d.y, -1.0, 1.0)) / PI);
+    color = vec4(textureLod(panorama, uv, lod).rgb, 1.0);
+}
@@ -0,0 +1,12 @@
+#version 150
+
+// A single triangle covering the viewport, with texture coordinates running from 0 to 1 across
+// the visible part
+
+out vec2 v_tex_coords;
+
+void main() {
+    vec2 ndc = vec2(float((gl_VertexID & 1) * 4 - 1), float((gl_VertexID & 2) * 2 - 1));
+    v_tex_coords = ndc * 0.5 + 0.5;
+    gl_Position = vec4(ndc, 0.0, 1.0);
+}
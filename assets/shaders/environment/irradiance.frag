@@ -0,0 +1,40 @@
+#version 150
+
+// Light arriving at a surface facing `v_direction` from the whole environment, weighted by the
+// cosine of its angle to the normal. The result is scaled so a white diffuse surface reflects
+// it as is.
+
+in vec3 v_direction;
+
+out vec4 color;
+
+uniform samplerCube environment;
+// The environment is low frequency at this scale, a blurry mip level hides the sparse sampling
+uniform float lod;
+
+const float PI = 3.14159265359;
+const int AZIMUTH_STEPS = 48;
+const int ELEVATION_STEPS = 12;
+
+void main() {
+    vec3 normal = normalize(v_direction);
+    vec3 up = abs(normal.y) < 0.999 ? vec3(0.0, 1.0, 0.0) : vec3(1.0, 0.0, 0.0);
+    vec3 right = normalize(cross(up, normal));
+    up = cross(normal, right);
+
+    vec3 sum = vec3(0.0);
+    float weight = 0.0;
+    for (int i = 0; i < AZIMUTH_STEPS; i++) {
+        float phi = (float(i) + 0.5) / float(AZIMUTH_STEPS) * 2.0 * PI;
+        for (int j = 0; j < ELEVATION_STEPS; j++) {
+            float theta = (float(j) + 0.5) / float(ELEVATION_STEPS) * 0.5 * PI;
+            vec3 tangent = vec3(sin(theta) * cos(phi), sin(theta) * sin(phi), cos(theta));
+            vec3 direction = tangent.x * right + tangent.y * up + tangent.z * normal;
+            // The cosine weights the light, the sine the area of the band of the hemisphere
+            float w = cos(theta) * sin(theta);
+            sum += textureLod(environment, direction, lod).rgb * w;
+            weight += w;
+        }
+    }
+    color = vec4(sum / weight, 1.0);
+}
@@ -0,0 +1,11 @@
+#version 150
+
+in vec3 v_direction;
+
+out vec4 color;
+
+uniform samplerCube environment;
+
+void main() {
+    color = vec4(textureLod(environment, v_direction, 0.0).rgb, 1.0);
+}
@@ -0,0 +1,15 @@
+#version 150
+
+// The environment behind everything else, drawn as a triangle covering the viewport
+
+// From clip space to world space directions, ignoring the camera's position
+uniform mat4 clip_to_world;
+
+out vec3 v_direction;
+
+void main() {
+    vec2 ndc = vec2(float((gl_VertexID & 1) * 4 - 1), float((gl_VertexID & 2) * 2 - 1));
+    vec4 world = clip_to_world * vec4(ndc, 1.0, 1.0);
+    v_direction = world.xyz / world.w;
+    gl_Position = vec4(ndc, 1.0, 1.0);
+}
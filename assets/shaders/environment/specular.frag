@@ -0,0 +1,47 @@
+#version 150
+
+#include "importance_sample.glsl"
+
+// The environment as reflected by a surface of the given roughness, for the direction
+// `v_direction`. Like most real-time renderers, this assumes the view direction equals the
+// normal, which loses the stretched reflections at grazing angles.
+
+in vec3 v_direction;
+
+out vec4 color;
+
+uniform samplerCube environment;
+// Width of a face of `environment`, in texels
+uniform float environment_size;
+uniform float roughness;
+
+const int SAMPLES = 64;
+
+void main() {
+    vec3 normal = normalize(v_direction);
+
+    vec3 sum = vec3(0.0);
+    float weight = 0.0;
+    for (int i = 0; i < SAMPLES; i++) {
+        vec3 h = importance_sample_ggx(hammersley(i, SAMPLES), normal, roughness);
+        vec3 l = normalize(2.0 * dot(normal, h) * h - normal);
+        float n_dot_l = dot(normal, l);
+        if (n_dot_l <= 0.0) {
+            continue;
+        }
+
+        // Take each sample from a mip level matching the solid angle it stands for, so few
+        // samples don't show up as speckles
+        float alpha = roughness * roughness;
+        float n_dot_h = max(dot(normal, h), 0.0);
+        float d = n_dot_h * n_dot_h * (alpha * alpha - 1.0) + 1.0;
+        float pdf = alpha * alpha / (PI * d * d) / 4.0;
+        float sample_angle = 1.0 / (float(SAMPLES) * pdf + 1e-4);
+        float texel_angle = 4.0 * PI / (6.0 * environment_size * environment_size);
+        float lod = roughness == 0.0 ? 0.0 : max(0.5 * log2(sample_angle / texel_angle) + 1.0, 0.0);
+
+        sum += textureLod(environment, l, lod).rgb * n_dot_l;
+        weight += n_dot_l;
+    }
+    color = vec4(sum / max(weight, 1e-4), 1.0);
+}
@@ -0,0 +1,13 @@
+#version 150
+
+#include "vertex.glsl"
+
+// Per instance, placing the copy within `model`
+in mat4 instance_model;
+in vec3 instance_tint;
+
+uniform mat4 model;
+
+void main() {
+    emit_vertex(model * instance_model, instance_tint);
+}
@@ -0,0 +1,147 @@
+// Lights, shadows and normal mapping shared by the lit fragment shaders. Everything is in view
+// space.
+
+const int MAX_LIGHTS = 8;
+const int DIRECTIONAL = 0;
+const int POINT = 1;
+const int SPOT = 2;
+
+// Filled in by `LightUniforms`
+struct Light {
+    int kind;
+    vec3 position;
+    // Where the light travels, for directional and spot lights
+    vec3 direction;
+    // Color times intensity
+    vec3 color;
+    // Constant, linear and quadratic terms of 1 / (c + l * d + q * d^2)
+    vec3 attenuation;
+    // Cosines of the spot cone's half-angles
+    float cos_inner;
+    float cos_outer;
+    // Index of the light's shadow map, or -1 if it has none
+    int shadow_map;
+    // From view space to the shadow map's texture coordinates and depth
+    mat4 shadow_matrix;
+};
+
+uniform Light lights[MAX_LIGHTS];
+uniform int light_count;
+// Sampler arrays can't be indexed by a variable in GLSL 1.50, so the maps get a name each
+uniform sampler2DShadow shadow_map_0;
+uniform sampler2DShadow shadow_map_1;
+// Constant and slope-scaled depth bias
+uniform vec2 shadow_bias;
+uniform int pcf_radius;
+// Use the per-vertex tangents instead of deriving them from screen-space derivatives
+uniform bool vertex_tangents;
+
+// Image-based lighting from `Environment`, looked up with world space directions
+uniform samplerCube irradiance_map;
+uniform samplerCube specular_map;
+uniform float specular_levels;
+uniform sampler2D brdf_lut;
+uniform mat3 view_to_world;
+
+mat3 cotangent_frame(vec3 normal, vec3 pos, vec2 uv) {
+    vec3 dp1 = dFdx(pos);
+    vec3 dp2 = dFdy(pos);
+    vec2 duv1 = dFdx(uv);
+    vec2 duv2 = dFdy(uv);
+
+    vec3 dp2perp = cross(dp2, normal);
+    vec3 dp1perp = cross(normal, dp1);
+    vec3 T = dp2perp * duv1.x + dp1perp * duv2.x;
+    vec3 B = dp2perp * duv1.y + dp1perp * duv2.y;
+
+    float invmax = inversesqrt(max(dot(T, T), dot(B, B)));
+    return mat3(T * invmax, B * invmax, normal);
+}
+
+// The interpolated normal bent by a tangent-space normal map sample
+vec3 surface_normal(vec3 normal, vec3 tangent, float bitangent_sign, vec3 position, vec2 uv,
+                    vec3 normal_map) {
+    vec3 n = normalize(normal);
+    mat3 tbn;
+    if (vertex_tangents) {
+        // Re-orthogonalize, interpolation doesn't keep the frame square
+        vec3 t = normalize(tangent - n * dot(n, tangent));
+        vec3 b = cross(n, t) * bitangent_sign;
+        tbn = mat3(t, b, n);
+    } else {
+        tbn = cotangent_frame(n, position, uv);
+    }
+    return normalize(tbn * (normal_map * 2.0 - 1.0));
+}
+
+float shadow_sample(int map, vec3 coords) {
+    if (map == 0) {
+        return texture(shadow_map_0, coords);
+    }
+    return texture(shadow_map_1, coords);
+}
+
+// Fraction of the light that reaches `position`, averaged over a square of shadow map texels.
+// `normal` is the geometric normal, the normal map would make the bias noisy.
+float shadow(Light light, vec3 position, vec3 normal, vec3 light_dir) {
+    if (light.shadow_map < 0) {
+        return 1.0;
+    }
+
+    vec4 coords = light.shadow_matrix * vec4(position, 1.0);
+    coords.xyz /= coords.w;
+    // The map only covers the models; anything outside it is lit
+    if (any(lessThan(coords.xyz, vec3(0.0))) || any(greaterThan(coords.xyz, vec3(1.0)))) {
+        return 1.0;
+    }
+
+    // Surfaces facing away from the light need more bias
+    float n_dot_l = clamp(dot(normalize(normal), light_dir), 0.0, 1.0);
+    float depth = coords.z - shadow_bias.x - shadow_bias.y * (1.0 - n_dot_l);
+
+    vec2 texel = 1.0 / vec2(textureSize(shadow_map_0, 0));
+    float lit = 0.0;
+    for (int x = -pcf_radius; x <= pcf_radius; x++) {
+        for (int y = -pcf_radius; y <= pcf_radius; y++) {
+            lit += shadow_sample(light.shadow_map, vec3(coords.xy + vec2(x, y) * texel, depth));
+        }
+    }
+    float width = float(2 * pcf_radius + 1);
+    return lit / (width * width);
+}
+
+// The light arriving at `position` after falloff, spot cone and shadows, with the direction
+// towards the light in `light_dir`
+vec3 incoming_light(Light light, vec3 position, vec3 normal, out vec3 light_dir) {
+    float strength = 1.0;
+    if (light.kind == DIRECTIONAL) {
+        light_dir = -light.direction;
+    } else {
+        vec3 offset = light.position - position;
+        float distance = length(offset);
+        light_dir = offset / distance;
+        strength = 1.0 / dot(light.attenuation, vec3(1.0, distance, distance * distance));
+        if (light.kind == SPOT) {
+            float cos_angle = dot(-light_dir, light.direction);
+            strength *= smoothstep(light.cos_outer, light.cos_inner, cos_angle);
+        }
+    }
+
+    return light.color * strength * shadow(light, position, normal, light_dir);
+}
+
+// Light from the environment reaching a surface facing `normal`, for a white diffuse surface
+vec3 ambient_irradiance(vec3 normal) {
+    return texture(irradiance_map, view_to_world * normal).rgb;
+}
+
+// The environment as reflected in the direction `reflected` by a surface of the given roughness
+vec3 ambient_reflection(vec3 reflected, float roughness) {
+    float lod = roughness * (specular_levels - 1.0);
+    return textureLod(specular_map, view_to_world * reflected, lod).rgb;
+}
+
+// Scale and bias to the reflectance at normal incidence for the environment's specular light
+vec2 ambient_brdf(float n_dot_v, float roughness) {
+    return texture(brdf_lut, vec2(n_dot_v, roughness)).rg;
+}
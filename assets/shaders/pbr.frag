@@ -0,0 +1,121 @@
+#version 150
+
+#include "lighting.glsl"
+
+in vec3 v_normal;
+in vec3 v_position;
+in vec2 v_tex_coords;
+in vec3 v_tangent;
+in float v_bitangent_sign;
+in vec3 v_tint;
+
+out vec4 color;
+
+// The glTF metallic-roughness inputs; each texture is multiplied by its factor
+uniform sampler2D base_color_tex;
+uniform vec4 base_color_factor;
+uniform sampler2D normal_tex;
+// Roughness in green, metalness in blue
+uniform sampler2D metallic_roughness_tex;
+uniform float metallic_factor;
+uniform float roughness_factor;
+// Ambient occlusion in red
+uniform sampler2D occlusion_tex;
+uniform sampler2D emissive_tex;
+uniform vec3 emissive_factor;
+
+const float PI = 3.14159265359;
+// Reflectance of dielectrics seen head-on
+const vec3 dielectric_f0 = vec3(0.04);
+
+// Trowbridge-Reitz GGX: how many microfacets face along the half vector
+float distribution_ggx(float n_dot_h, float roughness) {
+    float alpha = roughness * roughness;
+    float alpha2 = alpha * alpha;
+    float d = n_dot_h * n_dot_h * (alpha2 - 1.0) + 1.0;
+    return alpha2 / (PI * d * d);
+}
+
+// Smith's method with the Schlick-GGX approximation: how many microfacets are neither hidden
+// from the light nor from the camera
+float geometry_smith(float n_dot_v, float n_dot_l, float roughness) {
+    float k = (roughness + 1.0) * (roughness + 1.0) / 8.0;
+    float g_v = n_dot_v / (n_dot_v * (1.0 - k) + k);
+    float g_l = n_dot_l / (n_dot_l * (1.0 - k) + k);
+    return g_v * g_l;
+}
+
+vec3 fresnel_schlick(float cos_theta, vec3 f0) {
+    return f0 + (1.0 - f0) * pow(1.0 - cos_theta, 5.0);
+}
+
+// Fresnel averaged over the microfacets of a rough surface, for light from all directions
+vec3 fresnel_schlick_roughness(float cos_theta, vec3 f0, float roughness) {
+    return f0 + (max(vec3(1.0 - roughness), f0) - f0) * pow(1.0 - cos_theta, 5.0);
+}
+
+// Diffuse and specular light from the environment
+vec3 ambient(vec3 normal, vec3 camera_dir, vec3 albedo, float metallic, float roughness) {
+    float n_dot_v = max(dot(normal, camera_dir), 1e-4);
+    vec3 f0 = mix(dielectric_f0, albedo, metallic);
+    vec3 fresnel = fresnel_schlick_roughness(n_dot_v, f0, roughness);
+
+    vec3 diffuse = (1.0 - fresnel) * (1.0 - metallic) * albedo * ambient_irradiance(normal);
+
+    vec2 brdf = ambient_brdf(n_dot_v, roughness);
+    vec3 reflected = ambient_reflection(reflect(-camera_dir, normal), roughness);
+    vec3 specular = reflected * (fresnel * brdf.x + brdf.y);
+
+    return diffuse + specular;
+}
+
+// Cook-Torrance specular plus Lambertian diffuse for one light
+vec3 shade(Light light, vec3 normal, vec3 camera_dir, vec3 albedo, float metallic,
+           float roughness) {
+    vec3 light_dir;
+    // Light colors are set so that white light lights a white surface facing it fully, as in
+    // the Blinn-Phong shader, which takes π times the radiance
+    vec3 radiance = incoming_light(light, v_position, v_normal, light_dir) * PI;
+
+    float n_dot_l = dot(normal, light_dir);
+    if (n_dot_l <= 0.0) {
+        return vec3(0.0);
+    }
+    float n_dot_v = max(dot(normal, camera_dir), 1e-4);
+    vec3 half_direction = normalize(light_dir + camera_dir);
+
+    vec3 f0 = mix(dielectric_f0, albedo, metallic);
+    vec3 fresnel = fresnel_schlick(max(dot(half_direction, camera_dir), 0.0), f0);
+    float d = distribution_ggx(max(dot(normal, half_direction), 0.0), roughness);
+    float g = geometry_smith(n_dot_v, n_dot_l, roughness);
+    vec3 specular = d * g * fresnel / (4.0 * n_dot_v * n_dot_l);
+
+    // Light that isn't reflected is scattered; metals absorb it
+    vec3 diffuse = (1.0 - fresnel) * (1.0 - metallic) * albedo / PI;
+
+    return (diffuse + specular) * radiance * n_dot_l;
+}
+
+void main() {
+    vec4 base_color = texture(base_color_tex, v_tex_coords) * base_color_factor;
+    base_color.rgb *= v_tint;
+    vec2 metallic_roughness = texture(metallic_roughness_tex, v_tex_coords).bg;
+    float metallic = clamp(metallic_roughness.x * metallic_factor, 0.0, 1.0);
+    // Perfectly smooth surfaces turn lights into infinitely small highlights
+    float roughness = clamp(metallic_roughness.y * roughness_factor, 0.05, 1.0);
+    float occlusion = texture(occlusion_tex, v_tex_coords).r;
+    vec3 emissive = texture(emissive_tex, v_tex_coords).rgb * emissive_factor;
+
+    vec3 normal_map = texture(normal_tex, v_tex_coords).rgb;
+    vec3 normal = surface_normal(v_normal, v_tangent, v_bitangent_sign, v_position,
+                                 v_tex_coords, normal_map);
+
+    vec3 camera_dir = normalize(-v_position);
+    vec3 lit = ambient(normal, camera_dir, base_color.rgb, metallic, roughness) * occlusion;
+
+    for (int i = 0; i < min(light_count, MAX_LIGHTS); i++) {
+        lit += shade(lights[i], normal, camera_dir, base_color.rgb, metallic, roughness);
+    }
+
+    color = vec4(lit + emissive, base_color.a);
+}
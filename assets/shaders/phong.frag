@@ -0,0 +1,49 @@
+#version 150
+
+#include "lighting.glsl"
+
+in vec3 v_normal;
+in vec3 v_position;
+in vec2 v_tex_coords;
+in vec3 v_tangent;
+in float v_bitangent_sign;
+in vec3 v_tint;
+
+out vec4 color;
+
+uniform sampler2D diffuse_tex;
+uniform sampler2D normal_tex;
+
+// For now, we'll use a constant ambient and diffuse value
+// const vec3 ambient_color = vec3(0.2, 0.0, 0.0);
+// const vec3 diffuse_color = vec3(0.6, 0.0, 0.0);
+const vec3 specular_color = vec3(1.0, 1.0, 1.0);
+
+vec3 shade(Light light, vec3 normal, vec3 camera_dir, vec3 diffuse_color) {
+    vec3 light_dir;
+    vec3 light_color = incoming_light(light, v_position, v_normal, light_dir);
+
+    float diffuse = max(dot(normal, light_dir), 0.0);
+
+    vec3 half_direction = normalize(light_dir + camera_dir);
+    float specular = pow(max(dot(half_direction, normal), 0.0), 16.0);
+
+    return light_color * (diffuse * diffuse_color + specular * specular_color);
+}
+
+void main() {
+    vec3 diffuse_color = texture(diffuse_tex, v_tex_coords).rgb * v_tint;
+
+    vec3 normal_map = texture(normal_tex, v_tex_coords).rgb;
+    vec3 real_normal = surface_normal(v_normal, v_tangent, v_bitangent_sign, v_position,
+                                      v_tex_coords, normal_map);
+    vec3 ambient_color = diffuse_color * ambient_irradiance(real_normal);
+
+    vec3 camera_dir = normalize(-v_position);
+    vec3 lit = ambient_color;
+    for (int i = 0; i < min(light_count, MAX_LIGHTS); i++) {
+        lit += shade(lights[i], real_normal, camera_dir, diffuse_color);
+    }
+
+    color = vec4(lit, 1.0);
+}
@@ -0,0 +1,10 @@
+#version 150
+
+#include "vertex.glsl"
+
+uniform mat4 model;
+uniform vec3 tint;
+
+void main() {
+    emit_vertex(model, tint);
+}
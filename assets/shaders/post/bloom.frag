@@ -0,0 +1,16 @@
+#version 150
+
+// Adds the blurred bright parts back onto the image
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+uniform sampler2D bloom;
+uniform float intensity;
+
+void main() {
+    vec4 c = texture(source, v_tex_coords);
+    color = vec4(c.rgb + texture(bloom, v_tex_coords).rgb * intensity, c.a);
+}
@@ -0,0 +1,18 @@
+#version 150
+
+// Keeps the part of each color above the bloom threshold, for blurring into a glow around
+// bright areas
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+uniform float threshold;
+
+void main() {
+    vec3 c = texture(source, v_tex_coords).rgb;
+    float brightness = max(c.r, max(c.g, c.b));
+    float bright_part = max(brightness - threshold, 0.0) / max(brightness, 1e-4);
+    color = vec4(c * bright_part, 1.0);
+}
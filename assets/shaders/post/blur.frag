@@ -0,0 +1,24 @@
+#version 150
+
+// One direction of a separable 9-tap Gaussian blur, taking two taps per texture read with
+// linear filtering
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+// A texel in the direction to blur in
+uniform vec2 direction;
+
+const float OFFSETS[3] = float[](0.0, 1.3846153846, 3.2307692308);
+const float WEIGHTS[3] = float[](0.2270270270, 0.3162162162, 0.0702702703);
+
+void main() {
+    vec3 sum = texture(source, v_tex_coords).rgb * WEIGHTS[0];
+    for (int i = 1; i < 3; i++) {
+        sum += texture(source, v_tex_coords + direction * OFFSETS[i]).rgb * WEIGHTS[i];
+        sum += texture(source, v_tex_coords - direction * OFFSETS[i]).rgb * WEIGHTS[i];
+    }
+    color = vec4(sum, 1.0);
+}
@@ -0,0 +1,26 @@
+#version 150
+
+#include "srgb.glsl"
+
+// Looks colors up in a 3D table mapping sRGB encoded colors to graded ones, as color grading
+// tools export them
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+uniform sampler3D lut;
+// Entries along each side of the table
+uniform float lut_size;
+// Whether the source is sRGB encoded already, or linear
+uniform bool srgb_input;
+
+void main() {
+    vec4 c = texture(source, v_tex_coords);
+    vec3 encoded = srgb_input ? clamp(c.rgb, 0.0, 1.0) : linear_to_srgb(c.rgb);
+    // The centers of the first and last entries, so colors between them are interpolated
+    vec3 coords = encoded * ((lut_size - 1.0) / lut_size) + 0.5 / lut_size;
+    vec3 graded = texture(lut, coords).rgb;
+    color = vec4(srgb_input ? graded : srgb_to_linear(graded), c.a);
+}
@@ -0,0 +1,13 @@
+#version 150
+
+// Copies the image as it is, finishing the frame once the chain has run
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+
+void main() {
+    color = texture(source, v_tex_coords);
+}
@@ -0,0 +1,56 @@
+#version 150
+
+#include "srgb.glsl"
+
+// Fast approximate anti-aliasing: blurs along the edges found from the contrast in luma
+// between a pixel and its diagonal neighbours, after Lottes' FXAA
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+uniform vec2 texel_size;
+// Whether the source is sRGB encoded already; edges are found in perceptual luma either way
+uniform bool srgb_input;
+
+const float SPAN_MAX = 8.0;
+const float REDUCE_MUL = 1.0 / 8.0;
+const float REDUCE_MIN = 1.0 / 128.0;
+
+float luma(vec3 c) {
+    if (!srgb_input) {
+        c = linear_to_srgb(c);
+    }
+    return dot(c, vec3(0.299, 0.587, 0.114));
+}
+
+vec3 sample_at(vec2 offset) {
+    return texture(source, v_tex_coords + offset).rgb;
+}
+
+void main() {
+    vec4 center = texture(source, v_tex_coords);
+    float luma_m = luma(center.rgb);
+    float luma_nw = luma(sample_at(vec2(-1.0, 1.0) * texel_size));
+    float luma_ne = luma(sample_at(vec2(1.0, 1.0) * texel_size));
+    float luma_sw = luma(sample_at(vec2(-1.0, -1.0) * texel_size));
+    float luma_se = luma(sample_at(vec2(1.0, -1.0) * texel_size));
+    float luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    float luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+    // Perpendicular to the luma gradient, so along the edge
+    vec2 dir = vec2((luma_nw + luma_ne) - (luma_sw + luma_se),
+                    (luma_nw + luma_sw) - (luma_ne + luma_se));
+    float dir_reduce = max((luma_nw + luma_ne + luma_sw + luma_se) * 0.25 * REDUCE_MUL,
+                           REDUCE_MIN);
+    float scale = 1.0 / (min(abs(dir.x), abs(dir.y)) + dir_reduce);
+    dir = clamp(dir * scale, -SPAN_MAX, SPAN_MAX) * texel_size;
+
+    vec3 near = 0.5 * (sample_at(dir * (1.0 / 3.0 - 0.5)) + sample_at(dir * (2.0 / 3.0 - 0.5)));
+    vec3 far = near * 0.5 + 0.25 * (sample_at(dir * -0.5) + sample_at(dir * 0.5));
+    // The wider blur is only taken if it didn't reach past the edge
+    float luma_far = luma(far);
+    vec3 result = luma_far < luma_min || luma_far > luma_max ? near : far;
+    color = vec4(result, center.a);
+}
@@ -0,0 +1,16 @@
+#version 150
+
+#include "srgb.glsl"
+
+// Encodes linear colors as sRGB, the encoding displays expect
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+
+void main() {
+    vec4 linear = texture(source, v_tex_coords);
+    color = vec4(linear_to_srgb(linear.rgb), linear.a);
+}
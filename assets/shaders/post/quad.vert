@@ -0,0 +1,14 @@
+#version 150
+
+// The quad mesh stretched over the viewport, with texture coordinates running from 0 to 1 across
+// it
+
+in vec3 position;
+in vec2 tex_coords;
+
+out vec2 v_tex_coords;
+
+void main() {
+    v_tex_coords = tex_coords;
+    gl_Position = vec4(position.xy, 0.0, 1.0);
+}
@@ -0,0 +1,43 @@
+#version 150
+
+// Last pass of SMAA: mixes each pixel with its neighbours by the weights of the edges between
+// them
+
+out vec4 color;
+
+uniform sampler2D source;
+uniform sampler2D weights;
+
+vec4 weights_at(ivec2 p) {
+    if (any(lessThan(p, ivec2(0))) || any(greaterThanEqual(p, textureSize(weights, 0)))) {
+        return vec4(0.0);
+    }
+    return texelFetch(weights, p, 0);
+}
+
+vec3 source_at(ivec2 p) {
+    return texelFetch(source, clamp(p, ivec2(0), textureSize(source, 0) - 1), 0).rgb;
+}
+
+void main() {
+    ivec2 p = ivec2(gl_FragCoord.xy);
+    vec4 c = texelFetch(source, p, 0);
+    vec4 own = weights_at(p);
+    float from_above = own.x;
+    float from_left = own.z;
+    // The edges below and to the right belong to those neighbours
+    float from_below = weights_at(p - ivec2(0, 1)).y;
+    float from_right = weights_at(p + ivec2(1, 0)).w;
+
+    float total = from_above + from_below + from_left + from_right;
+    if (total == 0.0) {
+        color = c;
+        return;
+    }
+    float scale = 1.0 / max(total, 1.0);
+    vec3 neighbours = source_at(p + ivec2(0, 1)) * from_above
+        + source_at(p - ivec2(0, 1)) * from_below
+        + source_at(p - ivec2(1, 0)) * from_left
+        + source_at(p + ivec2(1, 0)) * from_right;
+    color = vec4(c.rgb * (1.0 - total * scale) + neighbours * scale, c.a);
+}
@@ -0,0 +1,45 @@
+#version 150
+
+#include "srgb.glsl"
+
+// First pass of SMAA: marks pixels whose luma differs enough from the pixel to their left (x)
+// or the one above them (y)
+
+out vec4 color;
+
+uniform sampler2D source;
+// Whether the source is sRGB encoded already; edges are found in perceptual luma either way
+uniform bool srgb_input;
+
+const float THRESHOLD = 0.1;
+// An edge is dropped when a neighbouring one has this many times its contrast, so the sharper
+// edge of two parallel ones is the one smoothed
+const float CONTRAST_ADAPTATION = 2.0;
+
+float luma_at(ivec2 offset) {
+    ivec2 p = clamp(ivec2(gl_FragCoord.xy) + offset, ivec2(0), textureSize(source, 0) - 1);
+    vec3 c = texelFetch(source, p, 0).rgb;
+    if (!srgb_input) {
+        c = linear_to_srgb(c);
+    }
+    return dot(c, vec3(0.2126, 0.7152, 0.0722));
+}
+
+void main() {
+    float luma = luma_at(ivec2(0, 0));
+    vec2 delta = abs(luma - vec2(luma_at(ivec2(-1, 0)), luma_at(ivec2(0, 1))));
+    vec2 edges = step(THRESHOLD, delta);
+    if (edges.x + edges.y == 0.0) {
+        color = vec4(0.0);
+        return;
+    }
+
+    // The contrast across the pixel's other sides, and the same sides of the neighbours
+    vec2 max_delta = max(delta, abs(luma - vec2(luma_at(ivec2(1, 0)), luma_at(ivec2(0, -1)))));
+    vec2 beyond = abs(vec2(luma_at(ivec2(-1, 0)) - luma_at(ivec2(-2, 0)),
+                           luma_at(ivec2(0, 1)) - luma_at(ivec2(0, 2))));
+    max_delta = max(max_delta, beyond);
+    float largest = max(max_delta.x, max_delta.y);
+    edges *= step(largest, CONTRAST_ADAPTATION * delta);
+    color = vec4(edges, 0.0, 1.0);
+}
@@ -0,0 +1,83 @@
+#version 150
+
+// Second pass of SMAA: follows each edge found to its ends, sees whether the silhouette turns
+// there, and estimates how much of the pixels on either side of the edge the silhouette covers.
+//
+// As in MLAA, each end that turns is joined to the middle of the edge by a straight line, and
+// the pixels on the wrong side of that line take some of the other side's color. SMAA looks
+// these areas up in a precomputed texture that also covers diagonal patterns; here they are
+// computed for the horizontal and vertical ones only.
+
+out vec4 color;
+
+uniform sampler2D edges;
+
+const int MAX_SEARCH = 16;
+
+vec2 edge_at(ivec2 p) {
+    if (any(lessThan(p, ivec2(0))) || any(greaterThanEqual(p, textureSize(edges, 0)))) {
+        return vec2(0.0);
+    }
+    return texelFetch(edges, p, 0).rg;
+}
+
+// Along an edge `size` pixels long, lines run from `h0` across the edge at its start and `h1` at
+// its end to the edge's middle. Returns the area between them and the edge over the pixel
+// from `x` to `x + 1`: on the positive side in x, on the negative side in y.
+vec2 area(float size, float x, float h0, float h1) {
+    float middle = size * 0.5;
+    float u = clamp(x, 0.0, middle);
+    float v = clamp(x + 1.0, 0.0, middle);
+    float first = h0 * ((v - u) - (v * v - u * u) / (2.0 * middle));
+    u = clamp(x, middle, size);
+    v = clamp(x + 1.0, middle, size);
+    float second = h1 * ((v * v - u * u) * 0.5 - middle * (v - u)) / middle;
+    return vec2(max(first, 0.0) + max(second, 0.0), max(-first, 0.0) + max(-second, 0.0));
+}
+
+void main() {
+    ivec2 p = ivec2(gl_FragCoord.xy);
+    vec2 e = edge_at(p);
+    // How much this pixel takes from the one above, the one above from this one, this one from
+    // the one to its left and that one from this one
+    vec4 weights = vec4(0.0);
+
+    if (e.y > 0.0) {
+        int left = 0;
+        while (left < MAX_SEARCH && edge_at(p - ivec2(left + 1, 0)).y > 0.0) {
+            left++;
+        }
+        int right = 0;
+        while (right < MAX_SEARCH && edge_at(p + ivec2(right + 1, 0)).y > 0.0) {
+            right++;
+        }
+        // The silhouette turns up at an end where the row above has an edge there, and down
+        // where this row has one
+        ivec2 start = p - ivec2(left, 0);
+        ivec2 end = p + ivec2(right + 1, 0);
+        float turn0 = edge_at(start + ivec2(0, 1)).x - edge_at(start).x;
+        float turn1 = edge_at(end + ivec2(0, 1)).x - edge_at(end).x;
+        vec2 a = area(float(left + right + 1), float(left), 0.5 * turn0, 0.5 * turn1);
+        weights.xy = a.yx;
+    }
+
+    if (e.x > 0.0) {
+        int down = 0;
+        while (down < MAX_SEARCH && edge_at(p - ivec2(0, down + 1)).x > 0.0) {
+            down++;
+        }
+        int up = 0;
+        while (up < MAX_SEARCH && edge_at(p + ivec2(0, up + 1)).x > 0.0) {
+            up++;
+        }
+        // Turning right, into this column, where it has an edge at the end, and left where
+        // the column to the left has one
+        ivec2 below = p - ivec2(0, down + 1);
+        ivec2 top = p + ivec2(0, up);
+        float turn0 = edge_at(below).y - edge_at(below - ivec2(1, 0)).y;
+        float turn1 = edge_at(top).y - edge_at(top - ivec2(1, 0)).y;
+        weights.zw = area(float(down + up + 1), float(down), 0.5 * turn0, 0.5 * turn1);
+    }
+
+    color = weights;
+}
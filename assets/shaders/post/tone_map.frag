@@ -0,0 +1,20 @@
+#version 150
+
+// Compresses HDR colors into the range a display can show, with Narkowicz's fit of the ACES
+// filmic curve
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+uniform float exposure;
+
+vec3 aces(vec3 x) {
+    return clamp(x * (2.51 * x + 0.03) / (x * (2.43 * x + 0.59) + 0.14), 0.0, 1.0);
+}
+
+void main() {
+    vec4 hdr = texture(source, v_tex_coords);
+    color = vec4(aces(hdr.rgb * exposure), hdr.a);
+}
@@ -0,0 +1,18 @@
+#version 150
+
+// Darkens the image towards its corners
+
+in vec2 v_tex_coords;
+
+out vec4 color;
+
+uniform sampler2D source;
+// How much darker the corners get, from 0 to 1
+uniform float strength;
+
+void main() {
+    vec4 c = texture(source, v_tex_coords);
+    // 0 in the center, 1 in the corners
+    float distance = length(v_tex_coords - 0.5) * sqrt(2.0);
+    color = vec4(c.rgb * (1.0 - strength * smoothstep(0.4, 1.0, distance)), c.a);
+}
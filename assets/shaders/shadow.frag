@@ -0,0 +1,5 @@
+#version 150
+
+// Only the depth is written
+void main() {
+}
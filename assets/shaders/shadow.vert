@@ -0,0 +1,11 @@
+#version 150
+
+in vec3 position;
+
+uniform mat4 model;
+// The light's projection times its view
+uniform mat4 light_matrix;
+
+void main() {
+    gl_Position = light_matrix * model * vec4(position, 1.0);
+}
@@ -0,0 +1,29 @@
+// Vertex inputs and outputs shared by the lit programs, drawn one mesh at a time or instanced.
+
+in vec3 position;
+in vec3 normal;
+in vec2 tex_coords;
+in vec4 tangent;
+
+out vec3 v_normal;
+// View space, where the lights are given
+out vec3 v_position;
+out vec2 v_tex_coords;
+out vec3 v_tangent;
+out float v_bitangent_sign;
+// Multiplies the surface color
+out vec3 v_tint;
+
+uniform mat4 perspective;
+uniform mat4 view;
+
+void emit_vertex(mat4 model, vec3 tint) {
+    v_tex_coords = tex_coords;
+    mat4 modelview = view * model;
+    v_normal = transpose(inverse(mat3(modelview))) * normal;
+    v_tangent = mat3(modelview) * tangent.xyz;
+    v_bitangent_sign = tangent.w;
+    v_position = (modelview * vec4(position, 1.0)).xyz;
+    v_tint = tint;
+    gl_Position = perspective * vec4(v_position, 1.0);
+}
@@ -0,0 +1,463 @@
+// Loads textures and models from an asset directory at runtime instead of baking them into the
+// binary, and notices when those files change so they can be reloaded while the demo runs.
+//
+// Changes are found by polling modification times, which is plenty for the handful of files a
+// scene uses and needs no platform-specific file watching.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use glium::backend::Facade;
+use glium::texture::SrgbTexture2d;
+use glium::texture::Texture2d;
+use image::codecs::hdr::HdrDecoder;
+
+use crate::texture;
+use crate::texture::MipmapLevels;
+
+// How often `poll` looks at the files, so it can be called every frame
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ModelFormat {
+    Obj,
+    Gltf,
+    Glb,
+}
+
+impl ModelFormat {
+    /// Recognizes a model file from its first bytes rather than its extension.
+    pub fn detect(header: &[u8]) -> Option<ModelFormat> {
+        if header.starts_with(b"glTF") {
+            return Some(ModelFormat::Glb);
+        }
+
+        let text = String::from_utf8_lossy(header);
+        let text = text.trim_start_matches('\u{feff}').trim_start();
+        if text.starts_with('{') {
+            return Some(ModelFormat::Gltf);
+        }
+
+        // OBJ has no magic number, but every line starts with a known keyword or a comment
+        let first_word = text.split_whitespace().next()?;
+        const OBJ_KEYWORDS: [&str; 10] =
+            ["v", "vt", "vn", "f", "o", "g", "s", "mtllib", "usemtl", "l"];
+        (OBJ_KEYWORDS.contains(&first_word) || first_word.starts_with('#'))
+            .then_some(ModelFormat::Obj)
+    }
+
+    /// Reads the start of the file at `path` and detects its format.
+    pub fn of_file(path: &Path) -> Result<ModelFormat, Box<dyn Error>> {
+        use std::io::Read;
+
+        let mut header = Vec::with_capacity(256);
+        std::fs::File::open(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?
+            .take(256)
+            .read_to_end(&mut header)?;
+        ModelFormat::detect(&header)
+            .ok_or_else(|| format!("{}: not an OBJ or glTF file", path.display()).into())
+    }
+}
+
+/// Decodes an image file, working out its format from the contents.
+pub fn load_image(path: &Path) -> Result<image::DynamicImage, Box<dyn Error>> {
+    let image = image::io::Reader::open(path)
+        .map_err(|e| format!("{}: {}", path.display(), e))?
+        .with_guessed_format()?
+        .decode()
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(image)
+}
+
+/// Decodes an image into linear floating point colors. Radiance `.hdr` files keep their full
+/// range, which `load_image` would clamp; other formats are taken to be sRGB encoded.
+pub fn load_linear_image(path: &Path) -> Result<image::Rgb32FImage, Box<dyn Error>> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    if image::guess_format(&bytes).ok() != Some(image::ImageFormat::Hdr) {
+        let mut image = load_image(path)?.to_rgb32f();
+        for c in image.iter_mut() {
+            *c = texture::srgb_to_linear(*c);
+        }
+        return Ok(image);
+    }
+
+    let decoder =
+        HdrDecoder::new(bytes.as_slice()).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let metadata = decoder.metadata();
+    let pixels = decoder
+        .read_image_hdr()
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    let data = pixels.into_iter().flat_map(|pixel| pixel.0).collect();
+    let image = image::Rgb32FImage::from_raw(metadata.width, metadata.height, data)
+        .ok_or_else(|| format!("{}: truncated image", path.display()))?;
+    Ok(image)
+}
+
+/// Where a texture loaded from a file gets its mipmaps.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Mipmaps {
+    None,
+    #[default]
+    Generated,
+    /// Image files for the levels after the first, looked up like the texture itself.
+    Files(Vec<PathBuf>),
+}
+
+pub struct AssetManager {
+    root: PathBuf,
+    srgb_textures: HashMap<(PathBuf, Mipmaps), Rc<SrgbTexture2d>>,
+    linear_textures: HashMap<(PathBuf, Mipmaps), Rc<Texture2d>>,
+    /// Files to check for changes, with the modification time they had when last loaded.
+    watched: HashMap<PathBuf, Option<SystemTime>>,
+    last_poll: Instant,
+}
+
+impl AssetManager {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        AssetManager {
+            root: root.into(),
+            srgb_textures: HashMap::new(),
+            linear_textures: HashMap::new(),
+            watched: HashMap::new(),
+            last_poll: Instant::now(),
+        }
+    }
+
+    /// The `assets` directory of the source tree.
+    pub fn default_root() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("assets")
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Finds `path` in the asset directory. Paths that are absolute or not found there are used
+    /// as given, relative to the working directory.
+    pub fn resolve(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+        let in_root = self.root.join(path);
+        if path.is_relative() && in_root.exists() {
+            in_root
+        } else {
+            path.to_path_buf()
+        }
+    }
+
+    /// Loads the color texture at `path`, or returns the copy already on the GPU.
+    pub fn srgb_texture<F: Facade>(
+        &mut self,
+        facade: &F,
+        path: impl AsRef<Path>,
+    ) -> Result<Rc<SrgbTexture2d>, Box<dyn Error>> {
+        self.srgb_texture_with_mipmaps(facade, path, &Mipmaps::Generated)
+    }
+
+    /// Like `srgb_texture`, with the mipmaps given. The same file with different mipmaps is
+    /// uploaded once for each.
+    pub fn srgb_texture_with_mipmaps<F: Facade>(
+        &mut self,
+        facade: &F,
+        path: impl AsRef<Path>,
+        mipmaps: &Mipmaps,
+    ) -> Result<Rc<SrgbTexture2d>, Box<dyn Error>> {
+        let key = (self.resolve(path), self.resolve_mipmaps(mipmaps));
+        if let Some(texture) = self.srgb_textures.get(&key) {
+            return Ok(texture.clone());
+        }
+
+        let (image, levels) = self.load_levels(&key.0, &key.1)?;
+        let texture = texture::srgb_texture_with_mipmaps(facade, image, levels)
+            .map_err(|e| format!("{}: {}", key.0.display(), e))?;
+        let texture = Rc::new(texture);
+        self.srgb_textures.insert(key, texture.clone());
+        Ok(texture)
+    }
+
+    /// Loads the data texture at `path`, such as a normal map, or returns the copy already on
+    /// the GPU.
+    pub fn linear_texture<F: Facade>(
+        &mut self,
+        facade: &F,
+        path: impl AsRef<Path>,
+    ) -> Result<Rc<Texture2d>, Box<dyn Error>> {
+        self.linear_texture_with_mipmaps(facade, path, &Mipmaps::Generated)
+    }
+
+    /// Like `linear_texture`, with the mipmaps given.
+    pub fn linear_texture_with_mipmaps<F: Facade>(
+        &mut self,
+        facade: &F,
+        path: impl AsRef<Path>,
+        mipmaps: &Mipmaps,
+    ) -> Result<Rc<Texture2d>, Box<dyn Error>> {
+        let key = (self.resolve(path), self.resolve_mipmaps(mipmaps));
+        if let Some(texture) = self.linear_textures.get(&key) {
+            return Ok(texture.clone());
+        }
+
+        let (image, levels) = self.load_levels(&key.0, &key.1)?;
+        let texture = texture::linear_texture_with_mipmaps(facade, image, levels)
+            .map_err(|e| format!("{}: {}", key.0.display(), e))?;
+        let texture = Rc::new(texture);
+        self.linear_textures.insert(key, texture.clone());
+        Ok(texture)
+    }
+
+    fn resolve_mipmaps(&self, mipmaps: &Mipmaps) -> Mipmaps {
+        match mipmaps {
+            Mipmaps::Files(files) => {
+                Mipmaps::Files(files.iter().map(|f| self.resolve(f)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+
+    // Reads the image at `path` and those of its mipmaps, watching all of them
+    fn load_levels(
+        &mut self,
+        path: &Path,
+        mipmaps: &Mipmaps,
+    ) -> Result<(image::DynamicImage, MipmapLevels), Box<dyn Error>> {
+        self.watch(path);
+        let image = load_image(path)?;
+        let levels = match mipmaps {
+            Mipmaps::None => MipmapLevels::None,
+            Mipmaps::Generated => MipmapLevels::Generated,
+            Mipmaps::Files(files) => {
+                let mut levels = Vec::new();
+                for file in files {
+                    self.watch(file);
+                    levels.push(load_image(file)?);
+                }
+                MipmapLevels::Precomputed(levels)
+            }
+        };
+        Ok((image, levels))
+    }
+
+    /// Reads a text file such as a shader, watching it for changes.
+    pub fn text(&mut self, path: impl AsRef<Path>) -> Result<String, Box<dyn Error>> {
+        let path = self.resolve(path);
+        self.watch(&path);
+        let text =
+            std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(text)
+    }
+
+    /// Starts reporting changes to the file at `path` from `changed_files`.
+    pub fn watch(&mut self, path: &Path) {
+        self.watched.insert(path.to_path_buf(), modified_time(path));
+    }
+
+    /// Returns the watched files that were modified, created or deleted since they were loaded
+    /// or last reported. Cached textures using those files are dropped, so loading them again
+    /// reads the new contents.
+    pub fn changed_files(&mut self) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        for (path, time) in &mut self.watched {
+            let current = modified_time(path);
+            if current != *time {
+                *time = current;
+                changed.push(path.clone());
+            }
+        }
+
+        let uses_changed = |(path, mipmaps): &(PathBuf, Mipmaps)| match mipmaps {
+            Mipmaps::Files(files) => {
+                changed.contains(path) || files.iter().any(|f| changed.contains(f))
+            }
+            _ => changed.contains(path),
+        };
+        self.srgb_textures.retain(|key, _| !uses_changed(key));
+        self.linear_textures.retain(|key, _| !uses_changed(key));
+        changed
+    }
+
+    /// Like `changed_files`, but only looks at the disk every `POLL_INTERVAL` and returns nothing
+    /// in between, so it is cheap to call every frame.
+    pub fn poll(&mut self) -> Vec<PathBuf> {
+        if self.last_poll.elapsed() < POLL_INTERVAL {
+            return Vec::new();
+        }
+        self.last_poll = Instant::now();
+        self.changed_files()
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::File;
+
+    // A scratch directory under `target`, emptied for each test
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("target/asset-tests")
+            .join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn hdr_images_keep_their_range() {
+        let image =
+            load_linear_image(&AssetManager::default_root().join("environment.hdr")).unwrap();
+        assert_eq!(image.dimensions(), (512, 256));
+        // The sun is far brighter than white
+        let brightest = image.pixels().map(|p| p.0[0]).fold(0.0, f32::max);
+        assert!(brightest > 10.0, "{}", brightest);
+
+        let image = load_linear_image(&AssetManager::default_root().join("normal.png")).unwrap();
+        assert!(image
+            .pixels()
+            .all(|p| p.0.iter().all(|c| (0.0..=1.0).contains(c))));
+    }
+
+    #[test]
+    fn model_formats_are_detected_from_contents() {
+        let glb = [b"glTF".as_slice(), &[2, 0, 0, 0]].concat();
+        assert_eq!(ModelFormat::detect(&glb), Some(ModelFormat::Glb));
+        assert_eq!(
+            ModelFormat::detect(b"\n  {\"asset\": {\"version\": \"2.0\"}}"),
+            Some(ModelFormat::Gltf)
+        );
+        assert_eq!(
+            ModelFormat::detect(b"# Blender export\nmtllib a.mtl\n"),
+            Some(ModelFormat::Obj)
+        );
+        assert_eq!(ModelFormat::detect(b"v 0 0 0\n"), Some(ModelFormat::Obj));
+        assert_eq!(ModelFormat::detect(b"\x89PNG\r\n"), None);
+        assert_eq!(ModelFormat::detect(b""), None);
+    }
+
+    #[test]
+    fn images_are_decoded_whatever_their_extension() {
+        let dir = scratch_dir("images");
+        let path = dir.join("texture.dat");
+        image::RgbImage::from_pixel(2, 3, image::Rgb([1, 2, 3]))
+            .save_with_format(&path, image::ImageFormat::Png)
+            .unwrap();
+
+        let image = load_image(&path).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 3));
+    }
+
+    #[test]
+    fn paths_resolve_inside_the_root_first() {
+        let dir = scratch_dir("resolve");
+        std::fs::write(dir.join("present.txt"), "").unwrap();
+        let assets = AssetManager::new(&dir);
+
+        assert_eq!(assets.resolve("present.txt"), dir.join("present.txt"));
+        assert_eq!(assets.resolve("missing.txt"), PathBuf::from("missing.txt"));
+    }
+
+    #[test]
+    fn modified_files_are_reported_once() {
+        let dir = scratch_dir("changes");
+        let path = dir.join("model.obj");
+        std::fs::write(&path, "v 0 0 0\n").unwrap();
+
+        let mut assets = AssetManager::new(&dir);
+        assets.watch(&path);
+        assert!(assets.changed_files().is_empty());
+
+        // Set the time explicitly, file systems may not resolve a quick rewrite
+        let later = SystemTime::now() + Duration::from_secs(10);
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(assets.changed_files(), vec![path.clone()]);
+        assert!(assets.changed_files().is_empty());
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(assets.changed_files(), vec![path]);
+    }
+
+    #[test]
+    fn precomputed_mipmaps_are_checked_and_watched() {
+        let dir = scratch_dir("mipmaps");
+        for (name, size) in [
+            ("base.png", 4),
+            ("half.png", 2),
+            ("quarter.png", 1),
+            ("odd.png", 3),
+        ] {
+            image::RgbImage::from_pixel(size, size, image::Rgb([size as u8, 0, 0]))
+                .save(dir.join(name))
+                .unwrap();
+        }
+        let context = crate::headless::create_context((1, 1)).unwrap();
+        let mut assets = AssetManager::new(&dir);
+        let files = |names: &[&str]| Mipmaps::Files(names.iter().map(PathBuf::from).collect());
+
+        let precomputed = assets
+            .srgb_texture_with_mipmaps(&context, "base.png", &files(&["half.png", "quarter.png"]))
+            .unwrap();
+        assert_eq!(precomputed.get_mipmap_levels(), 3);
+        let single = assets
+            .linear_texture_with_mipmaps(&context, "base.png", &Mipmaps::None)
+            .unwrap();
+        assert_eq!(single.get_mipmap_levels(), 1);
+
+        let error = assets
+            .srgb_texture_with_mipmaps(&context, "base.png", &files(&["odd.png"]))
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .ends_with("mipmap level 1 is 3x3, but should be 2x2"),
+            "{}",
+            error
+        );
+        let error = assets
+            .srgb_texture_with_mipmaps(
+                &context,
+                "base.png",
+                &files(&["half.png", "quarter.png", "quarter.png"]),
+            )
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .ends_with("3 mipmap levels given, but only 2 fit"),
+            "{}",
+            error
+        );
+
+        // A changed level is loaded again, like a changed image
+        let later = SystemTime::now() + Duration::from_secs(10);
+        File::options()
+            .write(true)
+            .open(dir.join("quarter.png"))
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(assets.changed_files(), vec![dir.join("quarter.png")]);
+        let reloaded = assets
+            .srgb_texture_with_mipmaps(&context, "base.png", &files(&["half.png", "quarter.png"]))
+            .unwrap();
+        assert!(!Rc::ptr_eq(&precomputed, &reloaded));
+        let unchanged = assets
+            .linear_texture_with_mipmaps(&context, "base.png", &Mipmaps::None)
+            .unwrap();
+        assert!(Rc::ptr_eq(&single, &unchanged));
+    }
+}
@@ -0,0 +1,217 @@
+// Bounding volumes and tests against the view frustum.
+//
+// Spheres are passed around as a center and a radius. They are cheap to move and to test, but
+// loose around long or flat meshes, so anything a sphere can't rule out is checked again with
+// the mesh's axis-aligned box.
+
+use crate::math::vec3;
+use crate::math::Mat4;
+use crate::math::Vec3;
+use crate::math::Vec4;
+
+/// Axis-aligned bounding box.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// The smallest box containing `points`, or `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Aabb> {
+        points.into_iter().fold(None, |aabb, p| {
+            Some(match aabb {
+                Some(Aabb { min, max }) => Aabb {
+                    min: min.min(p),
+                    max: max.max(p),
+                },
+                None => Aabb { min: p, max: p },
+            })
+        })
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn half_extents(&self) -> Vec3 {
+        (self.max - self.min) / 2.0
+    }
+
+    /// The axis-aligned box around this box moved by `transform`.
+    pub fn transform(&self, transform: &Mat4) -> Aabb {
+        // Each axis of the box adds the absolute value of its transformed half extent
+        let center = transform.transform_point(self.center());
+        let half = self.half_extents();
+        let axes = [Vec3::X * half.x, Vec3::Y * half.y, Vec3::Z * half.z];
+        let extent = axes
+            .map(|axis| abs(transform.transform_vector(axis)))
+            .into_iter()
+            .fold(Vec3::ZERO, |sum, axis| sum + axis);
+        Aabb {
+            min: center - extent,
+            max: center + extent,
+        }
+    }
+}
+
+fn abs(v: Vec3) -> Vec3 {
+    vec3(v.x.abs(), v.y.abs(), v.z.abs())
+}
+
+/// The smallest sphere containing both spheres, each given as center and radius.
+pub fn merge_spheres(a: (Vec3, f32), b: (Vec3, f32)) -> (Vec3, f32) {
+    let offset = b.0 - a.0;
+    let distance = offset.length();
+    if distance + b.1 <= a.1 {
+        return a;
+    }
+    if distance + a.1 <= b.1 {
+        return b;
+    }
+    let radius = (distance + a.1 + b.1) / 2.0;
+    (a.0 + offset * ((radius - a.1) / distance), radius)
+}
+
+/// Moves a sphere given as center and radius by `transform`, growing it by the transform's
+/// largest scale.
+pub fn transform_sphere(transform: &Mat4, sphere: (Vec3, f32)) -> (Vec3, f32) {
+    let scale = [Vec3::X, Vec3::Y, Vec3::Z]
+        .map(|axis| transform.transform_vector(axis).length())
+        .into_iter()
+        .fold(0.0, f32::max);
+    (transform.transform_point(sphere.0), sphere.1 * scale)
+}
+
+/// The volume a camera sees, as six planes facing inwards.
+pub struct Frustum {
+    /// `dot(plane.xyz, p) + plane.w` is the distance of `p` in front of the plane.
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// The frustum of `view_projection`, a projection times a view matrix. Points inside it
+    /// land in the -1 to 1 cube of clip space.
+    pub fn from_matrix(view_projection: &Mat4) -> Self {
+        // Gribb and Hartmann: -w <= x <= w becomes w + x >= 0 and w - x >= 0, and likewise for
+        // y and z, each a plane made of rows of the matrix
+        let m = view_projection;
+        let w = m.row(3);
+        let planes = [
+            w + m.row(0),
+            w - m.row(0),
+            w + m.row(1),
+            w - m.row(1),
+            w + m.row(2),
+            w - m.row(2),
+        ]
+        .map(|plane| plane / plane.truncate().length());
+        Frustum { planes }
+    }
+
+    /// Whether any part of the sphere may be visible.
+    pub fn intersects_sphere(&self, center: Vec3, radius: f32) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.truncate().dot(center) + plane.w >= -radius)
+    }
+
+    /// Whether any part of the box may be visible.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let center = aabb.center();
+        let half = aabb.half_extents();
+        self.planes.iter().all(|plane| {
+            // How far the box reaches towards the plane's normal
+            let normal = plane.truncate();
+            let reach = abs(normal).dot(half);
+            normal.dot(center) + plane.w >= -reach
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f32::consts::FRAC_PI_2;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    // A camera at the origin looking down +Z, seeing 45 degrees to each side, from 1 to 10
+    fn frustum() -> Frustum {
+        let projection = Mat4::perspective(FRAC_PI_2, 1.0, 1.0, 10.0);
+        let view = Mat4::look_to(Vec3::ZERO, Vec3::Z, Vec3::Y);
+        Frustum::from_matrix(&(projection * view))
+    }
+
+    #[test]
+    fn boxes_hold_their_points() {
+        let aabb = Aabb::from_points([vec3(1.0, -2.0, 0.0), vec3(-1.0, 3.0, 0.5)]).unwrap();
+        assert_eq!(aabb.min, vec3(-1.0, -2.0, 0.0));
+        assert_eq!(aabb.max, vec3(1.0, 3.0, 0.5));
+        assert_eq!(Aabb::from_points([]), None);
+    }
+
+    #[test]
+    fn transformed_boxes_contain_the_rotated_corners() {
+        let aabb = Aabb {
+            min: vec3(-1.0, -1.0, -1.0),
+            max: vec3(1.0, 1.0, 1.0),
+        };
+        let transform = Mat4::from_translation(vec3(5.0, 0.0, 0.0))
+            * Mat4::from_rotation_y(std::f32::consts::FRAC_PI_4);
+        let moved = aabb.transform(&transform);
+        let reach = 2f32.sqrt();
+        assert_vec3_eq(moved.min, vec3(5.0 - reach, -1.0, -reach));
+        assert_vec3_eq(moved.max, vec3(5.0 + reach, 1.0, reach));
+    }
+
+    #[test]
+    fn merged_spheres_contain_both() {
+        let a = (vec3(-1.0, 0.0, 0.0), 1.0);
+        let b = (vec3(2.0, 0.0, 0.0), 1.0);
+        let (center, radius) = merge_spheres(a, b);
+        assert_vec3_eq(center, vec3(0.5, 0.0, 0.0));
+        assert!((radius - 2.5).abs() < 1e-5);
+
+        // A sphere inside another one adds nothing
+        assert_eq!(merge_spheres(a, (vec3(-1.0, 0.5, 0.0), 0.2)), a);
+    }
+
+    #[test]
+    fn transformed_spheres_grow_with_the_scale() {
+        let transform =
+            Mat4::from_translation(vec3(0.0, 1.0, 0.0)) * Mat4::from_scale(vec3(1.0, 3.0, 2.0));
+        let (center, radius) = transform_sphere(&transform, (vec3(1.0, 0.0, 0.0), 1.0));
+        assert_vec3_eq(center, vec3(1.0, 1.0, 0.0));
+        assert_eq!(radius, 3.0);
+    }
+
+    #[test]
+    fn spheres_outside_any_plane_are_culled() {
+        let frustum = frustum();
+        assert!(frustum.intersects_sphere(vec3(0.0, 0.0, 5.0), 0.5));
+        // Behind the camera, beyond the far plane, and off to the side
+        assert!(!frustum.intersects_sphere(vec3(0.0, 0.0, -5.0), 0.5));
+        assert!(!frustum.intersects_sphere(vec3(0.0, 0.0, 12.0), 1.0));
+        assert!(!frustum.intersects_sphere(vec3(-8.0, 0.0, 5.0), 1.0));
+        // Straddling the near plane
+        assert!(frustum.intersects_sphere(vec3(0.0, 0.0, 0.5), 1.0));
+    }
+
+    #[test]
+    fn boxes_are_tested_against_the_planes() {
+        let frustum = frustum();
+        let aabb = |min: Vec3, max: Vec3| Aabb { min, max };
+        assert!(frustum.intersects_aabb(&aabb(vec3(-1.0, -1.0, 4.0), vec3(1.0, 1.0, 6.0))));
+        assert!(!frustum.intersects_aabb(&aabb(vec3(-1.0, -1.0, -6.0), vec3(1.0, 1.0, -4.0))));
+
+        // A long thin box just above the view, whose bounding sphere reaches into it
+        let thin = aabb(vec3(-9.0, 5.0, 4.0), vec3(9.0, 5.1, 4.1));
+        assert!(!frustum.intersects_aabb(&thin));
+        let sphere = (thin.center(), thin.half_extents().length());
+        assert!(frustum.intersects_sphere(sphere.0, sphere.1));
+    }
+}
@@ -0,0 +1,371 @@
+// Interactive camera driven by winit window events.
+//
+// The camera is described by the point it looks at, its distance from that point and the
+// yaw/pitch of its view direction. Orbit mode turns the camera around the target, fly mode turns
+// it in place and moves it with the keyboard; both share the same state so switching between
+// them doesn't move the view.
+
+use std::collections::HashSet;
+use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::FRAC_PI_3;
+
+use winit::event::ElementState;
+use winit::event::MouseButton;
+use winit::event::MouseScrollDelta;
+use winit::event::WindowEvent;
+use winit::keyboard::KeyCode;
+use winit::keyboard::PhysicalKey;
+
+use crate::math::vec3;
+use crate::math::Mat4;
+use crate::math::Vec3;
+
+// Radians per pixel of mouse movement
+const ROTATE_SPEED: f32 = 0.005;
+// Distance change per scroll line, as a fraction of the current distance
+const ZOOM_SPEED: f32 = 0.1;
+// Fly speed in world units per second
+const MOVE_SPEED: f32 = 2.0;
+// Keeps the view direction away from straight up or down, where yaw is undefined
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.01;
+const MIN_DISTANCE: f32 = 0.1;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CameraMode {
+    /// Left drag rotates around the target, scrolling zooms and middle drag pans.
+    Orbit,
+    /// Left drag looks around, WASD moves and Q/E move down and up.
+    Fly,
+}
+
+#[derive(Clone, Debug)]
+pub struct Camera {
+    pub mode: CameraMode,
+    target: Vec3,
+    distance: f32,
+    /// Rotation of the view direction around Y; zero looks down +Z.
+    yaw: f32,
+    /// Angle of the view direction above the horizon.
+    pitch: f32,
+    pub fov_y: f32,
+    pub near: f32,
+    pub far: f32,
+    cursor: Option<(f64, f64)>,
+    buttons: HashSet<MouseButton>,
+    keys: HashSet<KeyCode>,
+}
+
+impl Default for Camera {
+    /// The view the demo has always started with.
+    fn default() -> Self {
+        Camera::looking_at(vec3(0.5, 0.2, -3.0), Vec3::ZERO)
+    }
+}
+
+impl Camera {
+    /// An orbit camera at `eye`, turning around `target`.
+    pub fn looking_at(eye: Vec3, target: Vec3) -> Self {
+        let offset = target - eye;
+        let distance = offset.length().max(MIN_DISTANCE);
+        let direction = offset / distance;
+        Camera {
+            mode: CameraMode::Orbit,
+            target,
+            distance,
+            yaw: direction.x.atan2(direction.z),
+            pitch: direction
+                .y
+                .clamp(-1.0, 1.0)
+                .asin()
+                .clamp(-MAX_PITCH, MAX_PITCH),
+            fov_y: FRAC_PI_3,
+            near: 0.1,
+            far: 1024.0,
+            cursor: None,
+            buttons: HashSet::new(),
+            keys: HashSet::new(),
+        }
+    }
+
+    /// Unit vector the camera looks along.
+    pub fn forward(&self) -> Vec3 {
+        let (sin_yaw, cos_yaw) = self.yaw.sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.sin_cos();
+        vec3(cos_pitch * sin_yaw, sin_pitch, cos_pitch * cos_yaw)
+    }
+
+    /// Unit vector to the right of the view, parallel to the ground.
+    pub fn right(&self) -> Vec3 {
+        Vec3::Y.cross(self.forward()).normalize()
+    }
+
+    pub fn position(&self) -> Vec3 {
+        self.target - self.forward() * self.distance
+    }
+
+    pub fn target(&self) -> Vec3 {
+        self.target
+    }
+
+    pub fn view_matrix(&self) -> Mat4 {
+        Mat4::look_to(self.position(), self.forward(), Vec3::Y)
+    }
+
+    pub fn projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
+        Mat4::perspective(self.fov_y, aspect_ratio, self.near, self.far)
+    }
+
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            CameraMode::Orbit => CameraMode::Fly,
+            CameraMode::Fly => CameraMode::Orbit,
+        };
+    }
+
+    /// Turns the view by the given angles. Orbit mode keeps the target in place and moves the
+    /// camera around it, fly mode keeps the camera in place.
+    pub fn rotate(&mut self, yaw: f32, pitch: f32) {
+        let position = self.position();
+        self.yaw += yaw;
+        self.pitch = (self.pitch + pitch).clamp(-MAX_PITCH, MAX_PITCH);
+        if self.mode == CameraMode::Fly {
+            self.target = position + self.forward() * self.distance;
+        }
+    }
+
+    /// Moves towards the target by `lines` scroll steps, or away from it when negative.
+    pub fn zoom(&mut self, lines: f32) {
+        self.distance = (self.distance * (1.0 - ZOOM_SPEED).powf(lines)).max(MIN_DISTANCE);
+    }
+
+    /// Slides the camera and its target sideways by `dx`, `dy` pixels, scaled so the scene
+    /// roughly follows the cursor.
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        let right = self.right();
+        let up = self.forward().cross(right);
+        let scale = self.distance * ROTATE_SPEED;
+        self.target += (up * dy - right * dx) * scale;
+    }
+
+    /// Moves the camera and its target by `offset`, given in view space (X right, Y up,
+    /// Z forward).
+    pub fn translate(&mut self, offset: Vec3) {
+        let forward = self.forward();
+        let right = self.right();
+        let up = forward.cross(right);
+        self.target += right * offset.x + up * offset.y + forward * offset.z;
+    }
+
+    /// Updates the camera from an input event. Returns true if the event was used.
+    pub fn handle_event(&mut self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::MouseInput { state, button, .. } => {
+                match state {
+                    ElementState::Pressed => self.buttons.insert(*button),
+                    ElementState::Released => self.buttons.remove(button),
+                };
+                true
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor_moved(position.x, position.y);
+                true
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.cursor = None;
+                false
+            }
+            WindowEvent::MouseWheel { delta, .. } if self.mode == CameraMode::Orbit => {
+                let lines = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y,
+                    // Roughly one line per 20 pixels, like most platforms
+                    MouseScrollDelta::PixelDelta(position) => position.y as f32 / 20.0,
+                };
+                self.zoom(lines);
+                true
+            }
+            WindowEvent::KeyboardInput { event, .. } => match event.physical_key {
+                PhysicalKey::Code(code) if Self::is_movement_key(code) => {
+                    match event.state {
+                        ElementState::Pressed => self.keys.insert(code),
+                        ElementState::Released => self.keys.remove(&code),
+                    };
+                    self.mode == CameraMode::Fly
+                }
+                _ => false,
+            },
+            WindowEvent::Focused(false) => {
+                // Releases won't arrive while another window has focus
+                self.buttons.clear();
+                self.keys.clear();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn cursor_moved(&mut self, x: f64, y: f64) {
+        if let Some((last_x, last_y)) = self.cursor.replace((x, y)) {
+            let (dx, dy) = ((x - last_x) as f32, (y - last_y) as f32);
+            if self.buttons.contains(&MouseButton::Left) {
+                // Window Y grows downwards
+                self.rotate(dx * ROTATE_SPEED, -dy * ROTATE_SPEED);
+            } else if self.buttons.contains(&MouseButton::Middle) && self.mode == CameraMode::Orbit
+            {
+                self.pan(dx, dy);
+            }
+        }
+    }
+
+    fn is_movement_key(code: KeyCode) -> bool {
+        matches!(
+            code,
+            KeyCode::KeyW
+                | KeyCode::KeyA
+                | KeyCode::KeyS
+                | KeyCode::KeyD
+                | KeyCode::KeyQ
+                | KeyCode::KeyE
+        )
+    }
+
+    /// Advances fly mode movement by `dt` seconds for the keys currently held.
+    pub fn update(&mut self, dt: f32) {
+        if self.mode != CameraMode::Fly {
+            return;
+        }
+
+        let mut direction = Vec3::ZERO;
+        for key in &self.keys {
+            direction += match key {
+                KeyCode::KeyW => Vec3::Z,
+                KeyCode::KeyS => -Vec3::Z,
+                KeyCode::KeyD => Vec3::X,
+                KeyCode::KeyA => -Vec3::X,
+                KeyCode::KeyE => Vec3::Y,
+                KeyCode::KeyQ => -Vec3::Y,
+                _ => Vec3::ZERO,
+            };
+        }
+        if direction.length() > 0.0 {
+            self.translate(direction.normalize() * MOVE_SPEED * dt);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    fn press(camera: &mut Camera, button: MouseButton) {
+        camera.buttons.insert(button);
+    }
+
+    #[test]
+    fn default_matches_the_original_view() {
+        let camera = Camera::default();
+        let expected = Mat4::look_to(vec3(0.5, 0.2, -3.0), vec3(-0.5, -0.2, 3.0), Vec3::Y);
+        let actual = camera.view_matrix();
+        for (a, e) in actual
+            .to_cols_array()
+            .iter()
+            .flatten()
+            .zip(expected.to_cols_array().iter().flatten())
+        {
+            assert!((a - e).abs() < 1e-5, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn orbit_keeps_the_target_and_distance() {
+        let mut camera = Camera::looking_at(vec3(0.0, 0.0, -5.0), Vec3::ZERO);
+        camera.rotate(FRAC_PI_2, 0.0);
+
+        assert_vec3_eq(camera.target(), Vec3::ZERO);
+        assert_vec3_eq(camera.position(), vec3(-5.0, 0.0, 0.0));
+        assert_vec3_eq(camera.forward(), Vec3::X);
+    }
+
+    #[test]
+    fn fly_rotation_keeps_the_position() {
+        let mut camera = Camera::looking_at(vec3(0.0, 0.0, -5.0), Vec3::ZERO);
+        camera.mode = CameraMode::Fly;
+        camera.rotate(FRAC_PI_2, 0.0);
+
+        assert_vec3_eq(camera.position(), vec3(0.0, 0.0, -5.0));
+        assert_vec3_eq(camera.forward(), Vec3::X);
+    }
+
+    #[test]
+    fn pitch_is_clamped() {
+        let mut camera = Camera::default();
+        camera.rotate(0.0, 10.0);
+
+        assert!(camera.forward().y < 1.0);
+        assert!(camera
+            .view_matrix()
+            .to_cols_array()
+            .iter()
+            .flatten()
+            .all(|v| v.is_finite()));
+    }
+
+    #[test]
+    fn zoom_moves_towards_the_target_but_not_through_it() {
+        let mut camera = Camera::looking_at(vec3(0.0, 0.0, -5.0), Vec3::ZERO);
+        camera.zoom(1.0);
+        assert_vec3_eq(camera.position(), vec3(0.0, 0.0, -4.5));
+
+        camera.zoom(1000.0);
+        assert!(camera.position().z < 0.0);
+    }
+
+    #[test]
+    fn pan_moves_camera_and_target_together() {
+        let mut camera = Camera::looking_at(vec3(0.0, 0.0, -5.0), Vec3::ZERO);
+        press(&mut camera, MouseButton::Middle);
+        camera.cursor_moved(100.0, 100.0);
+        camera.cursor_moved(80.0, 100.0);
+
+        // Dragging left moves the scene left, so the camera goes right
+        assert!(camera.target().x > 0.0);
+        assert_vec3_eq(camera.position() - camera.target(), vec3(0.0, 0.0, -5.0));
+    }
+
+    #[test]
+    fn left_drag_rotates() {
+        let mut camera = Camera::looking_at(vec3(0.0, 0.0, -5.0), Vec3::ZERO);
+        camera.cursor_moved(0.0, 0.0);
+        camera.cursor_moved(10.0, 0.0);
+        assert_vec3_eq(camera.forward(), Vec3::Z);
+
+        press(&mut camera, MouseButton::Left);
+        camera.cursor_moved(20.0, 0.0);
+        assert!(camera.forward().x > 0.0);
+    }
+
+    #[test]
+    fn fly_moves_along_the_view() {
+        let mut camera = Camera::looking_at(vec3(0.0, 0.0, -5.0), Vec3::ZERO);
+        camera.keys.insert(KeyCode::KeyW);
+
+        // Orbit mode ignores the keyboard
+        camera.update(1.0);
+        assert_vec3_eq(camera.position(), vec3(0.0, 0.0, -5.0));
+
+        camera.toggle_mode();
+        camera.update(0.5);
+        assert_vec3_eq(camera.position(), vec3(0.0, 0.0, -5.0 + MOVE_SPEED * 0.5));
+
+        camera.keys.clear();
+        camera.keys.insert(KeyCode::KeyD);
+        camera.update(1.0);
+        assert_vec3_eq(
+            camera.position(),
+            vec3(MOVE_SPEED, 0.0, -5.0 + MOVE_SPEED * 0.5),
+        );
+    }
+}
@@ -0,0 +1,128 @@
+// Saving what the window shows: single screenshots, and recordings of every frame drawn.
+//
+// Captures are numbered rather than overwritten, so a run can take several of each. Recordings
+// step the animation by exactly one fixed step per frame, however long saving takes, so the
+// frames play back smoothly at the simulation rate.
+
+use std::error::Error;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use glium::backend::glutin::Display;
+use glium::glutin::surface::WindowSurface;
+use glium::texture::RawImage2d;
+use image::RgbaImage;
+
+/// Turns pixels read back from OpenGL, which stores the bottom row first, into an image with
+/// the top row first.
+pub fn image_from_gl(pixels: RawImage2d<u8>) -> Result<RgbaImage, Box<dyn Error>> {
+    let image = RgbaImage::from_raw(pixels.width, pixels.height, pixels.data.into_owned())
+        .ok_or("framebuffer readback has the wrong size")?;
+    Ok(image::imageops::flip_vertical(&image))
+}
+
+/// The frame the window shows, which is the last one finished.
+pub fn read_front_buffer(display: &Display<WindowSurface>) -> Result<RgbaImage, Box<dyn Error>> {
+    image_from_gl(display.read_front_buffer()?)
+}
+
+/// The first of `dir/name_0000`, `dir/name_0001` and so on, followed by `extension`, that
+/// doesn't exist yet.
+pub fn next_free_path(dir: &Path, name: &str, extension: &str) -> PathBuf {
+    (0..)
+        .map(|i| dir.join(format!("{}_{:04}{}", name, i, extension)))
+        .find(|path| !path.exists())
+        .unwrap()
+}
+
+/// Saves `image` as the next free `screenshot_NNNN.png` in `dir`, and returns its path.
+pub fn save_screenshot(dir: &Path, image: &RgbaImage) -> Result<PathBuf, Box<dyn Error>> {
+    std::fs::create_dir_all(dir)?;
+    let path = next_free_path(dir, "screenshot", ".png");
+    image.save(&path)?;
+    Ok(path)
+}
+
+/// Frames saved as `frame_0000.png` and so on, in a directory of their own.
+pub struct Recording {
+    dir: PathBuf,
+    frames: u32,
+}
+
+impl Recording {
+    /// Starts a recording in the next free `recording_NNNN` directory below `parent`.
+    pub fn start(parent: &Path) -> io::Result<Self> {
+        let dir = next_free_path(parent, "recording", "");
+        std::fs::create_dir_all(&dir)?;
+        Ok(Recording { dir, frames: 0 })
+    }
+
+    pub fn save(&mut self, image: &RgbaImage) -> Result<(), Box<dyn Error>> {
+        image.save(self.dir.join(format!("frame_{:04}.png", self.frames)))?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::borrow::Cow;
+
+    use image::Rgba;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("target/capture-tests")
+            .join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn readbacks_are_flipped() {
+        // One red pixel above one blue one, as OpenGL returns them
+        let pixels = RawImage2d {
+            data: Cow::Owned(vec![0, 0, 255, 255, 255, 0, 0, 255]),
+            width: 1,
+            height: 2,
+            format: glium::texture::ClientFormat::U8U8U8U8,
+        };
+        let image = image_from_gl(pixels).unwrap();
+        assert_eq!(*image.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(*image.get_pixel(0, 1), Rgba([0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn captures_are_numbered() {
+        let dir = test_dir("numbered");
+        let image = RgbaImage::from_pixel(2, 2, Rgba([10, 20, 30, 255]));
+        assert_eq!(
+            save_screenshot(&dir, &image).unwrap(),
+            dir.join("screenshot_0000.png")
+        );
+        assert_eq!(
+            save_screenshot(&dir, &image).unwrap(),
+            dir.join("screenshot_0001.png")
+        );
+
+        let mut first = Recording::start(&dir).unwrap();
+        first.save(&image).unwrap();
+        first.save(&image).unwrap();
+        assert_eq!(first.frames(), 2);
+        assert!(first.dir().join("frame_0001.png").exists());
+        let second = Recording::start(&dir).unwrap();
+        assert_eq!(second.dir(), dir.join("recording_0001"));
+    }
+}
@@ -0,0 +1,377 @@
+// Command-line options.
+//
+// Parsed by hand: there are only a few flags, each taking at most one value, and a failed parse
+// names the flag and the value that was wrong.
+
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::assets::AssetManager;
+use crate::post;
+use crate::post::Antialiasing;
+use crate::post::PostPass;
+use crate::renderer;
+
+pub const USAGE: &str = "\
+Usage: glium_demo [options]
+
+Options:
+  --model NAME       model to show: quad, teapot, teapots, crowd or a model file (default quad)
+  --scene FILE       scene file to show instead of a model
+  --save-scene FILE  write the scene being shown to a scene file
+  --assets DIR       asset directory (default the crate's assets directory)
+  --diffuse PATH     texture for meshes without a material (default diffuse.jpg)
+  --normal PATH      normal map for meshes without a material (default normal.png)
+  --size WxH         window or image size in pixels (default 1024x768)
+  --vsync            wait for the display's refresh between frames
+  --aa MODE          anti-aliasing: none, msaa, fxaa or smaa (default none)
+  --msaa N           samples per pixel for msaa: 2, 4, 8 or 16 (default 4); implies --aa msaa
+  --post PASSES      post-processing passes in the order to apply them, separated by commas:
+                     tone_map, gamma, fxaa, bloom, vignette and color_grade (default none)
+  --lut PATH         color grading table for color_grade (default luts/warm.png)
+  --headless         render to PNG files instead of opening a window
+  --frames N         frames to render with --headless, one simulation step apart (default 1)
+  --out DIR          where --headless saves its frames (default the current directory)
+  --benchmark        time the crowd model with and without instancing
+  --help             show this message";
+
+const FLAGS_WITH_VALUES: [&str; 13] = [
+    "--model",
+    "--scene",
+    "--save-scene",
+    "--assets",
+    "--diffuse",
+    "--normal",
+    "--size",
+    "--aa",
+    "--msaa",
+    "--post",
+    "--lut",
+    "--frames",
+    "--out",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Window,
+    /// Saves `frames` frames as numbered PNGs in `out`.
+    Headless {
+        frames: u32,
+        out: PathBuf,
+    },
+    Benchmark,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    pub mode: Mode,
+    pub model: String,
+    /// Shown instead of `model` when given.
+    pub scene: Option<PathBuf>,
+    pub save_scene: Option<PathBuf>,
+    pub assets: PathBuf,
+    /// Default textures, relative to the asset directory unless absolute.
+    pub diffuse: String,
+    pub normal: String,
+    pub size: (u32, u32),
+    pub vsync: bool,
+    pub antialiasing: Antialiasing,
+    /// Samples per pixel when multisampling, also when switching to it while running.
+    pub msaa: u32,
+    /// Applied in order; none draws straight into the frame.
+    pub post: Vec<PostPass>,
+    /// Relative to the asset directory unless absolute, like the textures.
+    pub lut: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            mode: Mode::Window,
+            model: "quad".to_string(),
+            scene: None,
+            save_scene: None,
+            assets: AssetManager::default_root(),
+            diffuse: renderer::DIFFUSE_TEXTURE.to_string(),
+            normal: renderer::NORMAL_MAP.to_string(),
+            size: (1024, 768),
+            vsync: false,
+            antialiasing: Antialiasing::None,
+            msaa: 4,
+            post: Vec::new(),
+            lut: post::COLOR_GRADE_LUT.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    /// `--help` was given; not a failure, but nothing should run.
+    Help,
+    Unknown(String),
+    MissingValue(&'static str),
+    /// The flag, the value given and what was expected instead.
+    InvalidValue(&'static str, String, &'static str),
+    /// A flag that only makes sense together with another one.
+    Requires(&'static str, &'static str),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Help => write!(f, "{}", USAGE),
+            CliError::Unknown(arg) => write!(f, "unknown argument {}", arg),
+            CliError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            CliError::InvalidValue(flag, value, expected) => {
+                write!(f, "{} {}: expected {}", flag, value, expected)
+            }
+            CliError::Requires(flag, other) => write!(f, "{} only works with {}", flag, other),
+        }
+    }
+}
+
+impl Error for CliError {}
+
+/// Parses the arguments following the program name.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Options, CliError> {
+    let mut options = Options::default();
+    let mut headless = false;
+    let mut benchmark = false;
+    let mut antialiasing = None;
+    let mut samples = None;
+    let mut frames = None;
+    let mut out = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let flag = match arg.as_str() {
+            "--help" | "-h" => return Err(CliError::Help),
+            "--headless" => {
+                headless = true;
+                continue;
+            }
+            "--benchmark" => {
+                benchmark = true;
+                continue;
+            }
+            "--vsync" => {
+                options.vsync = true;
+                continue;
+            }
+            _ => match FLAGS_WITH_VALUES.iter().find(|&&flag| flag == arg) {
+                Some(&flag) => flag,
+                None => return Err(CliError::Unknown(arg)),
+            },
+        };
+        let value = args.next().ok_or(CliError::MissingValue(flag))?;
+        match flag {
+            "--model" => options.model = value,
+            "--scene" => options.scene = Some(PathBuf::from(value)),
+            "--save-scene" => options.save_scene = Some(PathBuf::from(value)),
+            "--assets" => options.assets = PathBuf::from(value),
+            "--diffuse" => options.diffuse = value,
+            "--normal" => options.normal = value,
+            "--size" => {
+                options.size = parse_size(&value).ok_or(CliError::InvalidValue(
+                    flag,
+                    value,
+                    "WIDTHxHEIGHT, such as 800x600",
+                ))?
+            }
+            "--aa" => {
+                antialiasing = Some(match value.as_str() {
+                    "none" => Antialiasing::None,
+                    "msaa" => Antialiasing::Msaa(0),
+                    "fxaa" => Antialiasing::Fxaa,
+                    "smaa" => Antialiasing::Smaa,
+                    _ => {
+                        return Err(CliError::InvalidValue(
+                            flag,
+                            value,
+                            "none, msaa, fxaa or smaa",
+                        ))
+                    }
+                })
+            }
+            "--msaa" => {
+                samples = match value.parse() {
+                    Ok(n @ (2 | 4 | 8 | 16)) => Some(n),
+                    _ => return Err(CliError::InvalidValue(flag, value, "2, 4, 8 or 16")),
+                }
+            }
+            "--post" => {
+                options.post = post::parse_chain(&value).ok_or(CliError::InvalidValue(
+                    flag,
+                    value,
+                    "pass names separated by commas, or none",
+                ))?
+            }
+            "--lut" => options.lut = value,
+            "--frames" => {
+                frames = match value.parse() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => return Err(CliError::InvalidValue(flag, value, "a positive number")),
+                }
+            }
+            "--out" => out = Some(PathBuf::from(value)),
+            _ => unreachable!("{} is listed in FLAGS_WITH_VALUES", flag),
+        }
+    }
+
+    options.msaa = samples.unwrap_or(options.msaa);
+    options.antialiasing = match (antialiasing, samples) {
+        (Some(Antialiasing::Msaa(_)), _) | (None, Some(_)) => Antialiasing::Msaa(options.msaa),
+        (Some(_), Some(_)) => return Err(CliError::Requires("--msaa", "--aa msaa")),
+        (Some(mode), None) => mode,
+        (None, None) => Antialiasing::None,
+    };
+
+    if !headless {
+        if frames.is_some() {
+            return Err(CliError::Requires("--frames", "--headless"));
+        }
+        if out.is_some() {
+            return Err(CliError::Requires("--out", "--headless"));
+        }
+    }
+    options.mode = if benchmark {
+        Mode::Benchmark
+    } else if headless {
+        Mode::Headless {
+            frames: frames.unwrap_or(1),
+            out: out.unwrap_or_else(|| PathBuf::from(".")),
+        }
+    } else {
+        Mode::Window
+    };
+    Ok(options)
+}
+
+/// Reads a size written as `WIDTHxHEIGHT`, neither of them zero.
+fn parse_size(value: &str) -> Option<(u32, u32)> {
+    let (width, height) = value.split_once(['x', 'X'])?;
+    let size = (width.parse().ok()?, height.parse().ok()?);
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Options, CliError> {
+        parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn no_arguments_open_a_window_with_the_defaults() {
+        assert_eq!(parse_str(""), Ok(Options::default()));
+    }
+
+    #[test]
+    fn values_follow_their_flags() {
+        let options = parse_str(
+            "--model teapot --save-scene teapot.json --diffuse wood.png --normal /tmp/bumps.png --size 640x480 --vsync --msaa 4 --post bloom,tone_map,gamma --lut cold.png",
+        )
+        .unwrap();
+        assert_eq!(
+            options,
+            Options {
+                model: "teapot".to_string(),
+                save_scene: Some(PathBuf::from("teapot.json")),
+                diffuse: "wood.png".to_string(),
+                normal: "/tmp/bumps.png".to_string(),
+                size: (640, 480),
+                vsync: true,
+                antialiasing: Antialiasing::Msaa(4),
+                post: vec![PostPass::Bloom, PostPass::ToneMap, PostPass::Gamma],
+                lut: "cold.png".to_string(),
+                ..Options::default()
+            }
+        );
+    }
+
+    #[test]
+    fn headless_runs_take_frames_and_an_output_directory() {
+        let options = parse_str("--headless --frames 30 --out target/frames").unwrap();
+        assert_eq!(
+            options.mode,
+            Mode::Headless {
+                frames: 30,
+                out: PathBuf::from("target/frames")
+            }
+        );
+        // The order of the flags doesn't matter
+        let options = parse_str("--out frames --headless").unwrap();
+        assert_eq!(
+            options.mode,
+            Mode::Headless {
+                frames: 1,
+                out: PathBuf::from("frames")
+            }
+        );
+        assert_eq!(parse_str("--benchmark").unwrap().mode, Mode::Benchmark);
+    }
+
+    #[test]
+    fn antialiasing_modes_and_samples() {
+        let mode = |args| parse_str(args).unwrap().antialiasing;
+        assert_eq!(mode("--aa fxaa"), Antialiasing::Fxaa);
+        assert_eq!(mode("--aa msaa"), Antialiasing::Msaa(4));
+        assert_eq!(mode("--msaa 16"), Antialiasing::Msaa(16));
+        assert_eq!(mode("--msaa 2 --aa msaa"), Antialiasing::Msaa(2));
+        // Kept for switching to multisampling while running
+        assert_eq!(parse_str("--aa smaa").unwrap().msaa, 4);
+    }
+
+    #[test]
+    fn sizes_need_two_positive_numbers() {
+        assert_eq!(parse_size("1920x1080"), Some((1920, 1080)));
+        assert_eq!(parse_size("32X16"), Some((32, 16)));
+        for bad in ["1920", "0x10", "10x", "x10", "-1x10", "ax10", "10x10x10"] {
+            assert_eq!(parse_size(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn mistakes_name_the_flag() {
+        assert_eq!(parse_str("--model"), Err(CliError::MissingValue("--model")));
+        assert_eq!(
+            parse_str("--size big"),
+            Err(CliError::InvalidValue(
+                "--size",
+                "big".to_string(),
+                "WIDTHxHEIGHT, such as 800x600"
+            ))
+        );
+        assert_eq!(
+            parse_str("--msaa 3"),
+            Err(CliError::InvalidValue(
+                "--msaa",
+                "3".to_string(),
+                "2, 4, 8 or 16"
+            ))
+        );
+        assert_eq!(
+            parse_str("--aa smaa --msaa 8"),
+            Err(CliError::Requires("--msaa", "--aa msaa"))
+        );
+        assert!(matches!(
+            parse_str("--post tone_map,blur"),
+            Err(CliError::InvalidValue("--post", _, _))
+        ));
+        assert!(matches!(
+            parse_str("--headless --frames 0"),
+            Err(CliError::InvalidValue("--frames", _, _))
+        ));
+        assert_eq!(
+            parse_str("--frames 10"),
+            Err(CliError::Requires("--frames", "--headless"))
+        );
+        assert_eq!(
+            parse_str("--fullscreen"),
+            Err(CliError::Unknown("--fullscreen".to_string()))
+        );
+        assert_eq!(parse_str("--vsync --help"), Err(CliError::Help));
+    }
+}
@@ -0,0 +1,262 @@
+// Simulation time, advanced in fixed steps.
+//
+// The animation doesn't read the wall clock directly. A `Clock` turns the time that passed on
+// its `TimeSource` into a whole number of fixed steps, carrying the remainder over to the next
+// frame, so the poses drawn depend only on how many steps were taken and not on the frame rate.
+// Tests and headless renders use a `ManualTime` source, which only moves when told to, and get
+// the same frames on every run.
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The demo's simulation rate.
+pub const DEFAULT_STEP: Duration = Duration::from_micros(16_667);
+
+/// At most this many steps are taken per `advance`. After a long stall, such as the window
+/// being dragged, the simulation drops the time it can't catch up on instead of falling
+/// further behind with every slow frame.
+const MAX_STEPS_PER_ADVANCE: u32 = 8;
+
+/// Where a clock gets the passing of real time from.
+pub trait TimeSource {
+    /// Time since some fixed starting point. Never decreases.
+    fn elapsed(&self) -> Duration;
+}
+
+/// The system's monotonic clock.
+pub struct SystemTime {
+    start: Instant,
+}
+
+impl SystemTime {
+    pub fn new() -> Self {
+        SystemTime {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl TimeSource for SystemTime {
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// A time source that stands still until `advance` is called. Clones share the same time.
+#[derive(Clone, Default)]
+pub struct ManualTime {
+    elapsed: Rc<Cell<Duration>>,
+}
+
+impl ManualTime {
+    pub fn new() -> Self {
+        ManualTime::default()
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.elapsed.set(self.elapsed.get() + duration);
+    }
+}
+
+impl TimeSource for ManualTime {
+    fn elapsed(&self) -> Duration {
+        self.elapsed.get()
+    }
+}
+
+pub struct Clock {
+    source: Box<dyn TimeSource>,
+    /// The source's reading at the last `advance`.
+    last_reading: Duration,
+    /// Simulation time, always a whole number of steps.
+    time: Duration,
+    /// Scaled real time not yet turned into steps.
+    pending: Duration,
+    step: Duration,
+    scale: f64,
+    paused: bool,
+    /// Steps requested with `single_step` while paused.
+    queued_steps: u32,
+}
+
+impl Clock {
+    /// A clock at time zero, reading real time from `source` and stepping by `step`.
+    pub fn new(source: impl TimeSource + 'static, step: Duration) -> Self {
+        let last_reading = source.elapsed();
+        Clock {
+            source: Box::new(source),
+            last_reading,
+            time: Duration::ZERO,
+            pending: Duration::ZERO,
+            step,
+            scale: 1.0,
+            paused: false,
+            queued_steps: 0,
+        }
+    }
+
+    /// Reads the time source and moves the simulation forward by the steps that fit in the
+    /// time passed since the last call. Returns how many steps were taken; the simulation
+    /// should be updated that many times.
+    pub fn advance(&mut self) -> u32 {
+        let reading = self.source.elapsed();
+        let real = reading.saturating_sub(self.last_reading);
+        self.last_reading = reading;
+
+        let steps = if self.paused {
+            std::mem::take(&mut self.queued_steps)
+        } else {
+            self.pending += real.mul_f64(self.scale);
+            let available = (self.pending.as_nanos() / self.step.as_nanos()) as u32;
+            let steps = available.min(MAX_STEPS_PER_ADVANCE);
+            self.pending = if steps < available {
+                Duration::ZERO
+            } else {
+                self.pending - self.step * steps
+            };
+            steps
+        };
+        self.time += self.step * steps;
+        steps
+    }
+
+    /// Takes exactly one step, whether paused or not and whatever the scale, and skips the
+    /// real time that passed since the last call. For recording frames at the simulation rate.
+    pub fn step(&mut self) {
+        self.last_reading = self.source.elapsed();
+        self.pending = Duration::ZERO;
+        self.time += self.step;
+    }
+
+    /// Simulation time in seconds.
+    pub fn time(&self) -> f32 {
+        self.time.as_secs_f32()
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stops or restarts the simulation. Real time passing while paused is not made up for.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+        self.pending = Duration::ZERO;
+        self.queued_steps = 0;
+    }
+
+    /// While paused, makes the next `advance` take one step.
+    pub fn single_step(&mut self) {
+        if self.paused {
+            self.queued_steps += 1;
+        }
+    }
+
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Runs the simulation `scale` times as fast as real time.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale.max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: Duration = Duration::from_millis(10);
+
+    fn clock() -> (Clock, ManualTime) {
+        let time = ManualTime::new();
+        (Clock::new(time.clone(), STEP), time)
+    }
+
+    #[test]
+    fn steps_depend_only_on_elapsed_time() {
+        let (mut smooth, smooth_time) = clock();
+        let (mut uneven, uneven_time) = clock();
+
+        let mut smooth_steps = 0;
+        for _ in 0..12 {
+            smooth_time.advance(Duration::from_millis(5));
+            smooth_steps += smooth.advance();
+        }
+        let mut uneven_steps = 0;
+        for ms in [1, 23, 0, 17, 19] {
+            uneven_time.advance(Duration::from_millis(ms));
+            uneven_steps += uneven.advance();
+        }
+
+        assert_eq!(smooth_steps, 6);
+        assert_eq!(uneven_steps, 6);
+        assert_eq!(smooth.time().to_bits(), uneven.time().to_bits());
+    }
+
+    #[test]
+    fn remainders_carry_over() {
+        let (mut clock, time) = clock();
+        time.advance(Duration::from_millis(15));
+        assert_eq!(clock.advance(), 1);
+        time.advance(Duration::from_millis(5));
+        assert_eq!(clock.advance(), 1);
+        assert_eq!(clock.time(), 0.02);
+    }
+
+    #[test]
+    fn scale_speeds_up_and_slows_down() {
+        let (mut clock, time) = clock();
+        clock.set_scale(2.0);
+        time.advance(Duration::from_millis(30));
+        assert_eq!(clock.advance(), 6);
+
+        clock.set_scale(0.5);
+        time.advance(Duration::from_millis(30));
+        assert_eq!(clock.advance(), 1);
+    }
+
+    #[test]
+    fn paused_clocks_only_single_step() {
+        let (mut clock, time) = clock();
+        clock.set_paused(true);
+        time.advance(Duration::from_secs(1));
+        assert_eq!(clock.advance(), 0);
+
+        clock.single_step();
+        clock.single_step();
+        time.advance(Duration::from_secs(1));
+        assert_eq!(clock.advance(), 2);
+        assert_eq!(clock.advance(), 0);
+        assert_eq!(clock.time(), 0.02);
+
+        // The time spent paused is skipped
+        clock.set_paused(false);
+        time.advance(Duration::from_millis(10));
+        assert_eq!(clock.advance(), 1);
+    }
+
+    #[test]
+    fn steps_ignore_the_time_source() {
+        let (mut clock, time) = clock();
+        time.advance(Duration::from_millis(25));
+        clock.step();
+        clock.set_paused(true);
+        clock.step();
+        assert_eq!(clock.time(), 0.02);
+
+        // The time that passed while stepping is not made up for afterwards
+        clock.set_paused(false);
+        time.advance(Duration::from_millis(5));
+        assert_eq!(clock.advance(), 0);
+    }
+
+    #[test]
+    fn long_stalls_are_dropped() {
+        let (mut clock, time) = clock();
+        time.advance(Duration::from_secs(10));
+        assert_eq!(clock.advance(), MAX_STEPS_PER_ADVANCE);
+        assert_eq!(clock.advance(), 0);
+    }
+}
@@ -0,0 +1,303 @@
+// Image-based lighting from an equirectangular HDR panorama.
+//
+// When loading, the panorama is drawn onto the faces of a cubemap, which is also the skybox.
+// From that cubemap three maps are precomputed on the GPU:
+//
+// - an irradiance map, the diffuse light reaching a surface facing each direction;
+// - a specular map whose mip levels hold the reflected environment blurred for increasing
+//   roughness;
+// - a lookup table with the scale and bias that turn a material's reflectance into its
+//   reflection of the specular map, the second half of the split-sum approximation.
+
+use std::error::Error;
+use std::path::Path;
+use std::path::PathBuf;
+
+use glium::backend::Facade;
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::index::NoIndices;
+use glium::index::PrimitiveType;
+use glium::texture::CubeLayer;
+use glium::texture::Cubemap;
+use glium::texture::MipmapsOption;
+use glium::texture::RawImage2d;
+use glium::texture::Texture2d;
+use glium::texture::UncompressedFloatFormat;
+use glium::uniforms::EmptyUniforms;
+use glium::uniforms::MagnifySamplerFilter;
+use glium::uniforms::MinifySamplerFilter;
+use glium::uniforms::SamplerBehavior;
+use glium::uniforms::SamplerWrapFunction;
+use glium::uniforms::UniformValue;
+use glium::uniforms::Uniforms;
+use glium::vertex::EmptyVertexAttributes;
+use glium::DrawParameters;
+use glium::Program;
+use glium::Surface;
+
+use crate::assets;
+use crate::assets::AssetManager;
+use crate::math::Mat3;
+use crate::math::Mat4;
+use crate::shader::ShaderProgram;
+
+const SKYBOX_SIZE: u32 = 256;
+const IRRADIANCE_SIZE: u32 = 16;
+const SPECULAR_SIZE: u32 = 64;
+/// Mip levels of the specular map, from a mirror at level 0 to fully rough at the last one.
+const SPECULAR_LEVELS: u32 = 5;
+const BRDF_LUT_SIZE: u32 = 64;
+
+const CUBE_FACE_SHADER: &str = "shaders/environment/cube_face.vert";
+const FULLSCREEN_SHADER: &str = "shaders/environment/fullscreen.vert";
+const SKYBOX_VERTEX_SHADER: &str = "shaders/environment/skybox.vert";
+const SKYBOX_FRAGMENT_SHADER: &str = "shaders/environment/skybox.frag";
+
+// In the order the `face` uniform of the cube face shader numbers them
+const CUBE_LAYERS: [CubeLayer; 6] = [
+    CubeLayer::PositiveX,
+    CubeLayer::NegativeX,
+    CubeLayer::PositiveY,
+    CubeLayer::NegativeY,
+    CubeLayer::PositiveZ,
+    CubeLayer::NegativeZ,
+];
+
+pub struct Environment {
+    skybox: Cubemap,
+    irradiance: Cubemap,
+    specular: Cubemap,
+    brdf_lut: Texture2d,
+    skybox_program: ShaderProgram,
+    /// The panorama and every shader the maps were computed with.
+    files: Vec<PathBuf>,
+}
+
+impl Environment {
+    /// Loads the panorama at `path`, an `.hdr` or any other image format,
+    /// and precomputes the lighting maps from it.
+    pub fn load<F: Facade>(
+        facade: &F,
+        assets: &mut AssetManager,
+        path: impl AsRef<Path>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let path = assets.resolve(path);
+        assets.watch(&path);
+        let image = assets::load_linear_image(&path)?;
+        let dimensions = image.dimensions();
+        let panorama = Texture2d::with_format(
+            facade,
+            RawImage2d::from_raw_rgb_reversed(image.as_raw(), dimensions),
+            UncompressedFloatFormat::F16F16F16F16,
+            MipmapsOption::AutoGeneratedMipmaps,
+        )?;
+
+        let mut files = vec![path];
+        let mut load_program = |vertex: &str, fragment: &str| {
+            let program = ShaderProgram::load(facade, assets, vertex, fragment)?;
+            files.extend(program.files().iter().cloned());
+            Ok::<_, Box<dyn Error>>(program)
+        };
+        let equirect = load_program(CUBE_FACE_SHADER, "shaders/environment/equirect.frag")?;
+        let irradiance_program =
+            load_program(CUBE_FACE_SHADER, "shaders/environment/irradiance.frag")?;
+        let specular_program = load_program(CUBE_FACE_SHADER, "shaders/environment/specular.frag")?;
+        let brdf_program = load_program(FULLSCREEN_SHADER, "shaders/environment/brdf.frag")?;
+        let skybox_program = load_program(SKYBOX_VERTEX_SHADER, SKYBOX_FRAGMENT_SHADER)?;
+        files.sort();
+        files.dedup();
+
+        // Every level is drawn from the panorama, as glium can't generate mipmaps of a texture
+        // that was rendered to
+        let skybox = Cubemap::empty_with_format(
+            facade,
+            UncompressedFloatFormat::F16F16F16F16,
+            MipmapsOption::EmptyMipmaps,
+            SKYBOX_SIZE,
+        )?;
+        for level in 0..skybox.get_mipmap_levels() {
+            let size = (SKYBOX_SIZE >> level).max(1);
+            // A face spans a quarter of the panorama's width
+            let lod = (dimensions.0 as f32 / (4.0 * size as f32)).log2().max(0.0);
+            let sampler = panorama
+                .sampled()
+                .minify_filter(MinifySamplerFilter::LinearMipmapLinear)
+                .magnify_filter(MagnifySamplerFilter::Linear)
+                .wrap_function(SamplerWrapFunction::Repeat);
+            draw_faces(facade, &skybox, level, equirect.program(), |face| {
+                uniform! { face: face, panorama: sampler, lod: lod }
+            })?;
+        }
+        let environment = skybox
+            .sampled()
+            .minify_filter(MinifySamplerFilter::LinearMipmapLinear);
+
+        let irradiance = Cubemap::empty_with_format(
+            facade,
+            UncompressedFloatFormat::F16F16F16F16,
+            MipmapsOption::NoMipmap,
+            IRRADIANCE_SIZE,
+        )?;
+        let lod = (SKYBOX_SIZE as f32 / IRRADIANCE_SIZE as f32).log2();
+        draw_faces(
+            facade,
+            &irradiance,
+            0,
+            irradiance_program.program(),
+            |face| {
+                uniform! { face: face, environment: environment, lod: lod }
+            },
+        )?;
+
+        let specular = Cubemap::empty_with_format(
+            facade,
+            UncompressedFloatFormat::F16F16F16F16,
+            MipmapsOption::EmptyMipmapsMax(SPECULAR_LEVELS - 1),
+            SPECULAR_SIZE,
+        )?;
+        for level in 0..SPECULAR_LEVELS {
+            let roughness = level as f32 / (SPECULAR_LEVELS - 1) as f32;
+            draw_faces(
+                facade,
+                &specular,
+                level,
+                specular_program.program(),
+                |face| {
+                    uniform! { face: face, environment: environment,
+                    environment_size: SKYBOX_SIZE as f32, roughness: roughness }
+                },
+            )?;
+        }
+
+        let brdf_lut = Texture2d::empty_with_format(
+            facade,
+            UncompressedFloatFormat::F16F16,
+            MipmapsOption::NoMipmap,
+            BRDF_LUT_SIZE,
+            BRDF_LUT_SIZE,
+        )?;
+        SimpleFrameBuffer::new(facade, &brdf_lut)?.draw(
+            EmptyVertexAttributes { len: 3 },
+            NoIndices(PrimitiveType::TrianglesList),
+            brdf_program.program(),
+            &EmptyUniforms,
+            &DrawParameters::default(),
+        )?;
+
+        Ok(Environment {
+            skybox,
+            irradiance,
+            specular,
+            brdf_lut,
+            skybox_program,
+            files,
+        })
+    }
+
+    /// Files the environment was computed from; it should be loaded again when one changes.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Fills `target` with the environment as seen through `view` and `projection`. Meant to
+    /// be drawn first, in place of clearing the color.
+    pub fn draw_skybox<S: Surface>(&self, target: &mut S, view: Mat4, projection: Mat4) {
+        // The environment is infinitely far away, so only the camera's rotation matters
+        let rotation = Mat4::from_mat3(view.to_mat3());
+        let clip_to_world = (projection * rotation).inverse().unwrap_or(Mat4::IDENTITY);
+        target
+            .draw(
+                EmptyVertexAttributes { len: 3 },
+                NoIndices(PrimitiveType::TrianglesList),
+                self.skybox_program.program(),
+                &uniform! { clip_to_world: clip_to_world,
+                environment: self.skybox.sampled().magnify_filter(MagnifySamplerFilter::Linear) },
+                &DrawParameters::default(),
+            )
+            .unwrap();
+    }
+
+    /// Adds the lighting maps to the uniforms in `rest`, with `view` being the view matrix the
+    /// lit shaders work in.
+    pub fn uniforms<U: Uniforms>(&self, view: Mat4, rest: U) -> EnvironmentUniforms<'_, U> {
+        EnvironmentUniforms {
+            environment: self,
+            view,
+            rest,
+        }
+    }
+}
+
+fn draw_faces<F: Facade, U: Uniforms>(
+    facade: &F,
+    cubemap: &Cubemap,
+    level: u32,
+    program: &Program,
+    uniforms: impl Fn(i32) -> U,
+) -> Result<(), Box<dyn Error>> {
+    let mipmap = cubemap.mipmap(level).ok_or("missing cubemap mip level")?;
+    for (face, layer) in CUBE_LAYERS.into_iter().enumerate() {
+        let mut target = SimpleFrameBuffer::new(facade, mipmap.image(layer))?;
+        target.draw(
+            EmptyVertexAttributes { len: 3 },
+            NoIndices(PrimitiveType::TrianglesList),
+            program,
+            &uniforms(face as i32),
+            &DrawParameters::default(),
+        )?;
+    }
+    Ok(())
+}
+
+pub struct EnvironmentUniforms<'a, U> {
+    environment: &'a Environment,
+    view: Mat4,
+    rest: U,
+}
+
+impl<U: Uniforms> Uniforms for EnvironmentUniforms<'_, U> {
+    fn visit_values<'b, F: FnMut(&str, UniformValue<'b>)>(&'b self, mut f: F) {
+        self.rest.visit_values(&mut f);
+
+        let clamp = SamplerBehavior {
+            wrap_function: (
+                SamplerWrapFunction::Clamp,
+                SamplerWrapFunction::Clamp,
+                SamplerWrapFunction::Clamp,
+            ),
+            minify_filter: MinifySamplerFilter::Linear,
+            magnify_filter: MagnifySamplerFilter::Linear,
+            ..Default::default()
+        };
+        let environment = self.environment;
+        f(
+            "irradiance_map",
+            UniformValue::Cubemap(&environment.irradiance, Some(clamp)),
+        );
+        f(
+            "specular_map",
+            UniformValue::Cubemap(
+                &environment.specular,
+                Some(SamplerBehavior {
+                    minify_filter: MinifySamplerFilter::LinearMipmapLinear,
+                    ..clamp
+                }),
+            ),
+        );
+        f(
+            "specular_levels",
+            UniformValue::Float(SPECULAR_LEVELS as f32),
+        );
+        f(
+            "brdf_lut",
+            UniformValue::Texture2d(&environment.brdf_lut, Some(clamp)),
+        );
+
+        // The maps are indexed by world space directions
+        let view_to_world = self.view.to_mat3().inverse().unwrap_or(Mat3::IDENTITY);
+        f(
+            "view_to_world",
+            UniformValue::Mat3(view_to_world.to_cols_array()),
+        );
+    }
+}
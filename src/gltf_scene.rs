@@ -0,0 +1,536 @@
+// glTF 2.0 scene import (`.gltf` with embedded or external buffers, and `.glb`).
+//
+// The scene is flattened into CPU-side data the renderer can upload: nodes with their world
+// transforms, triangle meshes in the common `Vertex` layout, PBR material factors, decoded
+// images and cameras. glTF is right-handed with cameras looking down -Z; `SceneCamera` converts
+// to the renderer's left-handed view space.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use glium::uniforms::MagnifySamplerFilter;
+use glium::uniforms::MinifySamplerFilter;
+use glium::uniforms::SamplerBehavior;
+use glium::uniforms::SamplerWrapFunction;
+
+use crate::math::vec3;
+use crate::math::Mat4;
+use crate::math::Quat;
+use crate::mesh::MeshData;
+use crate::mesh::Vertex;
+use crate::scene::Transform;
+use crate::texture;
+
+pub struct SceneNode {
+    pub name: Option<String>,
+    /// Index into `GltfScene::nodes`; `None` for root nodes.
+    pub parent: Option<usize>,
+    /// Placement relative to the parent.
+    pub transform: Transform,
+    pub world_transform: Mat4,
+    pub mesh: Option<usize>,
+    pub camera: Option<usize>,
+}
+
+pub struct Primitive {
+    pub data: MeshData,
+    pub material: Option<usize>,
+}
+
+pub struct GltfMesh {
+    pub name: Option<String>,
+    pub primitives: Vec<Primitive>,
+}
+
+/// A texture a material reads: one of `GltfScene::images`, and the file's sampler settings
+/// for it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TextureRef {
+    pub image: usize,
+    pub sampler: SamplerBehavior,
+}
+
+/// Metallic-roughness material factors and textures.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PbrMaterial {
+    pub name: Option<String>,
+    pub base_color_factor: [f32; 4],
+    pub base_color_texture: Option<TextureRef>,
+    pub metallic_factor: f32,
+    pub roughness_factor: f32,
+    /// Roughness in the green channel, metalness in the blue one.
+    pub metallic_roughness_texture: Option<TextureRef>,
+    pub emissive_factor: [f32; 3],
+    pub emissive_texture: Option<TextureRef>,
+    pub normal_texture: Option<TextureRef>,
+    /// Ambient occlusion in the red channel.
+    pub occlusion_texture: Option<TextureRef>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Projection {
+    Perspective {
+        yfov: f32,
+        aspect_ratio: Option<f32>,
+        znear: f32,
+        zfar: Option<f32>,
+    },
+    Orthographic {
+        xmag: f32,
+        ymag: f32,
+        znear: f32,
+        zfar: f32,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SceneCamera {
+    pub name: Option<String>,
+    pub projection: Projection,
+}
+
+pub struct GltfScene {
+    /// Nodes of the default scene, parents before their children.
+    pub nodes: Vec<SceneNode>,
+    pub meshes: Vec<GltfMesh>,
+    pub materials: Vec<PbrMaterial>,
+    pub images: Vec<image::DynamicImage>,
+    pub cameras: Vec<SceneCamera>,
+    /// External buffer and image files the scene was read from, besides the glTF file itself.
+    pub external_files: Vec<PathBuf>,
+}
+
+impl GltfScene {
+    /// The first node carrying a camera, with the camera it carries.
+    pub fn first_camera(&self) -> Option<(&SceneNode, &SceneCamera)> {
+        self.nodes
+            .iter()
+            .find_map(|node| node.camera.map(|c| (node, &self.cameras[c])))
+    }
+
+    /// Prints the node hierarchy and materials, for checking what an import produced.
+    pub fn print_summary(&self) {
+        let mut depths = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let depth = node.parent.map_or(0, |p| depths[p] + 1);
+            depths.push(depth);
+
+            let mut line = format!(
+                "{}node {}",
+                "  ".repeat(depth + 1),
+                display_name(&node.name)
+            );
+            if let Some(mesh) = node.mesh {
+                line += &format!(", mesh {}", display_name(&self.meshes[mesh].name));
+            }
+            if let Some(camera) = node.camera {
+                line += &format!(", camera {}", display_name(&self.cameras[camera].name));
+            }
+            println!("{}", line);
+        }
+
+        for material in &self.materials {
+            println!(
+                "  material {}: base color {:?}, metallic {}, roughness {}, emissive {:?}",
+                display_name(&material.name),
+                material.base_color_factor,
+                material.metallic_factor,
+                material.roughness_factor,
+                material.emissive_factor
+            );
+        }
+    }
+}
+
+fn display_name(name: &Option<String>) -> &str {
+    name.as_deref().unwrap_or("(unnamed)")
+}
+
+impl SceneCamera {
+    /// Projection matrix for a target with the given width / height ratio. The camera's own
+    /// aspect ratio wins when the file specifies one.
+    pub fn projection_matrix(&self, aspect: f32) -> Mat4 {
+        match self.projection {
+            Projection::Perspective {
+                yfov,
+                aspect_ratio,
+                znear,
+                zfar,
+            } => Mat4::perspective(
+                yfov,
+                aspect_ratio.unwrap_or(aspect),
+                znear,
+                zfar.unwrap_or(1024.0),
+            ),
+            Projection::Orthographic {
+                xmag,
+                ymag,
+                znear,
+                zfar,
+            } => Mat4::orthographic(-xmag, xmag, -ymag, ymag, znear, zfar),
+        }
+    }
+
+    /// View matrix for a camera placed by `world_transform`.
+    pub fn view_matrix(world_transform: &Mat4) -> Mat4 {
+        // Invert the camera's placement, then flip Z so it looks down +Z like the demo camera
+        let flip = Mat4::from_scale(vec3(1.0, 1.0, -1.0));
+        flip * world_transform.inverse().unwrap_or(Mat4::IDENTITY)
+    }
+}
+
+/// Loads a `.gltf` or `.glb` file along with its buffers and images.
+pub fn load(path: &Path) -> Result<GltfScene, gltf::Error> {
+    let (document, buffers, images) = gltf::import(path)?;
+
+    let mut scene = GltfScene {
+        nodes: Vec::new(),
+        meshes: Vec::new(),
+        materials: Vec::new(),
+        images: images
+            .into_iter()
+            .map(convert_image)
+            .collect::<Result<_, _>>()?,
+        cameras: Vec::new(),
+        external_files: Vec::new(),
+    };
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let buffer_uris = document
+        .buffers()
+        .filter_map(|buffer| match buffer.source() {
+            gltf::buffer::Source::Uri(uri) => Some(uri),
+            gltf::buffer::Source::Bin => None,
+        });
+    let image_uris = document.images().filter_map(|image| match image.source() {
+        gltf::image::Source::Uri { uri, .. } => Some(uri),
+        gltf::image::Source::View { .. } => None,
+    });
+    scene.external_files = buffer_uris
+        .chain(image_uris)
+        .filter(|uri| !uri.starts_with("data:"))
+        .map(|uri| dir.join(uri))
+        .collect();
+
+    for mesh in document.meshes() {
+        let mut primitives = Vec::new();
+        for primitive in mesh.primitives() {
+            // Points and lines have nothing to shade
+            if primitive.mode() != gltf::mesh::Mode::Triangles {
+                continue;
+            }
+            let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+
+            let positions: Vec<[f32; 3]> = match reader.read_positions() {
+                Some(positions) => positions.collect(),
+                None => continue,
+            };
+            let normals: Option<Vec<[f32; 3]>> = reader.read_normals().map(|n| n.collect());
+            let tex_coords: Option<Vec<[f32; 2]>> =
+                reader.read_tex_coords(0).map(|t| t.into_f32().collect());
+            // Tangents are only meaningful together with the normals they were made for
+            let tangents: Option<Vec<[f32; 4]>> = reader
+                .read_tangents()
+                .filter(|_| normals.is_some())
+                .map(|t| t.collect());
+
+            let vertices = positions
+                .iter()
+                .enumerate()
+                .map(|(i, &position)| Vertex {
+                    position,
+                    normal: normals.as_ref().map_or([0.0; 3], |n| n[i]),
+                    // glTF puts the texture origin in the top-left corner, OpenGL bottom-left
+                    tex_coords: tex_coords
+                        .as_ref()
+                        .map_or([0.0; 2], |t| [t[i][0], 1.0 - t[i][1]]),
+                    // Flipping V above mirrors the bitangent
+                    tangent: tangents.as_ref().map_or([0.0; 4], |t| {
+                        let [x, y, z, w] = t[i];
+                        [x, y, z, -w]
+                    }),
+                })
+                .collect();
+            let indices = match reader.read_indices() {
+                Some(indices) => indices.into_u32().collect(),
+                None => (0..positions.len() as u32).collect(),
+            };
+
+            let mut data = MeshData { vertices, indices };
+            if normals.is_none() {
+                data.compute_normals();
+            }
+            if tangents.is_none() {
+                data.compute_tangents();
+            }
+            primitives.push(Primitive {
+                data,
+                material: primitive.material().index(),
+            });
+        }
+        scene.meshes.push(GltfMesh {
+            name: mesh.name().map(str::to_string),
+            primitives,
+        });
+    }
+
+    for material in document.materials() {
+        let pbr = material.pbr_metallic_roughness();
+        scene.materials.push(PbrMaterial {
+            name: material.name().map(str::to_string),
+            base_color_factor: pbr.base_color_factor(),
+            base_color_texture: pbr
+                .base_color_texture()
+                .map(|info| texture_ref(info.texture())),
+            metallic_factor: pbr.metallic_factor(),
+            roughness_factor: pbr.roughness_factor(),
+            metallic_roughness_texture: pbr
+                .metallic_roughness_texture()
+                .map(|info| texture_ref(info.texture())),
+            emissive_factor: material.emissive_factor(),
+            emissive_texture: material
+                .emissive_texture()
+                .map(|info| texture_ref(info.texture())),
+            normal_texture: material
+                .normal_texture()
+                .map(|normal| texture_ref(normal.texture())),
+            occlusion_texture: material
+                .occlusion_texture()
+                .map(|occlusion| texture_ref(occlusion.texture())),
+        });
+    }
+
+    for camera in document.cameras() {
+        let projection = match camera.projection() {
+            gltf::camera::Projection::Perspective(p) => Projection::Perspective {
+                yfov: p.yfov(),
+                aspect_ratio: p.aspect_ratio(),
+                znear: p.znear(),
+                zfar: p.zfar(),
+            },
+            gltf::camera::Projection::Orthographic(o) => Projection::Orthographic {
+                xmag: o.xmag(),
+                ymag: o.ymag(),
+                znear: o.znear(),
+                zfar: o.zfar(),
+            },
+        };
+        scene.cameras.push(SceneCamera {
+            name: camera.name().map(str::to_string),
+            projection,
+        });
+    }
+
+    let roots = document
+        .default_scene()
+        .or_else(|| document.scenes().next());
+    if let Some(roots) = roots {
+        for node in roots.nodes() {
+            add_node(&mut scene.nodes, node, None);
+        }
+    }
+
+    Ok(scene)
+}
+
+fn add_node(nodes: &mut Vec<SceneNode>, node: gltf::Node, parent: Option<usize>) {
+    let (translation, [x, y, z, w], scale) = node.transform().decomposed();
+    let transform = Transform {
+        translation: translation.into(),
+        rotation: Quat { x, y, z, w },
+        scale: scale.into(),
+    };
+    let world_transform = match parent {
+        Some(parent) => nodes[parent].world_transform * transform.matrix(),
+        None => transform.matrix(),
+    };
+
+    nodes.push(SceneNode {
+        name: node.name().map(str::to_string),
+        parent,
+        transform,
+        world_transform,
+        mesh: node.mesh().map(|m| m.index()),
+        camera: node.camera().map(|c| c.index()),
+    });
+
+    let index = nodes.len() - 1;
+    for child in node.children() {
+        add_node(nodes, child, Some(index));
+    }
+}
+
+fn texture_ref(texture: gltf::Texture) -> TextureRef {
+    use gltf::texture::MagFilter;
+    use gltf::texture::MinFilter;
+    use gltf::texture::WrappingMode;
+
+    let wrap = |mode| match mode {
+        WrappingMode::ClampToEdge => SamplerWrapFunction::Clamp,
+        WrappingMode::MirroredRepeat => SamplerWrapFunction::Mirror,
+        WrappingMode::Repeat => SamplerWrapFunction::Repeat,
+    };
+    let sampler = texture.sampler();
+    let mut behavior = SamplerBehavior {
+        wrap_function: (
+            wrap(sampler.wrap_s()),
+            wrap(sampler.wrap_t()),
+            SamplerWrapFunction::Repeat,
+        ),
+        ..texture::DEFAULT_SAMPLER
+    };
+    // Filters the file leaves out are up to the renderer
+    if let Some(filter) = sampler.min_filter() {
+        behavior.minify_filter = match filter {
+            MinFilter::Nearest => MinifySamplerFilter::Nearest,
+            MinFilter::Linear => MinifySamplerFilter::Linear,
+            MinFilter::NearestMipmapNearest => MinifySamplerFilter::NearestMipmapNearest,
+            MinFilter::LinearMipmapNearest => MinifySamplerFilter::LinearMipmapNearest,
+            MinFilter::NearestMipmapLinear => MinifySamplerFilter::NearestMipmapLinear,
+            MinFilter::LinearMipmapLinear => MinifySamplerFilter::LinearMipmapLinear,
+        };
+    }
+    if let Some(filter) = sampler.mag_filter() {
+        behavior.magnify_filter = match filter {
+            MagFilter::Nearest => MagnifySamplerFilter::Nearest,
+            MagFilter::Linear => MagnifySamplerFilter::Linear,
+        };
+    }
+    TextureRef {
+        image: texture.source().index(),
+        sampler: behavior,
+    }
+}
+
+fn convert_image(data: gltf::image::Data) -> Result<image::DynamicImage, gltf::Error> {
+    use gltf::image::Format;
+
+    let (width, height) = (data.width, data.height);
+    let image = match data.format {
+        Format::R8 => image::GrayImage::from_raw(width, height, data.pixels)
+            .map(image::DynamicImage::ImageLuma8),
+        Format::R8G8 => image::GrayAlphaImage::from_raw(width, height, data.pixels)
+            .map(image::DynamicImage::ImageLumaA8),
+        Format::R8G8B8 => image::RgbImage::from_raw(width, height, data.pixels)
+            .map(image::DynamicImage::ImageRgb8),
+        Format::R8G8B8A8 => image::RgbaImage::from_raw(width, height, data.pixels)
+            .map(image::DynamicImage::ImageRgba8),
+        // 16-bit and float images would need a conversion nothing has asked for yet
+        _ => None,
+    };
+    image.ok_or(gltf::Error::UnsupportedImageEncoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::Vec3;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/gltf")
+            .join(name)
+    }
+
+    #[test]
+    fn embedded_buffer_without_indices() {
+        let scene = load(&fixture("TriangleWithoutIndices.gltf")).unwrap();
+        assert_eq!(scene.nodes.len(), 1);
+        assert!(scene.external_files.is_empty());
+        assert_eq!(scene.nodes[0].mesh, Some(0));
+
+        let data = &scene.meshes[0].primitives[0].data;
+        assert_eq!(data.indices, vec![0, 1, 2]);
+        assert_eq!(data.vertices[1].position, [1.0, 0.0, 0.0]);
+
+        // The file has no normals, so they are computed from the triangle
+        assert_eq!(data.vertices[0].normal, [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn external_buffer_with_indices() {
+        let scene = load(&fixture("Triangle.gltf")).unwrap();
+        let data = &scene.meshes[0].primitives[0].data;
+        assert_eq!(data.indices, vec![0, 1, 2]);
+        assert_eq!(data.vertices[2].position, [0.0, 1.0, 0.0]);
+        assert_eq!(scene.meshes[0].primitives[0].material, None);
+        assert_eq!(scene.external_files, vec![fixture("triangle.bin")]);
+    }
+
+    #[test]
+    fn cameras() {
+        let scene = load(&fixture("Cameras.gltf")).unwrap();
+        assert_eq!(scene.cameras.len(), 2);
+        assert!(matches!(
+            scene.cameras[0].projection,
+            Projection::Perspective { yfov, aspect_ratio: Some(_), .. } if yfov == 0.7
+        ));
+        assert!(matches!(
+            scene.cameras[1].projection,
+            Projection::Orthographic { xmag, .. } if xmag == 1.0
+        ));
+
+        let (node, camera) = scene.first_camera().unwrap();
+        assert_eq!(camera, &scene.cameras[0]);
+
+        // The camera sits at z = 3 looking back at the square, which ends up in front of it
+        let view = SceneCamera::view_matrix(&node.world_transform);
+        let square = view.transform_point(vec3(0.5, 0.5, 0.0));
+        assert!((square - vec3(0.0, 0.0, 3.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn glb_with_hierarchy_and_texture() {
+        let scene = load(&fixture("BoxTextured.glb")).unwrap();
+
+        assert_eq!(scene.nodes.len(), 2);
+        assert_eq!(scene.nodes[0].name.as_deref(), Some("root"));
+        assert_eq!(scene.nodes[1].parent, Some(0));
+        assert_eq!(scene.nodes[1].mesh, Some(0));
+
+        // The child inherits the parent's rotation of -90 degrees around X
+        let up = scene.nodes[1].world_transform.transform_vector(Vec3::Y);
+        assert!((up - vec3(0.0, 0.0, -1.0)).length() < 1e-5);
+
+        let material = &scene.materials[0];
+        assert_eq!(material.name.as_deref(), Some("Textured"));
+        assert_eq!(material.base_color_factor, [1.0, 1.0, 1.0, 1.0]);
+        let base_color = material.base_color_texture.unwrap();
+        assert_eq!(base_color.image, 0);
+        // The file's sampler blends between mipmap levels but not texels, and repeats the texture
+        assert_eq!(
+            base_color.sampler.minify_filter,
+            MinifySamplerFilter::NearestMipmapLinear
+        );
+        assert_eq!(
+            base_color.sampler.magnify_filter,
+            MagnifySamplerFilter::Linear
+        );
+        assert_eq!(
+            base_color.sampler.wrap_function.0,
+            SamplerWrapFunction::Repeat
+        );
+        assert_eq!(material.metallic_factor, 0.0);
+        assert_eq!(material.roughness_factor, 0.8);
+        assert_eq!(material.normal_texture, None);
+        assert_eq!(material.metallic_roughness_texture, None);
+        assert_eq!(material.occlusion_texture, None);
+        assert_eq!(material.emissive_texture, None);
+
+        assert_eq!(scene.images.len(), 1);
+        assert_eq!((scene.images[0].width(), scene.images[0].height()), (4, 4));
+
+        let primitive = &scene.meshes[0].primitives[0];
+        assert_eq!(primitive.material, Some(0));
+        assert_eq!(primitive.data.indices.len(), 36);
+
+        // No tangents in the file, so they are computed from the texture coordinates
+        for vertex in &primitive.data.vertices {
+            let normal = Vec3::from(vertex.normal);
+            let tangent = vec3(vertex.tangent[0], vertex.tangent[1], vertex.tangent[2]);
+            assert!(normal.dot(tangent).abs() < 1e-5);
+            assert!((tangent.length() - 1.0).abs() < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,539 @@
+// Golden-image regression tests: fixed scenes are rendered headlessly at a fixed time and
+// compared against the reference PNGs in `tests/golden`.
+//
+// Run with `UPDATE_GOLDEN=1 cargo test` to (re)write the references after an intended change.
+// When a comparison fails, an image highlighting the differing pixels is written to
+// `target/golden-diff`.
+
+use std::fs::File;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use image::Rgba;
+use image::RgbaImage;
+
+use crate::assets::AssetManager;
+use crate::camera::Camera;
+use crate::clock;
+use crate::clock::Clock;
+use crate::clock::ManualTime;
+use crate::headless;
+use crate::light;
+use crate::material::ShadingModel;
+use crate::post;
+use crate::post::Antialiasing;
+use crate::post::PostPass;
+use crate::renderer::Renderer;
+use crate::renderer::TangentMode;
+use crate::scene_file;
+use crate::scene_file::Background;
+use crate::scene_file::MaterialDescription;
+use crate::scene_file::SceneDescription;
+use crate::scene_file::Shading;
+use crate::scene_file::TextureDescription;
+
+const DIMENSIONS: (u32, u32) = (256, 192);
+
+// How far a single channel may drift before the pixel counts as different. Software and
+// hardware rasterizers disagree slightly on texture filtering and derivatives.
+const TOLERANCE: u8 = 3;
+
+struct Comparison {
+    mismatched: usize,
+    max_difference: u8,
+    diff: RgbaImage,
+}
+
+fn compare(actual: &RgbaImage, expected: &RgbaImage, tolerance: u8) -> Comparison {
+    let mut mismatched = 0;
+    let mut max_difference = 0;
+    let mut diff = RgbaImage::new(actual.width(), actual.height());
+
+    for (x, y, pixel) in actual.enumerate_pixels() {
+        let reference = expected.get_pixel(x, y);
+        let difference = pixel
+            .0
+            .iter()
+            .zip(reference.0.iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap();
+        max_difference = max_difference.max(difference);
+
+        let out = if difference > tolerance {
+            mismatched += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            // Dimmed copy of the reference so the red pixels can be located
+            let [r, g, b, _] = reference.0;
+            Rgba([r / 4, g / 4, b / 4, 255])
+        };
+        diff.put_pixel(x, y, out);
+    }
+
+    Comparison {
+        mismatched,
+        max_difference,
+        diff,
+    }
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.png", name))
+}
+
+fn diff_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("target/golden-diff")
+        .join(format!("{}.png", name))
+}
+
+/// Compares `actual` with the reference image called `name`, panicking with a description of
+/// the difference when they don't match.
+fn assert_golden(name: &str, actual: &RgbaImage) {
+    let path = golden_path(name);
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        actual.save(&path).unwrap();
+        return;
+    }
+
+    let expected = image::open(&path)
+        .unwrap_or_else(|e| {
+            panic!(
+                "could not open reference image {} ({}); run with UPDATE_GOLDEN=1 to create it",
+                path.display(),
+                e
+            )
+        })
+        .to_rgba8();
+    assert_eq!(
+        actual.dimensions(),
+        expected.dimensions(),
+        "{}: rendered image has a different size than the reference",
+        name
+    );
+
+    let comparison = compare(actual, &expected, TOLERANCE);
+    if comparison.mismatched > 0 {
+        let diff = diff_path(name);
+        std::fs::create_dir_all(diff.parent().unwrap()).unwrap();
+        comparison.diff.save(&diff).unwrap();
+        actual.save(diff.with_extension("actual.png")).unwrap();
+
+        panic!(
+            "{}: {} pixels differ from the reference (largest channel difference {}); diff written to {}",
+            name,
+            comparison.mismatched,
+            comparison.max_difference,
+            diff.display()
+        );
+    }
+}
+
+fn render(model: &str, t: f32) -> RgbaImage {
+    render_with(model, t, |_| {})
+}
+
+/// Renders `model` after `configure` has changed the renderer's settings.
+fn render_with(model: &str, t: f32, configure: impl FnOnce(&mut Renderer)) -> RgbaImage {
+    let context = headless::create_context(DIMENSIONS).unwrap();
+    let assets = AssetManager::new(AssetManager::default_root());
+    let mut renderer = Renderer::new(&context, assets).unwrap();
+    assert!(renderer.select_model(model), "unknown model {}", model);
+    configure(&mut renderer);
+    renderer.update(t);
+    headless::render_frame(&context, &renderer, &Camera::default(), DIMENSIONS).unwrap()
+}
+
+/// Renders the model built from `scene`, seen through the scene's camera.
+fn render_scene(scene: &SceneDescription, t: f32) -> RgbaImage {
+    let context = headless::create_context(DIMENSIONS).unwrap();
+    let assets = AssetManager::new(AssetManager::default_root());
+    let mut renderer = Renderer::new(&context, assets).unwrap();
+    renderer.show_scene(&context, "scene", scene, None).unwrap();
+    renderer.update(t);
+    let camera = scene
+        .camera
+        .as_ref()
+        .map_or_else(Camera::default, |c| c.camera());
+    headless::render_frame(&context, &renderer, &camera, DIMENSIONS).unwrap()
+}
+
+#[test]
+fn compare_accepts_differences_within_tolerance() {
+    let a = RgbaImage::from_pixel(2, 2, Rgba([100, 100, 100, 255]));
+    let b = RgbaImage::from_pixel(2, 2, Rgba([102, 98, 100, 255]));
+
+    let comparison = compare(&a, &b, 2);
+    assert_eq!(comparison.mismatched, 0);
+    assert_eq!(comparison.max_difference, 2);
+}
+
+#[test]
+fn compare_marks_differing_pixels() {
+    let a = RgbaImage::from_pixel(2, 2, Rgba([100, 100, 100, 255]));
+    let mut b = a.clone();
+    b.put_pixel(1, 0, Rgba([0, 100, 100, 255]));
+
+    let comparison = compare(&a, &b, 2);
+    assert_eq!(comparison.mismatched, 1);
+    assert_eq!(comparison.max_difference, 100);
+    assert_eq!(*comparison.diff.get_pixel(1, 0), Rgba([255, 0, 0, 255]));
+    assert_eq!(*comparison.diff.get_pixel(0, 0), Rgba([25, 25, 25, 255]));
+}
+
+#[test]
+fn quad_at_rest() {
+    assert_golden("quad_t0", &render("quad", 0.0));
+}
+
+#[test]
+fn quad_rotated() {
+    assert_golden("quad_t1", &render("quad", 1.0));
+}
+
+#[test]
+fn teapot_at_rest() {
+    assert_golden("teapot_t0", &render("teapot", 0.0));
+}
+
+#[test]
+fn teapot_rotated() {
+    assert_golden("teapot_t1", &render("teapot", 1.0));
+}
+
+#[test]
+fn quad_vertex_tangents() {
+    let image = render_with("quad", 1.0, |r| r.set_tangent_mode(TangentMode::Vertex));
+    assert_golden("quad_t1_vertex_tangents", &image);
+}
+
+#[test]
+fn teapot_vertex_tangents() {
+    let image = render_with("teapot", 1.0, |r| r.set_tangent_mode(TangentMode::Vertex));
+    assert_golden("teapot_t1_vertex_tangents", &image);
+}
+
+#[test]
+fn teapot_point_and_spot_lights() {
+    let image = render_with("teapot", 1.0, |r| r.set_lights(light::lamp_lights()));
+    assert_golden("teapot_t1_lamps", &image);
+}
+
+#[test]
+fn teapot_pbr() {
+    let image = render_with("teapot", 1.0, |r| {
+        r.set_default_shading(ShadingModel::Pbr).unwrap()
+    });
+    assert_golden("teapot_t1_pbr", &image);
+}
+
+#[test]
+fn teapot_pbr_point_and_spot_lights() {
+    let image = render_with("teapot", 1.0, |r| {
+        r.set_default_shading(ShadingModel::Pbr).unwrap();
+        r.set_lights(light::lamp_lights());
+    });
+    assert_golden("teapot_t1_pbr_lamps", &image);
+}
+
+#[test]
+fn composed_teapots() {
+    assert_golden("teapots_t1", &render("teapots", 1.0));
+}
+
+#[test]
+fn crowd() {
+    assert_golden("crowd_t1", &render("crowd", 1.0));
+}
+
+#[test]
+fn instancing_matches_one_draw_call_per_copy() {
+    let separate = render_with("crowd", 1.0, |r| r.set_instancing(false));
+    let comparison = compare(&render("crowd", 1.0), &separate, TOLERANCE);
+    assert_eq!(comparison.mismatched, 0, "{}", comparison.max_difference);
+}
+
+#[test]
+fn frame_pacing_does_not_change_the_image() {
+    // One second of animation, once at 50 frames per second and once with uneven frames
+    let render_paced = |frames: &[u64]| {
+        let time = ManualTime::new();
+        let mut clock = Clock::new(time.clone(), clock::DEFAULT_STEP);
+        for &ms in frames {
+            time.advance(std::time::Duration::from_millis(ms));
+            clock.advance();
+        }
+        render("teapots", clock.time())
+    };
+    let smooth = render_paced(&[20; 50]);
+    let uneven = render_paced(&[5, 90, 110, 85, 40, 100, 110, 70, 115, 105, 110, 60]);
+    assert_eq!(smooth.as_raw(), uneven.as_raw());
+}
+
+#[test]
+fn example_scene_file() {
+    let path = AssetManager::default_root().join("scenes/example.json");
+    let scene = scene_file::load(&path).unwrap();
+    assert_golden("scene_example_t1", &render_scene(&scene, 1.0));
+}
+
+#[test]
+fn described_quad_matches_the_built_in_one() {
+    let material = MaterialDescription {
+        shading: Shading::BlinnPhong,
+        base_color: Some(TextureDescription::new("diffuse.jpg")),
+        normal_map: Some(TextureDescription::new("normal.png")),
+        ..MaterialDescription::default()
+    };
+    let scene = SceneDescription::single_model(
+        "quad",
+        Some(material),
+        &light::default_lights(),
+        &Camera::default(),
+    );
+    let comparison = compare(&render_scene(&scene, 1.0), &render("quad", 1.0), TOLERANCE);
+    assert_eq!(comparison.mismatched, 0, "{}", comparison.max_difference);
+}
+
+#[test]
+fn unknown_scene_material_is_an_error() {
+    let mut scene = floor_scene(r#""diffuse.jpg""#);
+    scene.nodes[0].material = Some("missing".to_string());
+    let context = headless::create_context(DIMENSIONS).unwrap();
+    let assets = AssetManager::new(AssetManager::default_root());
+    let mut renderer = Renderer::new(&context, assets).unwrap();
+    let error = renderer
+        .show_scene(&context, "scene", &scene, None)
+        .unwrap_err();
+    assert!(
+        error.to_string().starts_with("nodes[0].material"),
+        "{}",
+        error
+    );
+}
+
+/// A floor stretching away from a low camera, colored with `base_color`, the JSON of a texture.
+fn floor_scene(base_color: &str) -> SceneDescription {
+    let source = format!(
+        r#"{{
+            "camera": {{ "eye": [0, -0.3, -5.5], "target": [0, -0.5, 0], "fov_y": 45,
+                        "near": 0.1, "far": 50 }},
+            "background": {{ "color": [0.1, 0.1, 0.12] }},
+            "lights": [{{ "type": "directional", "direction": [0, -1, 0.3], "color": [1, 1, 1],
+                         "intensity": 1 }}],
+            "materials": {{ "floor": {{ "shading": "blinn_phong", "base_color": {} }} }},
+            "nodes": [{{ "name": "floor", "mesh": "quad", "material": "floor",
+                        "translation": [0, -0.5, 0], "rotation": [0.7071068, 0, 0, 0.7071068],
+                        "scale": [6, 6, 1] }}]
+        }}"#,
+        base_color
+    );
+    let scene = scene_file::parse(&source).unwrap();
+    scene.validate().unwrap();
+    scene
+}
+
+#[test]
+fn floor_anisotropic_filtering() {
+    let trilinear = render_scene(&floor_scene(r#""diffuse.jpg""#), 0.0);
+    let anisotropic = render_scene(
+        &floor_scene(r#"{ "path": "diffuse.jpg", "anisotropy": 16 }"#),
+        0.0,
+    );
+    assert_golden("floor_trilinear", &trilinear);
+    assert_golden("floor_anisotropic", &anisotropic);
+
+    // Only the far part of the floor, seen at a grazing angle, is sharper
+    let comparison = compare(&anisotropic, &trilinear, TOLERANCE);
+    assert!(comparison.mismatched > 0);
+}
+
+// Marks `path` as changed without relying on the file system noticing a quick rewrite
+fn touch(path: &Path) {
+    let later = SystemTime::now() + Duration::from_secs(10);
+    File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+}
+
+#[test]
+fn scenes_are_rebuilt_when_their_files_change() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/golden-scene-reload");
+    std::fs::create_dir_all(&dir).unwrap();
+    let texture = dir.join("floor.png");
+    let scene_path = dir.join("scene.json");
+    let solid = |color| image::RgbImage::from_pixel(4, 4, image::Rgb(color));
+    solid([255, 0, 0]).save(&texture).unwrap();
+    let mut scene = floor_scene(&format!("{:?}", texture.display().to_string()));
+    scene.save(&scene_path).unwrap();
+
+    let context = headless::create_context(DIMENSIONS).unwrap();
+    let assets = AssetManager::new(AssetManager::default_root());
+    let mut renderer = Renderer::new(&context, assets).unwrap();
+    renderer
+        .show_scene(&context, "scene", &scene, Some(&scene_path))
+        .unwrap();
+    let camera = scene.camera.as_ref().unwrap().camera();
+    let render = |renderer: &Renderer| {
+        headless::render_frame(&context, renderer, &camera, DIMENSIONS).unwrap()
+    };
+    // Changes are only looked for a few times a second
+    let reload = |renderer: &mut Renderer| {
+        std::thread::sleep(Duration::from_millis(600));
+        renderer.reload_changed_assets(&context);
+    };
+    let red = render(&renderer);
+
+    solid([0, 0, 255]).save(&texture).unwrap();
+    touch(&texture);
+    reload(&mut renderer);
+    let blue = render(&renderer);
+    assert!(compare(&blue, &red, TOLERANCE).mismatched > 0);
+
+    scene.background = Background::Color([1.0, 1.0, 0.0]);
+    scene.save(&scene_path).unwrap();
+    touch(&scene_path);
+    reload(&mut renderer);
+    let yellow = render(&renderer);
+    assert!(compare(&yellow, &blue, TOLERANCE).mismatched > 0);
+}
+
+#[test]
+fn precomputed_mipmaps_are_drawn_at_a_distance() {
+    // A white image whose levels are red, green and then blue
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/golden-mipmaps");
+    std::fs::create_dir_all(&dir).unwrap();
+    let colors = [[255, 255, 255], [255, 0, 0], [0, 255, 0], [0, 0, 255]];
+    let mut levels = Vec::new();
+    for (level, color) in colors.into_iter().enumerate() {
+        let size = 64 >> level;
+        let path = dir.join(format!("level_{}.png", level));
+        image::RgbImage::from_pixel(size, size, image::Rgb(color))
+            .save(&path)
+            .unwrap();
+        levels.push(format!("{:?}", path.display().to_string()));
+    }
+    let texture = |mipmaps: &str| {
+        format!(
+            r#"{{ "path": {}, "min_filter": "nearest_mipmap_nearest", "mipmaps": {} }}"#,
+            levels[0], mipmaps
+        )
+    };
+    let precomputed = texture(&format!(r#"{{ "files": [{}] }}"#, levels[1..].join(", ")));
+
+    // Far enough away that the quad covers about a quarter as many pixels as the image has
+    // texels across, so the third level is read
+    let far = |base_color: &str| {
+        let mut scene = floor_scene(base_color);
+        scene.camera.as_mut().unwrap().eye = [0.0, 0.0, -32.0];
+        scene.camera.as_mut().unwrap().target = [0.0, 0.0, 0.0];
+        scene.nodes[0].translation = [0.0; 3];
+        scene.nodes[0].rotation = [0.0, 0.0, 0.0, 1.0];
+        scene.nodes[0].scale = [1.0; 3];
+        render_scene(&scene, 0.0)
+    };
+    let center = |image: RgbaImage| *image.get_pixel(DIMENSIONS.0 / 2, DIMENSIONS.1 / 2);
+    let is_green = |Rgba([r, g, b, _]): Rgba<u8>| g > r && g > b;
+
+    let pixel = center(far(&precomputed));
+    assert!(is_green(pixel), "{:?}", pixel);
+    // Generated levels are white like the image, though lit a little blue
+    let pixel = center(far(&texture(r#""generated""#)));
+    assert!(!is_green(pixel) && pixel[0] > 100, "{:?}", pixel);
+}
+
+#[test]
+fn teapot_shadows_itself() {
+    let unshadowed = render_with("teapot", 1.0, |r| {
+        let lights = light::default_lights()
+            .into_iter()
+            .map(|l| light::Light {
+                casts_shadows: false,
+                ..l
+            })
+            .collect();
+        r.set_lights(lights);
+    });
+
+    // Parts of the teapot fall in the shadow of others; the rest should be unchanged
+    let comparison = compare(&render("teapot", 1.0), &unshadowed, TOLERANCE);
+    assert!(comparison.mismatched > 100, "{}", comparison.mismatched);
+    assert!(comparison.mismatched < 5000, "{}", comparison.mismatched);
+}
+
+#[test]
+fn teapots_post_processed() {
+    let image = render_with("teapots", 1.0, |r| {
+        r.set_post_chain(post::DEFAULT_CHAIN.to_vec())
+    });
+    assert_golden("teapots_t1_post", &image);
+}
+
+#[test]
+fn every_post_pass_changes_the_image() {
+    let plain = render("teapots", 1.0);
+    // Encoding is left to the frame unless a `gamma` pass did it, so that pass alone changes
+    // nothing; `gamma_pass_encodes_like_the_frame` covers it
+    for pass in PostPass::ALL.into_iter().filter(|&p| p != PostPass::Gamma) {
+        let image = render_with("teapots", 1.0, |r| r.set_post_chain(vec![pass]));
+        let comparison = compare(&image, &plain, TOLERANCE);
+        assert!(comparison.mismatched > 0, "{:?}", pass);
+    }
+}
+
+#[test]
+fn gamma_pass_encodes_like_the_frame() {
+    let plain = render("teapots", 1.0);
+    let encoded = render_with("teapots", 1.0, |r| r.set_post_chain(vec![PostPass::Gamma]));
+    let comparison = compare(&encoded, &plain, TOLERANCE);
+    assert_eq!(comparison.mismatched, 0, "{}", comparison.max_difference);
+}
+
+#[test]
+fn teapot_msaa() {
+    let image = render_with("teapot", 1.0, |r| r.set_antialiasing(Antialiasing::Msaa(4)));
+    assert_golden("teapot_t1_msaa", &image);
+}
+
+#[test]
+fn teapot_fxaa() {
+    let image = render_with("teapot", 1.0, |r| r.set_antialiasing(Antialiasing::Fxaa));
+    assert_golden("teapot_t1_fxaa", &image);
+}
+
+#[test]
+fn teapot_smaa() {
+    let image = render_with("teapot", 1.0, |r| r.set_antialiasing(Antialiasing::Smaa));
+    assert_golden("teapot_t1_smaa", &image);
+}
+
+#[test]
+fn antialiasing_only_changes_edges() {
+    let aliased = render("teapot", 1.0);
+    let pixels = (DIMENSIONS.0 * DIMENSIONS.1) as usize;
+    for mode in [
+        Antialiasing::Msaa(4),
+        Antialiasing::Fxaa,
+        Antialiasing::Smaa,
+    ] {
+        let image = render_with("teapot", 1.0, |r| r.set_antialiasing(mode));
+        let comparison = compare(&image, &aliased, TOLERANCE);
+        assert!(comparison.mismatched > 0, "{:?}", mode);
+        assert!(
+            comparison.mismatched < pixels / 20,
+            "{:?}: {}",
+            mode,
+            comparison.mismatched
+        );
+    }
+}
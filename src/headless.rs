@@ -0,0 +1,175 @@
+// Offscreen rendering without a window, for machines that have neither a GPU nor a display.
+// The OpenGL context comes from a surfaceless EGL display, which Mesa backs with its software
+// rasterizer when no hardware device is available.
+
+use std::error::Error;
+use std::ffi::CString;
+use std::os::raw::c_void;
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Instant;
+
+use glium::backend::Backend;
+use glium::backend::Context;
+use glium::backend::Facade;
+use glium::debug::DebugCallbackBehavior;
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::glutin::api::egl::context::PossiblyCurrentContext;
+use glium::glutin::api::egl::device::Device;
+use glium::glutin::api::egl::display::Display;
+use glium::glutin::config::ConfigSurfaceTypes;
+use glium::glutin::config::ConfigTemplateBuilder;
+use glium::glutin::context::ContextApi;
+use glium::glutin::context::ContextAttributesBuilder;
+use glium::glutin::context::Version;
+use glium::glutin::prelude::*;
+use glium::texture::DepthTexture2d;
+use glium::texture::SrgbTexture2d;
+
+use crate::assets::AssetManager;
+use crate::camera::Camera;
+use crate::capture;
+use crate::clock;
+use crate::clock::Clock;
+use crate::clock::ManualTime;
+use crate::renderer::Renderer;
+
+struct HeadlessBackend {
+    context: PossiblyCurrentContext,
+    display: Display,
+    dimensions: (u32, u32),
+}
+
+unsafe impl Backend for HeadlessBackend {
+    fn swap_buffers(&self) -> Result<(), glium::SwapBuffersError> {
+        // There is no default framebuffer to present
+        Ok(())
+    }
+
+    unsafe fn get_proc_address(&self, symbol: &str) -> *const c_void {
+        let symbol = CString::new(symbol).unwrap();
+        self.display.get_proc_address(&symbol)
+    }
+
+    fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+        self.dimensions
+    }
+
+    fn resize(&self, _new_size: (u32, u32)) {}
+
+    fn is_current(&self) -> bool {
+        self.context.is_current()
+    }
+
+    unsafe fn make_current(&self) {
+        self.context.make_current_surfaceless().unwrap();
+    }
+}
+
+/// Creates a glium context that is not attached to any window.
+///
+/// `dimensions` is only reported back to glium; draws have to target an offscreen framebuffer.
+pub fn create_context(dimensions: (u32, u32)) -> Result<Rc<Context>, Box<dyn Error>> {
+    let device = Device::query_devices()?
+        .next()
+        .ok_or("no EGL device available")?;
+    let display = unsafe { Display::with_device(&device, None)? };
+
+    let template = ConfigTemplateBuilder::new()
+        .with_surface_type(ConfigSurfaceTypes::empty())
+        .build();
+    let config = unsafe { display.find_configs(template)? }
+        .next()
+        .ok_or("no suitable EGL config")?;
+
+    let attributes = ContextAttributesBuilder::new()
+        .with_context_api(ContextApi::OpenGl(Some(Version::new(3, 2))))
+        .build(None);
+    let context = unsafe { display.create_context(&config, &attributes)? };
+    let context = context.make_current_surfaceless()?;
+
+    let backend = HeadlessBackend {
+        context,
+        display,
+        dimensions,
+    };
+    let context = unsafe { Context::new(backend, true, DebugCallbackBehavior::default())? };
+    Ok(context)
+}
+
+/// Draws one frame of `renderer`, seen through `camera`, into an offscreen texture and returns
+/// its pixels, top row first.
+pub fn render_frame<F: Facade>(
+    facade: &F,
+    renderer: &Renderer,
+    camera: &Camera,
+    dimensions: (u32, u32),
+) -> Result<image::RgbaImage, Box<dyn Error>> {
+    let (width, height) = dimensions;
+    let color = SrgbTexture2d::empty(facade, width, height)?;
+    let depth = DepthTexture2d::empty(facade, width, height)?;
+    let mut framebuffer = SimpleFrameBuffer::with_depth_buffer(facade, &color, &depth)?;
+    renderer.draw(&mut framebuffer, camera);
+    capture::image_from_gl(color.read())
+}
+
+/// Renders `frames` frames of what `renderer` shows, seen through `camera`, and saves them in
+/// `out` as `frame_0000.png` and so on. Frames are one simulation step apart, on a clock driven
+/// by hand, so the same scene always gives the same images.
+pub fn render_frames(
+    context: &Rc<Context>,
+    renderer: &mut Renderer,
+    camera: &Camera,
+    dimensions: (u32, u32),
+    frames: u32,
+    out: &Path,
+) -> Result<(), Box<dyn Error>> {
+    std::fs::create_dir_all(out)?;
+    let time = ManualTime::new();
+    let mut clock = Clock::new(time.clone(), clock::DEFAULT_STEP);
+    for frame in 0..frames {
+        renderer.update(clock.time());
+        let image = render_frame(context, renderer, camera, dimensions)?;
+        let path = out.join(format!("frame_{:04}.png", frame));
+        image.save(&path)?;
+        println!("Saved {}", path.display());
+
+        time.advance(clock::DEFAULT_STEP);
+        clock.advance();
+    }
+    Ok(())
+}
+
+/// Times the crowd model drawn with instancing against one draw call per teapot, and prints
+/// the results.
+pub fn benchmark_instancing(assets: AssetManager) -> Result<(), Box<dyn Error>> {
+    const DIMENSIONS: (u32, u32) = (640, 480);
+    const FRAMES: u32 = 10;
+
+    let context = create_context(DIMENSIONS)?;
+    let mut renderer = Renderer::new(&context, assets)?;
+    renderer.show_model(&context, "crowd")?;
+    renderer.update(0.0);
+
+    for instancing in [true, false] {
+        renderer.set_instancing(instancing);
+        // The first frame pays for compiling and uploading; reading the pixels back waits for
+        // the GPU to finish each frame
+        render_frame(&context, &renderer, &Camera::default(), DIMENSIONS)?;
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            render_frame(&context, &renderer, &Camera::default(), DIMENSIONS)?;
+        }
+        let per_frame = start.elapsed() / FRAMES;
+        println!(
+            "{}: {:.1} ms per frame",
+            if instancing {
+                "Instanced"
+            } else {
+                "One draw call per teapot"
+            },
+            per_frame.as_secs_f64() * 1000.0
+        );
+    }
+    Ok(())
+}
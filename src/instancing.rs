@@ -0,0 +1,81 @@
+// Many copies of one mesh drawn with a single draw call.
+//
+// Each copy's placement and tint live in a per-instance vertex buffer, which the instanced
+// vertex shader reads as attributes. That saves the uniform uploads and driver work that make
+// thousands of separate draw calls slow. Instanced meshes are left out of the shadow maps.
+
+use std::rc::Rc;
+
+use glium::backend::Facade;
+use glium::VertexBuffer;
+
+use crate::bounds;
+use crate::material::Material;
+use crate::math::Mat4;
+use crate::math::Vec3;
+use crate::mesh::Mesh;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Instance {
+    /// Places the copy within the model the instances belong to.
+    pub instance_model: [[f32; 4]; 4],
+    /// Multiplies the copy's surface color.
+    pub instance_tint: [f32; 3],
+}
+
+implement_vertex!(Instance, instance_model, instance_tint);
+
+impl Instance {
+    pub fn new(model: Mat4, tint: [f32; 3]) -> Self {
+        Instance {
+            instance_model: model.to_cols_array(),
+            instance_tint: tint,
+        }
+    }
+
+    pub fn model(&self) -> Mat4 {
+        Mat4::from(self.instance_model)
+    }
+}
+
+pub struct InstancedMesh {
+    pub mesh: Rc<Mesh>,
+    /// Replaces the mesh's material when set.
+    pub material: Option<Rc<Material>>,
+    /// Kept on the CPU as well, for drawing the copies one by one.
+    instances: Vec<Instance>,
+    buffer: VertexBuffer<Instance>,
+    /// Sphere around all copies, as center and radius.
+    bounds: (Vec3, f32),
+}
+
+impl InstancedMesh {
+    pub fn new<F: Facade>(facade: &F, mesh: Rc<Mesh>, instances: Vec<Instance>) -> Self {
+        let bounds = instances
+            .iter()
+            .map(|instance| bounds::transform_sphere(&instance.model(), mesh.bounding_sphere()))
+            .reduce(bounds::merge_spheres)
+            .unwrap_or((Vec3::ZERO, 0.0));
+        InstancedMesh {
+            buffer: VertexBuffer::new(facade, &instances).unwrap(),
+            mesh,
+            material: None,
+            instances,
+            bounds,
+        }
+    }
+
+    pub fn instances(&self) -> &[Instance] {
+        &self.instances
+    }
+
+    pub fn buffer(&self) -> &VertexBuffer<Instance> {
+        &self.buffer
+    }
+
+    /// A sphere around all copies, as center and radius, in the coordinates of the model they
+    /// belong to.
+    pub fn bounding_sphere(&self) -> (Vec3, f32) {
+        self.bounds
+    }
+}
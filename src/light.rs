@@ -0,0 +1,415 @@
+// Directional, point and spot lights, and the uniforms that pass them to the shaders.
+//
+// Lights are placed in world space. `LightUniforms` moves them into view space for the frame
+// being drawn and fills in the `lights` array of the Blinn-Phong shader.
+
+use glium::uniforms::UniformValue;
+use glium::uniforms::Uniforms;
+
+use crate::math::vec3;
+use crate::math::Mat4;
+use crate::math::Vec3;
+
+/// Size of the `lights` array in the shaders; lights beyond it are ignored.
+pub const MAX_LIGHTS: usize = 8;
+
+/// The range given to lights that never fade to 1%, farther than any scene reaches.
+pub const UNLIMITED_RANGE: f32 = 1000.0;
+
+/// How a light fades with distance `d`: `1 / (constant + linear * d + quadratic * d^2)`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Attenuation {
+    pub constant: f32,
+    pub linear: f32,
+    pub quadratic: f32,
+}
+
+impl Attenuation {
+    /// Falls off with the square of the distance, down to 1% of the intensity at `range`.
+    pub fn with_range(range: f32) -> Self {
+        Attenuation {
+            constant: 1.0,
+            linear: 0.0,
+            quadratic: 99.0 / (range * range),
+        }
+    }
+
+    /// The distance at which the light is down to 1% of its intensity, the inverse of
+    /// `with_range` for any attenuation. Capped at `UNLIMITED_RANGE`.
+    pub fn range(&self) -> f32 {
+        // Solves constant + linear * d + quadratic * d^2 = 100 for d
+        let remaining = 100.0 - self.constant;
+        if remaining <= 0.0 {
+            // Never brighter than 1% in the first place
+            return f32::MIN_POSITIVE;
+        }
+        let range = if self.quadratic > 0.0 {
+            let discriminant = self.linear * self.linear + 4.0 * self.quadratic * remaining;
+            (discriminant.sqrt() - self.linear) / (2.0 * self.quadratic)
+        } else if self.linear > 0.0 {
+            remaining / self.linear
+        } else {
+            UNLIMITED_RANGE
+        };
+        range.min(UNLIMITED_RANGE)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LightKind {
+    /// Infinitely far away, like the sun. `direction` is the way the light travels.
+    Directional { direction: Vec3 },
+    /// Shines from `position` in every direction.
+    Point {
+        position: Vec3,
+        attenuation: Attenuation,
+    },
+    /// Shines from `position` in a cone around `direction`. The light has full strength up to
+    /// `inner_angle` from the axis and fades out by `outer_angle`, both in radians.
+    Spot {
+        position: Vec3,
+        direction: Vec3,
+        attenuation: Attenuation,
+        inner_angle: f32,
+        outer_angle: f32,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Light {
+    pub kind: LightKind,
+    /// Linear RGB.
+    pub color: [f32; 3],
+    pub intensity: f32,
+    /// Whether the light gets a shadow map. Point lights never cast shadows.
+    pub casts_shadows: bool,
+}
+
+impl Light {
+    pub fn directional(direction: Vec3, color: [f32; 3], intensity: f32) -> Self {
+        Light {
+            kind: LightKind::Directional {
+                direction: direction.normalize(),
+            },
+            color,
+            intensity,
+            casts_shadows: false,
+        }
+    }
+
+    pub fn point(position: Vec3, range: f32, color: [f32; 3], intensity: f32) -> Self {
+        Light {
+            kind: LightKind::Point {
+                position,
+                attenuation: Attenuation::with_range(range),
+            },
+            color,
+            intensity,
+            casts_shadows: false,
+        }
+    }
+
+    pub fn spot(
+        position: Vec3,
+        direction: Vec3,
+        range: f32,
+        inner_angle: f32,
+        outer_angle: f32,
+        color: [f32; 3],
+        intensity: f32,
+    ) -> Self {
+        Light {
+            kind: LightKind::Spot {
+                position,
+                direction: direction.normalize(),
+                attenuation: Attenuation::with_range(range),
+                inner_angle,
+                outer_angle,
+            },
+            color,
+            intensity,
+            casts_shadows: false,
+        }
+    }
+
+    /// The same light, casting shadows.
+    pub fn with_shadows(self) -> Self {
+        Light {
+            casts_shadows: true,
+            ..self
+        }
+    }
+}
+
+/// The white light the demo has always been lit by, from the upper left and in front of the
+/// models.
+pub fn default_lights() -> Vec<Light> {
+    vec![Light::directional(vec3(1.0, -0.4, 0.9), [1.0, 1.0, 1.0], 1.0).with_shadows()]
+}
+
+/// A dim sky light with a warm point light and a cool spot light, to show the other types.
+pub fn lamp_lights() -> Vec<Light> {
+    vec![
+        Light::directional(vec3(0.0, -1.0, 0.2), [0.6, 0.7, 1.0], 0.15),
+        Light::point(vec3(-1.5, 1.0, -1.5), 6.0, [1.0, 0.7, 0.4], 1.5),
+        Light::spot(
+            vec3(1.5, 2.0, -2.0),
+            vec3(-1.5, -2.0, 2.0),
+            10.0,
+            0.2,
+            0.35,
+            [0.5, 0.8, 1.0],
+            2.0,
+        )
+        .with_shadows(),
+    ]
+}
+
+/// Adds the `lights` array and `light_count` uniforms, in the view space of `view`, to the
+/// uniforms in `rest`.
+///
+/// `shadows` has, for each light with a shadow map, the matrix from world space to the map as
+/// returned by `ShadowMaps::render`. The maps themselves are numbered in the same order.
+pub struct LightUniforms<'a, U> {
+    lights: &'a [Light],
+    shadows: &'a [Option<Mat4>],
+    view: Mat4,
+    rest: U,
+}
+
+impl<'a, U: Uniforms> LightUniforms<'a, U> {
+    pub fn new(lights: &'a [Light], shadows: &'a [Option<Mat4>], view: Mat4, rest: U) -> Self {
+        LightUniforms {
+            lights,
+            shadows,
+            view,
+            rest,
+        }
+    }
+}
+
+const DIRECTIONAL: i32 = 0;
+const POINT: i32 = 1;
+const SPOT: i32 = 2;
+
+impl<U: Uniforms> Uniforms for LightUniforms<'_, U> {
+    fn visit_values<'b, F: FnMut(&str, UniformValue<'b>)>(&'b self, mut f: F) {
+        self.rest.visit_values(&mut f);
+
+        let lights = &self.lights[..self.lights.len().min(MAX_LIGHTS)];
+        f("light_count", UniformValue::SignedInt(lights.len() as i32));
+
+        // The fragment shader works in view space, the shadow matrices start from world space
+        let view_to_world = self.view.inverse().unwrap_or(Mat4::IDENTITY);
+        let mut shadow_maps = 0;
+
+        for (i, light) in lights.iter().enumerate() {
+            let mut set = |field: &str, value| f(&format!("lights[{}].{}", i, field), value);
+
+            let color = light.color.map(|c| c * light.intensity);
+            set("color", UniformValue::Vec3(color));
+
+            let (kind, position, direction, attenuation, cos_inner, cos_outer) = match light.kind {
+                LightKind::Directional { direction } => {
+                    (DIRECTIONAL, Vec3::ZERO, direction, None, 1.0, 1.0)
+                }
+                LightKind::Point {
+                    position,
+                    attenuation,
+                } => (POINT, position, Vec3::ZERO, Some(attenuation), 1.0, 1.0),
+                LightKind::Spot {
+                    position,
+                    direction,
+                    attenuation,
+                    inner_angle,
+                    outer_angle,
+                } => (
+                    SPOT,
+                    position,
+                    direction,
+                    Some(attenuation),
+                    inner_angle.cos(),
+                    outer_angle.cos(),
+                ),
+            };
+            let attenuation =
+                attenuation.map_or([1.0, 0.0, 0.0], |a| [a.constant, a.linear, a.quadratic]);
+
+            set("kind", UniformValue::SignedInt(kind));
+            set(
+                "position",
+                UniformValue::Vec3(self.view.transform_point(position).to_array()),
+            );
+            set(
+                "direction",
+                UniformValue::Vec3(self.view.transform_vector(direction).to_array()),
+            );
+            set("attenuation", UniformValue::Vec3(attenuation));
+            set("cos_inner", UniformValue::Float(cos_inner));
+            set("cos_outer", UniformValue::Float(cos_outer));
+
+            match self.shadows.get(i).copied().flatten() {
+                Some(shadow_matrix) => {
+                    set("shadow_map", UniformValue::SignedInt(shadow_maps));
+                    set(
+                        "shadow_matrix",
+                        UniformValue::Mat4((shadow_matrix * view_to_world).to_cols_array()),
+                    );
+                    shadow_maps += 1;
+                }
+                None => set("shadow_map", UniformValue::SignedInt(-1)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use glium::uniforms::EmptyUniforms;
+
+    // The uniform value types lights use, comparable in assertions
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        Int(i32),
+        Float(f32),
+        Vec3([f32; 3]),
+        Mat4([[f32; 4]; 4]),
+    }
+
+    fn collect<U: Uniforms>(uniforms: &U) -> Vec<(String, Value)> {
+        let mut values = Vec::new();
+        uniforms.visit_values(|name, value| {
+            let value = match value {
+                UniformValue::SignedInt(v) => Value::Int(v),
+                UniformValue::Float(v) => Value::Float(v),
+                UniformValue::Vec3(v) => Value::Vec3(v),
+                UniformValue::Mat4(v) => Value::Mat4(v),
+                _ => panic!("unexpected type for {}", name),
+            };
+            values.push((name.to_string(), value));
+        });
+        values
+    }
+
+    fn value<'a>(values: &'a [(String, Value)], name: &str) -> &'a Value {
+        &values.iter().find(|(n, _)| n == name).unwrap().1
+    }
+
+    #[test]
+    fn attenuation_reaches_one_percent_at_range() {
+        let a = Attenuation::with_range(4.0);
+        let strength = 1.0 / (a.constant + a.linear * 4.0 + a.quadratic * 16.0);
+        assert!((strength - 0.01).abs() < 1e-6);
+    }
+
+    #[test]
+    fn range_is_worked_out_from_any_attenuation() {
+        assert!((Attenuation::with_range(4.0).range() - 4.0).abs() < 1e-5);
+        let linear = Attenuation {
+            constant: 1.0,
+            linear: 0.5,
+            quadratic: 0.0,
+        };
+        assert!((linear.range() - 198.0).abs() < 1e-3);
+        let constant = Attenuation {
+            constant: 1.0,
+            linear: 0.0,
+            quadratic: 0.0,
+        };
+        assert_eq!(constant.range(), UNLIMITED_RANGE);
+    }
+
+    #[test]
+    fn lights_are_moved_into_view_space() {
+        let lights = [
+            Light::point(vec3(1.0, 2.0, 3.0), 10.0, [1.0, 0.5, 0.0], 2.0),
+            Light::directional(vec3(0.0, 0.0, 2.0), [1.0; 3], 1.0),
+        ];
+        let view = Mat4::from_translation(vec3(0.0, 0.0, 5.0));
+        let values = collect(&LightUniforms::new(&lights, &[], view, EmptyUniforms));
+
+        assert_eq!(value(&values, "light_count"), &Value::Int(2));
+        assert_eq!(value(&values, "lights[0].kind"), &Value::Int(POINT));
+        assert_eq!(
+            value(&values, "lights[0].position"),
+            &Value::Vec3([1.0, 2.0, 8.0])
+        );
+        assert_eq!(
+            value(&values, "lights[0].color"),
+            &Value::Vec3([2.0, 1.0, 0.0])
+        );
+        // Directions ignore the translation
+        assert_eq!(value(&values, "lights[1].kind"), &Value::Int(DIRECTIONAL));
+        assert_eq!(
+            value(&values, "lights[1].direction"),
+            &Value::Vec3([0.0, 0.0, 1.0])
+        );
+        assert_eq!(
+            value(&values, "lights[1].attenuation"),
+            &Value::Vec3([1.0, 0.0, 0.0])
+        );
+    }
+
+    #[test]
+    fn spot_angles_become_cosines() {
+        let lights = [Light::spot(
+            Vec3::ZERO,
+            Vec3::Z,
+            10.0,
+            0.0,
+            std::f32::consts::PI,
+            [1.0; 3],
+            1.0,
+        )];
+        let values = collect(&LightUniforms::new(
+            &lights,
+            &[],
+            Mat4::IDENTITY,
+            EmptyUniforms,
+        ));
+
+        assert_eq!(value(&values, "lights[0].kind"), &Value::Int(SPOT));
+        assert_eq!(value(&values, "lights[0].cos_inner"), &Value::Float(1.0));
+        assert_eq!(value(&values, "lights[0].cos_outer"), &Value::Float(-1.0));
+    }
+
+    #[test]
+    fn extra_lights_are_dropped() {
+        let lights = vec![Light::directional(Vec3::Y, [1.0; 3], 1.0); MAX_LIGHTS + 2];
+        let values = collect(&LightUniforms::new(
+            &lights,
+            &[],
+            Mat4::IDENTITY,
+            EmptyUniforms,
+        ));
+
+        assert_eq!(
+            value(&values, "light_count"),
+            &Value::Int(MAX_LIGHTS as i32)
+        );
+        let first_extra = format!("lights[{}]", MAX_LIGHTS);
+        assert!(!values.iter().any(|(n, _)| n.starts_with(&first_extra)));
+    }
+
+    #[test]
+    fn shadow_maps_are_numbered_in_light_order() {
+        let lights = vec![Light::directional(Vec3::Y, [1.0; 3], 1.0); 3];
+        let shadow = Mat4::from_scale(vec3(2.0, 2.0, 2.0));
+        let view = Mat4::from_translation(vec3(1.0, 0.0, 0.0));
+        let shadows = [Some(shadow), None, Some(shadow)];
+        let values = collect(&LightUniforms::new(&lights, &shadows, view, EmptyUniforms));
+
+        assert_eq!(value(&values, "lights[0].shadow_map"), &Value::Int(0));
+        assert_eq!(value(&values, "lights[1].shadow_map"), &Value::Int(-1));
+        assert_eq!(value(&values, "lights[2].shadow_map"), &Value::Int(1));
+
+        // View space positions are taken back to world space first
+        let expected = shadow * view.inverse().unwrap();
+        assert_eq!(
+            value(&values, "lights[2].shadow_matrix"),
+            &Value::Mat4(expected.to_cols_array())
+        );
+    }
+}
@@ -1,12 +1,7 @@
-use std::io::Cursor;
+use std::error::Error;
+use std::path::Path;
 
-use glium::backend::glutin::SimpleWindowBuilder;
-
-use glium::Depth;
-use glium::DepthTest;
-use glium::DrawParameters;
-use glium::Program;
-use glium::Surface;
+use glium::backend::Facade;
 
 use winit::event::ElementState;
 use winit::event::Event;
@@ -15,163 +10,127 @@ use winit::event::WindowEvent;
 use winit::keyboard::KeyCode;
 use winit::keyboard::PhysicalKey;
 
+mod assets;
+mod bounds;
+mod camera;
+mod capture;
+mod cli;
+mod clock;
+mod environment;
+mod gltf_scene;
+#[cfg(test)]
+mod golden;
+mod headless;
+mod instancing;
+mod light;
+mod material;
+mod math;
+mod mesh;
+mod obj;
+mod post;
+mod renderer;
+mod scene;
+mod scene_file;
+mod shader;
+mod shadow;
 mod teapot;
+mod texture;
+mod window;
+
+use assets::AssetManager;
+use camera::Camera;
+use capture::Recording;
+use cli::CliError;
+use cli::Mode;
+use cli::Options;
+use clock::Clock;
+use material::ShadingModel;
+use renderer::FrameStats;
+use renderer::Renderer;
+use renderer::TangentMode;
+use scene_file::MaterialDescription;
+use scene_file::TextureDescription;
+use scene_file::SceneDescription;
+use window::WindowSettings;
 
 #[macro_use]
 extern crate glium;
-fn main() {
-    let event_loop = winit::event_loop::EventLoopBuilder::new().build().unwrap();
-    let (window, display) = SimpleWindowBuilder::new().build(&event_loop);
-
-    // FOR DISPLAYING 3D MODELS
-    // let positions = glium::VertexBuffer::new(&display, &teapot::VERTICES).unwrap();
-    // let normals = glium::VertexBuffer::new(&display, &teapot::NORMALS).unwrap();
-    // let indices = glium::IndexBuffer::new(
-    //     &display,
-    //     glium::index::PrimitiveType::TrianglesList,
-    //     &teapot::INDICES,
-    // )
-    // .unwrap();
-
-    let shape = glium::vertex::VertexBuffer::new(
-        &display,
-        &[
-            Vertex {
-                position: [-1.0, 1.0, 0.0],
-                normal: [0.0, 0.0, -1.0],
-                tex_coords: [0.0, 1.0],
-            },
-            Vertex {
-                position: [1.0, 1.0, 0.0],
-                normal: [0.0, 0.0, -1.0],
-                tex_coords: [1.0, 1.0],
-            },
-            Vertex {
-                position: [-1.0, -1.0, 0.0],
-                normal: [0.0, 0.0, -1.0],
-                tex_coords: [0.0, 0.0],
-            },
-            Vertex {
-                position: [1.0, -1.0, 0.0],
-                normal: [0.0, 0.0, -1.0],
-                tex_coords: [1.0, 0.0],
-            },
-        ],
-    )
-    .unwrap();
-
-    let image = image::load(
-        Cursor::new(&include_bytes!("../assets/diffuse.jpg")),
-        image::ImageFormat::Jpeg,
-    )
-    .unwrap()
-    .to_rgba8();
-    let image_dimensions = image.dimensions();
-    let image =
-        glium::texture::RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
-    let diffuse_texture = glium::texture::SrgbTexture2d::new(&display, image).unwrap();
-
-    let image = image::load(
-        std::io::Cursor::new(&include_bytes!("../assets/normal.png")),
-        image::ImageFormat::Png,
-    )
-    .unwrap()
-    .to_rgba8();
-    let image_dimensions = image.dimensions();
-    let image =
-        glium::texture::RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
-    let normal_map = glium::texture::Texture2d::new(&display, image).unwrap();
-
-    let vertex_shader_src = r#"
-        #version 150
-
-        in vec3 position;
-        in vec3 normal;
-        in vec2 tex_coords;
 
-        out vec3 v_normal;
-        out vec3 v_position;
-        out vec2 v_tex_coords;
+/// Where screenshots and recordings are saved, relative to the working directory.
+const CAPTURE_DIR: &str = "captures";
 
-        uniform mat4 perspective;
-        uniform mat4 view;
-        uniform mat4 model;
-
-        void main() {
-            v_tex_coords = tex_coords;
-            mat4 modelview = view * model;
-            v_normal = transpose(inverse(mat3(modelview))) * normal;
-            gl_Position = perspective * modelview * vec4(position, 1.0);
-            v_position = gl_Position.xyz / gl_Position.w;
+fn main() {
+    let options = match cli::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(CliError::Help) => {
+            println!("{}", cli::USAGE);
+            return;
         }
-    "#;
-
-    let fragment_shader_src = r#"
-        #version 150
-
-        in vec3 v_normal;
-        in vec3 v_position;
-        in vec2 v_tex_coords;
-
-        out vec4 color;
-
-        uniform vec3 u_light;
-        uniform sampler2D diffuse_tex;
-        uniform sampler2D normal_tex;
-
-        // For now, we'll use a constant ambient and diffuse value
-        // const vec3 ambient_color = vec3(0.2, 0.0, 0.0);
-        // const vec3 diffuse_color = vec3(0.6, 0.0, 0.0);
-        const vec3 specular_color = vec3(1.0, 1.0, 1.0);
-
-        mat3 cotangent_frame(vec3 normal, vec3 pos, vec2 uv) {
-            vec3 dp1 = dFdx(pos);
-            vec3 dp2 = dFdy(pos);
-            vec2 duv1 = dFdx(uv);
-            vec2 duv2 = dFdy(uv);
-
-            vec3 dp2perp = cross(dp2, normal);
-            vec3 dp1perp = cross(normal, dp1);
-            vec3 T = dp2perp * duv1.x + dp1perp * duv2.x;
-            vec3 B = dp2perp * duv1.y + dp1perp * duv2.y;
-
-            float invmax = inversesqrt(max(dot(T, T), dot(B, B)));
-            return mat3(T * invmax, B * invmax, normal);
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
         }
-
-        void main() {
-            vec3 diffuse_color = texture(diffuse_tex, v_tex_coords).rgb;
-            vec3 ambient_color = diffuse_color * 0.1;
-
-            vec3 v_normal_unit = normalize(v_normal);
-            vec3 normal_map = texture(normal_tex, v_tex_coords).rgb;
-            mat3 tbn = cotangent_frame(v_normal_unit, -v_position, v_tex_coords);
-            vec3 real_normal = normalize(tbn * -(normal_map * 2.0 - 1.0));
-
-            float diffuse = max(dot(real_normal, normalize(u_light)), 0.0);
-
-            vec3 camera_dir = normalize(-v_position);
-            vec3 half_direction = normalize(normalize(u_light) + camera_dir);
-            float specular = pow(max(dot(half_direction, real_normal), 0.0), 16.0);
-
-            color = vec4(ambient_color + diffuse * diffuse_color + specular * specular_color, 1.0);
+    };
+    let assets = AssetManager::new(&options.assets);
+    println!("Loading assets from {}", assets.root().display());
+
+    match &options.mode {
+        Mode::Window => {}
+        Mode::Benchmark => {
+            headless::benchmark_instancing(assets).unwrap();
+            return;
         }
-    "#;
-
-    let program =
-        Program::from_source(&display, vertex_shader_src, fragment_shader_src, None).unwrap();
+        Mode::Headless { frames, out } => {
+            let context = headless::create_context(options.size).unwrap();
+            let mut renderer = Renderer::new(&context, assets).unwrap();
+            let camera = show(&context, &mut renderer, &options).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            });
+            headless::render_frames(
+                &context,
+                &mut renderer,
+                &camera,
+                options.size,
+                *frames,
+                out,
+            )
+            .unwrap();
+            return;
+        }
+    }
 
-    // let image = image::load(
-    //     std::io::Cursor::new(&include_bytes!("../assets/meandcat.jpg")[..]),
-    //     image::ImageFormat::Jpeg,
-    // )
-    // .unwrap()
-    // .to_rgba8();
-    // let image_dimensions = image.dimensions();
-    // let image =
-    //     glium::texture::RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions);
+    let event_loop = winit::event_loop::EventLoopBuilder::new().build().unwrap();
+    let settings = WindowSettings {
+        title: "glium demo",
+        size: options.size,
+        vsync: options.vsync,
+    };
+    let (window, display) = window::create(&event_loop, &settings).unwrap();
 
-    let start = std::time::Instant::now();
+    let mut renderer = Renderer::new(&display, assets).unwrap();
+    let mut camera = show(&display, &mut renderer, &options).unwrap_or_else(|e| {
+        println!("{}; showing {}", e, renderer.model_name());
+        Camera::default()
+    });
+    println!("Camera in {:?} mode; press C to switch", camera.mode);
+
+    let title = window.title();
+    let mut shader_error: Option<String> = None;
+
+    let mut clock = Clock::new(clock::SystemTime::new(), clock::DEFAULT_STEP);
+    println!("Space pauses the animation, . steps it while paused, + and - change its speed");
+    let mut last_frame = std::time::Instant::now();
+    let mut last_stats = FrameStats::default();
+    // O switches between the chosen passes, or all of them if none were chosen, and none
+    let post_chain = if options.post.is_empty() {
+        post::DEFAULT_CHAIN.to_vec()
+    } else {
+        options.post.clone()
+    };
+    println!("Press O to turn post-processing on or off and X to switch anti-aliasing");
+    println!("F12 saves a screenshot and F9 starts or stops recording, both to {}", CAPTURE_DIR);
+    let mut recording: Option<Recording> = None;
 
     let _ = event_loop.run(move |event, elwt| {
         match event {
@@ -193,60 +152,394 @@ fn main() {
                 println!("The close button was pressed; stopping");
                 elwt.exit();
             }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::F12),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                match capture::read_front_buffer(&display)
+                    .and_then(|image| capture::save_screenshot(Path::new(CAPTURE_DIR), &image))
+                {
+                    Ok(path) => println!("Saved a screenshot to {}", path.display()),
+                    Err(e) => println!("Could not save a screenshot ({})", e),
+                }
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::F9),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                recording = match recording.take() {
+                    Some(stopped) => {
+                        println!(
+                            "Saved {} frames to {}; make a video with ffmpeg -framerate {} -i {}",
+                            stopped.frames(),
+                            stopped.dir().display(),
+                            (1.0 / clock::DEFAULT_STEP.as_secs_f64()).round(),
+                            stopped.dir().join("frame_%04d.png").display()
+                        );
+                        None
+                    }
+                    None => match Recording::start(Path::new(CAPTURE_DIR)) {
+                        Ok(started) => {
+                            println!("Recording to {}; press F9 to stop", started.dir().display());
+                            Some(started)
+                        }
+                        Err(e) => {
+                            println!("Could not start recording ({})", e);
+                            None
+                        }
+                    },
+                };
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::Tab),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                renderer.next_model();
+                println!("Showing {}", renderer.model_name());
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyN),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                let mode = match renderer.tangent_mode() {
+                    TangentMode::ScreenSpace => TangentMode::Vertex,
+                    TangentMode::Vertex => TangentMode::ScreenSpace,
+                };
+                renderer.set_tangent_mode(mode);
+                println!("Normal mapping with {:?} tangents", mode);
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyC),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                camera.toggle_mode();
+                println!(
+                    "Camera in {:?} mode at {:?}, looking at {:?}",
+                    camera.mode,
+                    camera.position(),
+                    camera.target()
+                );
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyL),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                let lights = if renderer.lights() == light::default_lights() {
+                    light::lamp_lights()
+                } else {
+                    light::default_lights()
+                };
+                println!("Lighting with {} lights", lights.len());
+                renderer.set_lights(lights);
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyP),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                let mut settings = renderer.shadow_settings();
+                settings.pcf_radius = (settings.pcf_radius + 1) % 3;
+                match renderer.set_shadow_settings(settings) {
+                    Ok(()) => println!("Shadow filtering radius {}", settings.pcf_radius),
+                    Err(e) => println!("Could not change shadow filtering ({})", e),
+                }
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyM),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                let shading = match renderer.default_shading() {
+                    ShadingModel::BlinnPhong => ShadingModel::Pbr,
+                    ShadingModel::Pbr => ShadingModel::BlinnPhong,
+                };
+                match renderer.set_default_shading(shading) {
+                    Ok(()) => println!("Shading built-in models with {:?}", shading),
+                    Err(e) => println!("Could not change the shading model ({})", e),
+                }
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyG),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                renderer.print_scene_graph();
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyI),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                renderer.set_instancing(!renderer.instancing());
+                println!(
+                    "Instancing {}",
+                    if renderer.instancing() { "on" } else { "off" }
+                );
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyO),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                let chain = if renderer.post_chain().is_empty() {
+                    post_chain.clone()
+                } else {
+                    Vec::new()
+                };
+                println!("Post-processing: {}", post::chain_names(&chain));
+                renderer.set_post_chain(chain);
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::KeyX),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                let antialiasing = renderer.antialiasing().next(options.msaa);
+                renderer.set_antialiasing(antialiasing);
+                println!("Anti-aliasing: {:?}", renderer.antialiasing());
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::Space),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                clock.set_paused(!clock.paused());
+                println!(
+                    "Animation {}",
+                    if clock.paused() { "paused" } else { "running" }
+                );
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::Period),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                clock.single_step();
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::Equal),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                clock.set_scale(clock.scale() * 2.0);
+                println!("Animation speed {}x", clock.scale());
+            }
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        event:
+                            KeyEvent {
+                                physical_key: PhysicalKey::Code(KeyCode::Minus),
+                                state: ElementState::Pressed,
+                                repeat: false,
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                clock.set_scale(clock.scale() / 2.0);
+                println!("Animation speed {}x", clock.scale());
+            }
             Event::AboutToWait => {
+                renderer.reload_changed_assets(&display);
+                let error = renderer.shader_error();
+                if error != shader_error {
+                    // The first line of the log names the file and line of the first problem
+                    match &error {
+                        Some(e) => {
+                            let first_line = e.lines().next().unwrap_or(e);
+                            window.set_title(&format!("{} - {}", title, first_line))
+                        }
+                        None => window.set_title(&title),
+                    }
+                    shader_error = error;
+                }
                 window.request_redraw();
             }
             Event::WindowEvent {
                 event: WindowEvent::RedrawRequested,
                 ..
             } => {
-                let mut frame = display.draw();
-                frame.clear_color_and_depth((0.0, 0.0, 1.0, 1.0), 1.0);
-
-                let t = (std::time::Instant::now() - start).as_secs_f32() * 2.0;
-                let ang = t.sin();
-                let (c, s) = (ang.cos(), ang.sin());
-                let model = [
-                    [c, 0.0, s, 0.0],
-                    [0.0, 1.0, 0.0, 0.0],
-                    [-s, 0.0, c, 0.0],
-                    [0.0, 0.0, 0.0, 1.0f32],
-                ];
-
-                let (width, height) = frame.get_dimensions();
-                let aspect_ratio = height as f32 / width as f32;
-                let perspective = perspective_matrix(aspect_ratio);
-
-                // the direction of the light
-                let light = [-1.0, 0.4, 0.9f32];
-
-                let params = DrawParameters {
-                    depth: Depth {
-                        test: DepthTest::IfLess,
-                        write: true,
-                        ..Default::default()
-                    },
-                    // DISABLED FOR NOW because:
-                    // You can look through holes and not see anything inside.
-                    // 3D models are usually entirely closed, but not our teapot.
-                    // backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
-                    ..Default::default()
-                };
-
-                let view = view_matrix(&[0.5, 0.2, -3.0], &[-0.5, -0.2, 3.0], &[0.0, 1.0, 0.0]);
-                
-                frame
-                    .draw(
-                        &shape,
-                        glium::index::NoIndices(glium::index::PrimitiveType::TriangleStrip),
-                        &program,
-                        &uniform! { model: model, view: view, perspective: perspective,
-                        u_light: light, diffuse_tex: &diffuse_texture },
-                        &params,
-                    )
-                    .unwrap();
+                let now = std::time::Instant::now();
+                // Recordings take one step per frame, however long saving the frames takes
+                if recording.is_some() {
+                    camera.update(clock::DEFAULT_STEP.as_secs_f32());
+                    clock.step();
+                    renderer.update(clock.time());
+                } else {
+                    camera.update((now - last_frame).as_secs_f32());
+                    // The animation only moves in whole steps, so it looks the same at any frame
+                    // rate
+                    if clock.advance() > 0 {
+                        renderer.update(clock.time());
+                    }
+                }
+                last_frame = now;
 
+                let mut frame = display.draw();
+                let stats = renderer.draw(&mut frame, &camera);
+                if stats != last_stats {
+                    println!(
+                        "Drew {} meshes in {} draw calls, culled {}",
+                        stats.drawn, stats.draw_calls, stats.culled
+                    );
+                    last_stats = stats;
+                }
                 frame.finish().unwrap();
+
+                if let Some(active) = &mut recording {
+                    let saved = capture::read_front_buffer(&display)
+                        .and_then(|image| active.save(&image));
+                    if let Err(e) = saved {
+                        println!("Stopped recording ({})", e);
+                        recording = None;
+                    }
+                }
+            }
+            Event::WindowEvent { event, .. } => {
+                camera.handle_event(&event);
             }
             // show_image::event::Event::RedrawEventsCleared => {
             //     window.request_redraw();
@@ -256,75 +549,57 @@ fn main() {
     });
 }
 
-fn perspective_matrix(aspect_ratio: f32) -> [[f32; 4]; 4] {
-    let fov: f32 = 3.141592 / 3.0;
-    let zfar = 1024.0;
-    let znear = 0.1;
-
-    let f = 1.0 / (fov / 2.0).tan();
-
-    [
-        [f * aspect_ratio, 0.0, 0.0, 0.0],
-        [0.0, f, 0.0, 0.0],
-        [0.0, 0.0, (zfar + znear) / (zfar - znear), 1.0],
-        [0.0, 0.0, -(2.0 * zfar * znear) / (zfar - znear), 0.0],
-    ]
-}
-
-fn view_matrix(position: &[f32; 3], direction: &[f32; 3], up: &[f32; 3]) -> [[f32; 4]; 4] {
-    let f = {
-        let f = direction;
-        let len = f[0] * f[0] + f[1] * f[1] + f[2] * f[2];
-        let len = len.sqrt();
-        [f[0] / len, f[1] / len, f[2] / len]
-    };
-
-    let s = [
-        up[1] * f[2] - up[2] * f[1],
-        up[2] * f[0] - up[0] * f[2],
-        up[0] * f[1] - up[1] * f[0],
-    ];
-
-    let s_norm = {
-        let len = s[0] * s[0] + s[1] * s[1] + s[2] * s[2];
-        let len = len.sqrt();
-        [s[0] / len, s[1] / len, s[2] / len]
+/// Shows the scene or model `options` ask for, with their default textures and post-processing,
+/// and saves it as a scene file if asked to. Returns the camera to start with.
+fn show<F: Facade>(
+    facade: &F,
+    renderer: &mut Renderer,
+    options: &Options,
+) -> Result<Camera, Box<dyn Error>> {
+    renderer.set_default_textures(facade, &options.diffuse, &options.normal)?;
+    renderer.set_color_grade_lut(facade, &options.lut)?;
+    renderer.set_post_chain(options.post.clone());
+    renderer.set_antialiasing(options.antialiasing);
+    let assets = AssetManager::new(&options.assets);
+
+    let (scene, camera) = match &options.scene {
+        Some(path) => {
+            let file = assets.resolve(path);
+            let scene = scene_file::load(&file)?;
+            renderer.show_scene(facade, &path.display().to_string(), &scene, Some(&file))?;
+            let camera = scene.camera.as_ref().map_or_else(Camera::default, |c| c.camera());
+            (Some(scene), camera)
+        }
+        None => {
+            let model = &options.model;
+            renderer
+                .show_model(facade, model)
+                .map_err(|e| format!("Could not show {} ({})", model, e))?;
+            (None, Camera::default())
+        }
     };
 
-    let u = [
-        f[1] * s_norm[2] - f[2] * s_norm[1],
-        f[2] * s_norm[0] - f[0] * s_norm[2],
-        f[0] * s_norm[1] - f[1] * s_norm[0],
-    ];
-
-    let p = [
-        -position[0] * s_norm[0] - position[1] * s_norm[1] - position[2] * s_norm[2],
-        -position[0] * u[0] - position[1] * u[1] - position[2] * u[2],
-        -position[0] * f[0] - position[1] * f[1] - position[2] * f[2],
-    ];
-
-    [
-        [s_norm[0], u[0], f[0], 0.0],
-        [s_norm[1], u[1], f[1], 0.0],
-        [s_norm[2], u[2], f[2], 0.0],
-        [p[0], p[1], p[2], 1.0],
-    ]
-}
-
-// FOR DISPLAYING 3D MODELS
-// #[derive(Copy, Clone)]
-// struct Vertex {
-//     position: [f32; 2],
-//     tex_coords: [f32; 2],
-// }
-
-// implement_vertex!(Vertex, position, tex_coords);
-
-#[derive(Copy, Clone)]
-struct Vertex {
-    position: [f32; 3],
-    normal: [f32; 3],
-    tex_coords: [f32; 2],
+    if let Some(path) = &options.save_scene {
+        let scene = match scene {
+            Some(scene) => scene,
+            None => {
+                // The built-in meshes are drawn with the default material
+                let model = options.model.as_str();
+                let material = match model {
+                    "quad" | "teapot" => Some(MaterialDescription {
+                        shading: renderer.default_shading().into(),
+                        base_color: Some(TextureDescription::new(&options.diffuse)),
+                        normal_map: Some(TextureDescription::new(&options.normal)),
+                        ..MaterialDescription::default()
+                    }),
+                    _ if assets.resolve(model).exists() => None,
+                    _ => return Err(format!("{} can't be saved as a scene", model).into()),
+                };
+                SceneDescription::single_model(model, material, renderer.lights(), &camera)
+            }
+        };
+        scene.save(path)?;
+        println!("Saved the scene to {}", path.display());
+    }
+    Ok(camera)
 }
-
-implement_vertex!(Vertex, position, normal, tex_coords);
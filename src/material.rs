@@ -0,0 +1,315 @@
+// Surface descriptions: the program a mesh is drawn with, and the named values, such as textures
+// and factors, set on its uniforms.
+//
+// A material is a program plus a map from uniform names to values, checked against the uniforms
+// the program declares when the material is created. A value misspelled here or renamed in a
+// shader is reported as an error instead of leaving the shader to read zero, and a shader with a
+// new texture only needs materials that set it.
+//
+// The demo's own programs both take their inputs from glTF's metallic-roughness model; the
+// Blinn-Phong shader only looks at the base color texture and the normal map. `StandardInputs`
+// holds those inputs and names them for either program.
+//
+// Every texture carries the sampler it is read with, so how a texture is filtered and wrapped is
+// part of the material rather than of the draw call.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use glium::backend::Facade;
+use glium::texture::SrgbTexture2d;
+use glium::texture::Texture2d;
+use glium::uniforms::SamplerBehavior;
+use glium::uniforms::UniformValue;
+use glium::uniforms::Uniforms;
+
+use crate::shader;
+use crate::shader::ShaderProgram;
+use crate::shader::UniformError;
+use crate::texture;
+
+/// Which lighting model, and so which program, a material is drawn with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShadingModel {
+    /// The demo's original lighting, a diffuse texture with a fixed white highlight.
+    BlinnPhong,
+    /// Cook-Torrance with the GGX distribution, driven by metalness and roughness.
+    Pbr,
+}
+
+/// A texture of a material and how it is read.
+pub struct MaterialTexture<T> {
+    pub texture: Rc<T>,
+    pub sampler: SamplerBehavior,
+}
+
+impl<T> MaterialTexture<T> {
+    /// `texture`, read with `texture::DEFAULT_SAMPLER`.
+    pub fn new(texture: Rc<T>) -> Self {
+        MaterialTexture {
+            texture,
+            sampler: texture::DEFAULT_SAMPLER,
+        }
+    }
+}
+
+// Derived, this would only be `Clone` for textures that are
+impl<T> Clone for MaterialTexture<T> {
+    fn clone(&self) -> Self {
+        MaterialTexture {
+            texture: self.texture.clone(),
+            sampler: self.sampler,
+        }
+    }
+}
+
+/// The programs a material is drawn with: the same fragment shader behind the vertex shaders for
+/// single meshes and instanced ones.
+pub struct MaterialProgram {
+    pub single: ShaderProgram,
+    pub instanced: ShaderProgram,
+}
+
+/// A program shared by the materials drawn with it. The renderer reloads it in place when its
+/// files change, so the materials pick up the new shaders.
+pub type ProgramHandle = Rc<RefCell<MaterialProgram>>;
+
+/// A value for a uniform of a material. Like `UniformValue`, but owning its textures.
+#[derive(Clone)]
+pub enum Parameter {
+    Float(f32),
+    Vec3([f32; 3]),
+    Vec4([f32; 4]),
+    SrgbTexture(MaterialTexture<SrgbTexture2d>),
+    Texture(MaterialTexture<Texture2d>),
+}
+
+impl Parameter {
+    fn uniform(&self) -> UniformValue<'_> {
+        match self {
+            Parameter::Float(value) => UniformValue::Float(*value),
+            Parameter::Vec3(value) => UniformValue::Vec3(*value),
+            Parameter::Vec4(value) => UniformValue::Vec4(*value),
+            Parameter::SrgbTexture(texture) => {
+                UniformValue::SrgbTexture2d(&texture.texture, Some(texture.sampler))
+            }
+            Parameter::Texture(texture) => {
+                UniformValue::Texture2d(&texture.texture, Some(texture.sampler))
+            }
+        }
+    }
+}
+
+/// Uniform values by name.
+pub type Parameters = BTreeMap<String, Parameter>;
+
+#[derive(Clone)]
+pub struct Material {
+    program: ProgramHandle,
+    parameters: Parameters,
+}
+
+impl Material {
+    /// A material drawn with `program`, setting its uniforms to `parameters`. Fails if the
+    /// program has no uniform of a fitting type for one of them.
+    pub fn new(program: ProgramHandle, parameters: Parameters) -> Result<Self, UniformError> {
+        let material = Material {
+            program,
+            parameters,
+        };
+        material.validate()?;
+        Ok(material)
+    }
+
+    pub fn program(&self) -> &ProgramHandle {
+        &self.program
+    }
+
+    // Checks that both of the material's programs have a uniform of the right type for every
+    // value the material sets
+    fn validate(&self) -> Result<(), UniformError> {
+        let program = self.program.borrow();
+        for shader in [&program.single, &program.instanced] {
+            for (name, value) in &self.parameters {
+                shader::check_value(shader.program(), name, &value.uniform())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The material's uniforms, added to the uniforms in `rest`.
+    pub fn uniforms<U: Uniforms>(&self, rest: U) -> MaterialUniforms<'_, U> {
+        MaterialUniforms {
+            material: self,
+            rest,
+        }
+    }
+}
+
+pub struct MaterialUniforms<'a, U> {
+    material: &'a Material,
+    rest: U,
+}
+
+impl<U: Uniforms> Uniforms for MaterialUniforms<'_, U> {
+    fn visit_values<'b, F: FnMut(&str, UniformValue<'b>)>(&'b self, mut f: F) {
+        self.rest.visit_values(&mut f);
+        for (name, value) in &self.material.parameters {
+            f(name, value.uniform());
+        }
+    }
+}
+
+/// The inputs of the demo's own lighting models, from glTF's metallic-roughness model.
+#[derive(Clone)]
+pub struct StandardInputs {
+    /// sRGB color, multiplied by `base_color_factor`.
+    pub base_color: MaterialTexture<SrgbTexture2d>,
+    pub base_color_factor: [f32; 4],
+    pub normal_map: MaterialTexture<Texture2d>,
+    /// Roughness in the green channel and metalness in the blue one, multiplied by the factors.
+    pub metallic_roughness: MaterialTexture<Texture2d>,
+    pub metallic_factor: f32,
+    pub roughness_factor: f32,
+    /// Ambient occlusion in the red channel.
+    pub occlusion: MaterialTexture<Texture2d>,
+    /// sRGB light given off by the surface, multiplied by `emissive_factor`.
+    pub emissive: MaterialTexture<SrgbTexture2d>,
+    pub emissive_factor: [f32; 3],
+}
+
+impl StandardInputs {
+    /// The given color and normal map, read with the default sampler. The other inputs describe
+    /// a non-metallic surface of medium roughness, without occlusion or emission.
+    pub fn new<F: Facade>(
+        facade: &F,
+        base_color: Rc<SrgbTexture2d>,
+        normal_map: Rc<Texture2d>,
+    ) -> Self {
+        let white = Rc::new(texture::solid_linear_texture(facade, [1.0, 1.0, 1.0]));
+        StandardInputs {
+            base_color: MaterialTexture::new(base_color),
+            base_color_factor: [1.0, 1.0, 1.0, 1.0],
+            normal_map: MaterialTexture::new(normal_map),
+            metallic_roughness: MaterialTexture::new(white.clone()),
+            metallic_factor: 0.0,
+            roughness_factor: 0.5,
+            occlusion: MaterialTexture::new(white),
+            emissive: MaterialTexture::new(Rc::new(texture::solid_srgb_texture(
+                facade,
+                [1.0, 1.0, 1.0],
+            ))),
+            emissive_factor: [0.0, 0.0, 0.0],
+        }
+    }
+
+    /// The inputs under the uniform names of the program for `shading`.
+    pub fn parameters(&self, shading: ShadingModel) -> Parameters {
+        let parameters = match shading {
+            ShadingModel::BlinnPhong => vec![
+                (
+                    "diffuse_tex",
+                    Parameter::SrgbTexture(self.base_color.clone()),
+                ),
+                ("normal_tex", Parameter::Texture(self.normal_map.clone())),
+            ],
+            ShadingModel::Pbr => vec![
+                (
+                    "base_color_tex",
+                    Parameter::SrgbTexture(self.base_color.clone()),
+                ),
+                ("base_color_factor", Parameter::Vec4(self.base_color_factor)),
+                ("normal_tex", Parameter::Texture(self.normal_map.clone())),
+                (
+                    "metallic_roughness_tex",
+                    Parameter::Texture(self.metallic_roughness.clone()),
+                ),
+                ("metallic_factor", Parameter::Float(self.metallic_factor)),
+                ("roughness_factor", Parameter::Float(self.roughness_factor)),
+                ("occlusion_tex", Parameter::Texture(self.occlusion.clone())),
+                (
+                    "emissive_tex",
+                    Parameter::SrgbTexture(self.emissive.clone()),
+                ),
+                ("emissive_factor", Parameter::Vec3(self.emissive_factor)),
+            ],
+        };
+        parameters
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use glium::uniforms::EmptyUniforms;
+
+    use crate::assets::AssetManager;
+    use crate::headless;
+
+    fn inputs<F: Facade>(facade: &F) -> StandardInputs {
+        StandardInputs::new(
+            facade,
+            Rc::new(texture::solid_srgb_texture(facade, [1.0, 1.0, 1.0])),
+            Rc::new(texture::flat_normal_map(facade)),
+        )
+    }
+
+    fn program<F: Facade>(facade: &F, fragment: &str) -> ProgramHandle {
+        let mut assets = AssetManager::new(AssetManager::default_root());
+        let mut load = |vertex| ShaderProgram::load(facade, &mut assets, vertex, fragment).unwrap();
+        Rc::new(RefCell::new(MaterialProgram {
+            single: load("shaders/phong.vert"),
+            instanced: load("shaders/instanced.vert"),
+        }))
+    }
+
+    #[test]
+    fn materials_fit_their_programs() {
+        let context = headless::create_context((1, 1)).unwrap();
+        for (shading, fragment) in [
+            (ShadingModel::BlinnPhong, "shaders/phong.frag"),
+            (ShadingModel::Pbr, "shaders/pbr.frag"),
+        ] {
+            let parameters = inputs(&context).parameters(shading);
+            assert!(Material::new(program(&context, fragment), parameters).is_ok());
+        }
+    }
+
+    #[test]
+    fn mismatches_are_reported() {
+        let context = headless::create_context((1, 1)).unwrap();
+        let phong = program(&context, "shaders/phong.frag");
+
+        // The Blinn-Phong shader has no metalness, and its normal map is a sampler
+        let pbr = inputs(&context).parameters(ShadingModel::Pbr);
+        assert_eq!(
+            Material::new(phong.clone(), pbr).err(),
+            Some(UniformError::Unknown("base_color_factor".to_string()))
+        );
+        let mut parameters = inputs(&context).parameters(ShadingModel::BlinnPhong);
+        parameters.insert("normal_tex".to_string(), Parameter::Float(1.0));
+        assert_eq!(
+            Material::new(phong.clone(), parameters).err(),
+            Some(UniformError::WrongType(
+                "normal_tex".to_string(),
+                glium::uniforms::UniformType::Sampler2d
+            ))
+        );
+
+        // A material alone leaves out the environment, lights and matrices
+        let parameters = inputs(&context).parameters(ShadingModel::BlinnPhong);
+        let material = Material::new(phong.clone(), parameters).unwrap();
+        assert_eq!(
+            phong
+                .borrow()
+                .single
+                .check_uniforms(&material.uniforms(EmptyUniforms)),
+            Err(UniformError::Missing("irradiance_map".to_string()))
+        );
+    }
+}
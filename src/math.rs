@@ -0,0 +1,820 @@
+// Small linear algebra library for the renderer.
+//
+// Matrices are column-major, the same layout glium expects for `mat3`/`mat4` uniforms, and
+// multiply column vectors (`projection * view * model * v`). The projection helpers follow the
+// left-handed convention the demo has always used: the camera looks down +Z.
+
+use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Div;
+use std::ops::Index;
+use std::ops::Mul;
+use std::ops::Neg;
+use std::ops::Sub;
+use std::ops::SubAssign;
+
+use glium::uniforms::AsUniformValue;
+use glium::uniforms::UniformValue;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Vec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Vec4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+/// 3x3 matrix stored as three columns.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mat3 {
+    pub cols: [Vec3; 3],
+}
+
+/// 4x4 matrix stored as four columns.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mat4 {
+    pub cols: [Vec4; 4],
+}
+
+/// Unit quaternion describing a rotation. `w` is the scalar part.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+// Only the tests build 2D vectors this way so far
+#[allow(dead_code)]
+pub const fn vec2(x: f32, y: f32) -> Vec2 {
+    Vec2 { x, y }
+}
+
+pub const fn vec3(x: f32, y: f32, z: f32) -> Vec3 {
+    Vec3 { x, y, z }
+}
+
+pub const fn vec4(x: f32, y: f32, z: f32, w: f32) -> Vec4 {
+    Vec4 { x, y, z, w }
+}
+
+// Implements the component-wise operators shared by all vector types.
+macro_rules! impl_vector {
+    ($name:ident { $($field:ident),+ }, $len:expr) => {
+        impl $name {
+            pub const ZERO: $name = $name { $($field: 0.0),+ };
+
+            pub fn dot(self, other: $name) -> f32 {
+                0.0 $(+ self.$field * other.$field)+
+            }
+
+            pub fn length_squared(self) -> f32 {
+                self.dot(self)
+            }
+
+            pub fn length(self) -> f32 {
+                self.length_squared().sqrt()
+            }
+
+            pub fn normalize(self) -> $name {
+                self / self.length()
+            }
+
+            pub fn lerp(self, other: $name, t: f32) -> $name {
+                self + (other - self) * t
+            }
+
+            pub fn min(self, other: $name) -> $name {
+                $name { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            pub fn max(self, other: $name) -> $name {
+                $name { $($field: self.$field.max(other.$field)),+ }
+            }
+
+            pub fn to_array(self) -> [f32; $len] {
+                [$(self.$field),+]
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, other: $name) -> $name {
+                $name { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: $name) {
+                *self = *self + other;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, other: $name) -> $name {
+                $name { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, other: $name) {
+                *self = *self - other;
+            }
+        }
+
+        impl Mul<f32> for $name {
+            type Output = $name;
+
+            fn mul(self, scalar: f32) -> $name {
+                $name { $($field: self.$field * scalar),+ }
+            }
+        }
+
+        impl Mul<$name> for f32 {
+            type Output = $name;
+
+            fn mul(self, vector: $name) -> $name {
+                vector * self
+            }
+        }
+
+        impl Div<f32> for $name {
+            type Output = $name;
+
+            fn div(self, scalar: f32) -> $name {
+                $name { $($field: self.$field / scalar),+ }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        impl From<[f32; $len]> for $name {
+            fn from(array: [f32; $len]) -> $name {
+                let [$($field),+] = array;
+                $name { $($field),+ }
+            }
+        }
+
+        impl From<$name> for [f32; $len] {
+            fn from(vector: $name) -> [f32; $len] {
+                vector.to_array()
+            }
+        }
+    };
+}
+
+impl_vector!(Vec2 { x, y }, 2);
+impl_vector!(Vec3 { x, y, z }, 3);
+impl_vector!(Vec4 { x, y, z, w }, 4);
+
+impl Vec3 {
+    pub const X: Vec3 = vec3(1.0, 0.0, 0.0);
+    pub const Y: Vec3 = vec3(0.0, 1.0, 0.0);
+    pub const Z: Vec3 = vec3(0.0, 0.0, 1.0);
+    pub const ONE: Vec3 = vec3(1.0, 1.0, 1.0);
+
+    pub fn cross(self, other: Vec3) -> Vec3 {
+        vec3(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn extend(self, w: f32) -> Vec4 {
+        vec4(self.x, self.y, self.z, w)
+    }
+}
+
+impl Vec4 {
+    pub fn truncate(self) -> Vec3 {
+        vec3(self.x, self.y, self.z)
+    }
+}
+
+impl Index<usize> for Vec3 {
+    type Output = f32;
+
+    fn index(&self, i: usize) -> &f32 {
+        match i {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("Vec3 index out of range: {}", i),
+        }
+    }
+}
+
+impl Index<usize> for Vec4 {
+    type Output = f32;
+
+    fn index(&self, i: usize) -> &f32 {
+        match i {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            3 => &self.w,
+            _ => panic!("Vec4 index out of range: {}", i),
+        }
+    }
+}
+
+impl Mat3 {
+    pub const IDENTITY: Mat3 = Mat3 {
+        cols: [Vec3::X, Vec3::Y, Vec3::Z],
+    };
+
+    pub fn from_cols(x: Vec3, y: Vec3, z: Vec3) -> Mat3 {
+        Mat3 { cols: [x, y, z] }
+    }
+
+    pub fn row(&self, i: usize) -> Vec3 {
+        vec3(self.cols[0][i], self.cols[1][i], self.cols[2][i])
+    }
+
+    pub fn transpose(&self) -> Mat3 {
+        Mat3::from_cols(self.row(0), self.row(1), self.row(2))
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let [a, b, c] = self.cols;
+        a.dot(b.cross(c))
+    }
+
+    /// Returns `None` when the matrix is singular.
+    pub fn inverse(&self) -> Option<Mat3> {
+        let [a, b, c] = self.cols;
+        let det = self.determinant();
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+        // The rows of the inverse are the cross products of the columns
+        let inverse = Mat3::from_cols(b.cross(c), c.cross(a), a.cross(b)).transpose();
+        Some(inverse * (1.0 / det))
+    }
+
+    pub fn to_cols_array(self) -> [[f32; 3]; 3] {
+        self.cols.map(Vec3::to_array)
+    }
+}
+
+impl Mat4 {
+    pub const IDENTITY: Mat4 = Mat4 {
+        cols: [
+            vec4(1.0, 0.0, 0.0, 0.0),
+            vec4(0.0, 1.0, 0.0, 0.0),
+            vec4(0.0, 0.0, 1.0, 0.0),
+            vec4(0.0, 0.0, 0.0, 1.0),
+        ],
+    };
+
+    pub fn from_cols(x: Vec4, y: Vec4, z: Vec4, w: Vec4) -> Mat4 {
+        Mat4 { cols: [x, y, z, w] }
+    }
+
+    pub fn from_translation(translation: Vec3) -> Mat4 {
+        let mut m = Mat4::IDENTITY;
+        m.cols[3] = translation.extend(1.0);
+        m
+    }
+
+    pub fn from_scale(scale: Vec3) -> Mat4 {
+        Mat4::from_cols(
+            vec4(scale.x, 0.0, 0.0, 0.0),
+            vec4(0.0, scale.y, 0.0, 0.0),
+            vec4(0.0, 0.0, scale.z, 0.0),
+            vec4(0.0, 0.0, 0.0, 1.0),
+        )
+    }
+
+    pub fn from_mat3(m: Mat3) -> Mat4 {
+        Mat4::from_cols(
+            m.cols[0].extend(0.0),
+            m.cols[1].extend(0.0),
+            m.cols[2].extend(0.0),
+            vec4(0.0, 0.0, 0.0, 1.0),
+        )
+    }
+
+    pub fn from_quat(rotation: Quat) -> Mat4 {
+        Mat4::from_mat3(rotation.to_mat3())
+    }
+
+    pub fn from_rotation_y(angle: f32) -> Mat4 {
+        Mat4::from_quat(Quat::from_axis_angle(Vec3::Y, angle))
+    }
+
+    /// Translation, rotation and scale combined, applied to points in the order scale first.
+    pub fn from_trs(translation: Vec3, rotation: Quat, scale: Vec3) -> Mat4 {
+        Mat4::from_translation(translation) * Mat4::from_quat(rotation) * Mat4::from_scale(scale)
+    }
+
+    /// Perspective projection. `aspect` is width divided by height.
+    pub fn perspective(fov_y: f32, aspect: f32, znear: f32, zfar: f32) -> Mat4 {
+        let f = 1.0 / (fov_y / 2.0).tan();
+        Mat4::from_cols(
+            vec4(f / aspect, 0.0, 0.0, 0.0),
+            vec4(0.0, f, 0.0, 0.0),
+            vec4(0.0, 0.0, (zfar + znear) / (zfar - znear), 1.0),
+            vec4(0.0, 0.0, -(2.0 * zfar * znear) / (zfar - znear), 0.0),
+        )
+    }
+
+    /// Orthographic projection of the box between the given planes onto clip space.
+    pub fn orthographic(
+        left: f32,
+        right: f32,
+        bottom: f32,
+        top: f32,
+        znear: f32,
+        zfar: f32,
+    ) -> Mat4 {
+        Mat4::from_cols(
+            vec4(2.0 / (right - left), 0.0, 0.0, 0.0),
+            vec4(0.0, 2.0 / (top - bottom), 0.0, 0.0),
+            vec4(0.0, 0.0, 2.0 / (zfar - znear), 0.0),
+            vec4(
+                -(right + left) / (right - left),
+                -(top + bottom) / (top - bottom),
+                -(zfar + znear) / (zfar - znear),
+                1.0,
+            ),
+        )
+    }
+
+    /// View matrix for a camera at `position` looking along `direction`.
+    pub fn look_to(position: Vec3, direction: Vec3, up: Vec3) -> Mat4 {
+        let f = direction.normalize();
+        let s = up.cross(f).normalize();
+        let u = f.cross(s);
+        Mat4::from_cols(
+            vec4(s.x, u.x, f.x, 0.0),
+            vec4(s.y, u.y, f.y, 0.0),
+            vec4(s.z, u.z, f.z, 0.0),
+            vec4(-position.dot(s), -position.dot(u), -position.dot(f), 1.0),
+        )
+    }
+
+    /// View matrix for a camera at `eye` looking at `target`.
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Mat4 {
+        Mat4::look_to(eye, target - eye, up)
+    }
+
+    pub fn row(&self, i: usize) -> Vec4 {
+        vec4(
+            self.cols[0][i],
+            self.cols[1][i],
+            self.cols[2][i],
+            self.cols[3][i],
+        )
+    }
+
+    pub fn transpose(&self) -> Mat4 {
+        Mat4::from_cols(self.row(0), self.row(1), self.row(2), self.row(3))
+    }
+
+    /// Upper-left 3x3 part, i.e. the matrix without its translation.
+    pub fn to_mat3(self) -> Mat3 {
+        Mat3::from_cols(
+            self.cols[0].truncate(),
+            self.cols[1].truncate(),
+            self.cols[2].truncate(),
+        )
+    }
+
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        let v = *self * point.extend(1.0);
+        v.truncate() / v.w
+    }
+
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        (*self * vector.extend(0.0)).truncate()
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let m = self.to_cols_array();
+        let (a, b) = Mat4::subfactors(&m);
+        a[0] * b[5] - a[1] * b[4] + a[2] * b[3] + a[3] * b[2] - a[4] * b[1] + a[5] * b[0]
+    }
+
+    /// Returns `None` when the matrix is singular.
+    pub fn inverse(&self) -> Option<Mat4> {
+        let m = self.to_cols_array();
+        let (a, b) = Mat4::subfactors(&m);
+        let det = a[0] * b[5] - a[1] * b[4] + a[2] * b[3] + a[3] * b[2] - a[4] * b[1] + a[5] * b[0];
+        if det.abs() < f32::EPSILON {
+            return None;
+        }
+        let inv = 1.0 / det;
+
+        // m[c][r] is column c, row r; the result is the adjugate divided by the determinant
+        let col = |c0: [f32; 4]| vec4(c0[0] * inv, c0[1] * inv, c0[2] * inv, c0[3] * inv);
+        Some(Mat4::from_cols(
+            col([
+                m[1][1] * b[5] - m[2][1] * b[4] + m[3][1] * b[3],
+                -m[0][1] * b[5] + m[2][1] * b[2] - m[3][1] * b[1],
+                m[0][1] * b[4] - m[1][1] * b[2] + m[3][1] * b[0],
+                -m[0][1] * b[3] + m[1][1] * b[1] - m[2][1] * b[0],
+            ]),
+            col([
+                -m[1][0] * b[5] + m[2][0] * b[4] - m[3][0] * b[3],
+                m[0][0] * b[5] - m[2][0] * b[2] + m[3][0] * b[1],
+                -m[0][0] * b[4] + m[1][0] * b[2] - m[3][0] * b[0],
+                m[0][0] * b[3] - m[1][0] * b[1] + m[2][0] * b[0],
+            ]),
+            col([
+                m[1][3] * a[5] - m[2][3] * a[4] + m[3][3] * a[3],
+                -m[0][3] * a[5] + m[2][3] * a[2] - m[3][3] * a[1],
+                m[0][3] * a[4] - m[1][3] * a[2] + m[3][3] * a[0],
+                -m[0][3] * a[3] + m[1][3] * a[1] - m[2][3] * a[0],
+            ]),
+            col([
+                -m[1][2] * a[5] + m[2][2] * a[4] - m[3][2] * a[3],
+                m[0][2] * a[5] - m[2][2] * a[2] + m[3][2] * a[1],
+                -m[0][2] * a[4] + m[1][2] * a[2] - m[3][2] * a[0],
+                m[0][2] * a[3] - m[1][2] * a[1] + m[2][2] * a[0],
+            ]),
+        ))
+    }
+
+    // 2x2 determinants of the top two rows (`a`) and bottom two rows (`b`), shared by the
+    // determinant and the inverse.
+    fn subfactors(m: &[[f32; 4]; 4]) -> ([f32; 6], [f32; 6]) {
+        let a = [
+            m[0][0] * m[1][1] - m[1][0] * m[0][1],
+            m[0][0] * m[2][1] - m[2][0] * m[0][1],
+            m[0][0] * m[3][1] - m[3][0] * m[0][1],
+            m[1][0] * m[2][1] - m[2][0] * m[1][1],
+            m[1][0] * m[3][1] - m[3][0] * m[1][1],
+            m[2][0] * m[3][1] - m[3][0] * m[2][1],
+        ];
+        let b = [
+            m[0][2] * m[1][3] - m[1][2] * m[0][3],
+            m[0][2] * m[2][3] - m[2][2] * m[0][3],
+            m[0][2] * m[3][3] - m[3][2] * m[0][3],
+            m[1][2] * m[2][3] - m[2][2] * m[1][3],
+            m[1][2] * m[3][3] - m[3][2] * m[1][3],
+            m[2][2] * m[3][3] - m[3][2] * m[2][3],
+        ];
+        (a, b)
+    }
+
+    pub fn to_cols_array(self) -> [[f32; 4]; 4] {
+        self.cols.map(Vec4::to_array)
+    }
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    /// Rotation of `angle` radians around `axis`, counter-clockwise when looking down the axis.
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Quat {
+        let axis = axis.normalize();
+        let (s, c) = (angle / 2.0).sin_cos();
+        Quat {
+            x: axis.x * s,
+            y: axis.y * s,
+            z: axis.z * s,
+            w: c,
+        }
+    }
+
+    pub fn length(self) -> f32 {
+        (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt()
+    }
+
+    pub fn normalize(self) -> Quat {
+        let len = self.length();
+        Quat {
+            x: self.x / len,
+            y: self.y / len,
+            z: self.z / len,
+            w: self.w / len,
+        }
+    }
+
+    pub fn conjugate(self) -> Quat {
+        Quat {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+            w: self.w,
+        }
+    }
+
+    pub fn to_mat3(self) -> Mat3 {
+        let Quat { x, y, z, w } = self;
+        Mat3::from_cols(
+            vec3(
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y + w * z),
+                2.0 * (x * z - w * y),
+            ),
+            vec3(
+                2.0 * (x * y - w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z + w * x),
+            ),
+            vec3(
+                2.0 * (x * z + w * y),
+                2.0 * (y * z - w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ),
+        )
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, other: Mat3) -> Mat3 {
+        Mat3 {
+            cols: other.cols.map(|c| self * c),
+        }
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        self.cols[0] * v.x + self.cols[1] * v.y + self.cols[2] * v.z
+    }
+}
+
+impl Mul<f32> for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, scalar: f32) -> Mat3 {
+        Mat3 {
+            cols: self.cols.map(|c| c * scalar),
+        }
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: Mat4) -> Mat4 {
+        Mat4 {
+            cols: other.cols.map(|c| self * c),
+        }
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+
+    fn mul(self, v: Vec4) -> Vec4 {
+        self.cols[0] * v.x + self.cols[1] * v.y + self.cols[2] * v.z + self.cols[3] * v.w
+    }
+}
+
+impl Mul for Quat {
+    type Output = Quat;
+
+    /// Rotation by `other` followed by `self`.
+    fn mul(self, other: Quat) -> Quat {
+        Quat {
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        }
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        let u = vec3(self.x, self.y, self.z);
+        let t = u.cross(v) * 2.0;
+        v + t * self.w + u.cross(t)
+    }
+}
+
+impl From<Mat4> for [[f32; 4]; 4] {
+    fn from(m: Mat4) -> [[f32; 4]; 4] {
+        m.to_cols_array()
+    }
+}
+
+impl From<[[f32; 4]; 4]> for Mat4 {
+    fn from(cols: [[f32; 4]; 4]) -> Mat4 {
+        Mat4 {
+            cols: cols.map(Vec4::from),
+        }
+    }
+}
+
+impl From<Mat3> for [[f32; 3]; 3] {
+    fn from(m: Mat3) -> [[f32; 3]; 3] {
+        m.to_cols_array()
+    }
+}
+
+impl AsUniformValue for Vec2 {
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec2(self.to_array())
+    }
+}
+
+impl AsUniformValue for Vec3 {
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec3(self.to_array())
+    }
+}
+
+impl AsUniformValue for Vec4 {
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Vec4(self.to_array())
+    }
+}
+
+impl AsUniformValue for Mat3 {
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Mat3(self.to_cols_array())
+    }
+}
+
+impl AsUniformValue for Mat4 {
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Mat4(self.to_cols_array())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f32::consts::FRAC_PI_2;
+    use std::f32::consts::FRAC_PI_3;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    fn assert_mat4_eq(a: Mat4, b: Mat4) {
+        for (ca, cb) in a.cols.iter().zip(b.cols.iter()) {
+            assert!((*ca - *cb).length() < 1e-5, "{:?}\n!=\n{:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn vector_products() {
+        assert_eq!(vec3(1.0, 2.0, 3.0).dot(vec3(4.0, -5.0, 6.0)), 12.0);
+        assert_eq!(Vec3::X.cross(Vec3::Y), Vec3::Z);
+        assert_eq!(Vec3::Y.cross(Vec3::Z), Vec3::X);
+        assert_eq!(vec2(3.0, 4.0).length(), 5.0);
+        assert_eq!(vec4(1.0, 2.0, 3.0, 4.0) * 2.0, vec4(2.0, 4.0, 6.0, 8.0));
+    }
+
+    #[test]
+    fn matrix_product_composes_transforms() {
+        let translate = Mat4::from_translation(vec3(1.0, 2.0, 3.0));
+        let scale = Mat4::from_scale(vec3(2.0, 2.0, 2.0));
+
+        // Scale first, then translate
+        let m = translate * scale;
+        assert_vec3_eq(m.transform_point(vec3(1.0, 1.0, 1.0)), vec3(3.0, 4.0, 5.0));
+        assert_vec3_eq(m.transform_vector(vec3(1.0, 1.0, 1.0)), vec3(2.0, 2.0, 2.0));
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let m = Mat4::from_translation(vec3(1.0, 2.0, 3.0));
+        assert_eq!(m.transpose().row(3), vec4(1.0, 2.0, 3.0, 1.0));
+        assert_eq!(m.transpose().transpose(), m);
+    }
+
+    #[test]
+    fn inverse_of_known_matrix() {
+        let m: Mat4 = [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 4.0, 0.0, 0.0],
+            [0.0, 0.0, 8.0, 0.0],
+            [1.0, 2.0, 3.0, 1.0],
+        ]
+        .into();
+        let expected: Mat4 = [
+            [0.5, 0.0, 0.0, 0.0],
+            [0.0, 0.25, 0.0, 0.0],
+            [0.0, 0.0, 0.125, 0.0],
+            [-0.5, -0.5, -0.375, 1.0],
+        ]
+        .into();
+        assert_mat4_eq(m.inverse().unwrap(), expected);
+        assert_eq!(m.determinant(), 64.0);
+    }
+
+    #[test]
+    fn inverse_round_trips() {
+        let m = Mat4::from_trs(
+            vec3(0.5, -2.0, 7.0),
+            Quat::from_axis_angle(vec3(1.0, 1.0, 0.0), 0.7),
+            vec3(1.0, 2.0, 3.0),
+        );
+        assert_mat4_eq(m * m.inverse().unwrap(), Mat4::IDENTITY);
+
+        let m3 = m.to_mat3();
+        let product = m3 * m3.inverse().unwrap();
+        for (c, e) in product.cols.iter().zip(Mat3::IDENTITY.cols.iter()) {
+            assert_vec3_eq(*c, *e);
+        }
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        let m = Mat4::from_scale(vec3(1.0, 0.0, 1.0));
+        assert!(m.inverse().is_none());
+        assert!(m.to_mat3().inverse().is_none());
+    }
+
+    #[test]
+    fn quaternion_rotation_matches_matrix() {
+        let q = Quat::from_axis_angle(Vec3::Z, FRAC_PI_2);
+        assert_vec3_eq(q * Vec3::X, Vec3::Y);
+        assert_vec3_eq(q.to_mat3() * Vec3::X, Vec3::Y);
+
+        // Two quarter turns make a half turn
+        assert_vec3_eq((q * q) * Vec3::X, -Vec3::X);
+        assert_vec3_eq(q.conjugate() * (q * Vec3::Y), Vec3::Y);
+    }
+
+    #[test]
+    fn rotation_y_turns_z_towards_x() {
+        let m = Mat4::from_rotation_y(FRAC_PI_2);
+        assert_vec3_eq(m.transform_vector(Vec3::Z), Vec3::X);
+        assert_vec3_eq(m.transform_vector(Vec3::X), -Vec3::Z);
+    }
+
+    #[test]
+    fn perspective_maps_near_and_far_planes() {
+        let m = Mat4::perspective(FRAC_PI_3, 4.0 / 3.0, 0.1, 1024.0);
+
+        assert!((m.transform_point(vec3(0.0, 0.0, 0.1)).z + 1.0).abs() < 1e-4);
+        assert!((m.transform_point(vec3(0.0, 0.0, 1024.0)).z - 1.0).abs() < 1e-4);
+
+        // The top of the field of view lands on the top of the screen
+        let top = (FRAC_PI_3 / 2.0).tan();
+        assert!((m.transform_point(vec3(0.0, top, 1.0)).y - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn orthographic_maps_box_to_clip_space() {
+        let m = Mat4::orthographic(-2.0, 2.0, -1.0, 1.0, 1.0, 11.0);
+        assert_vec3_eq(
+            m.transform_point(vec3(-2.0, -1.0, 1.0)),
+            vec3(-1.0, -1.0, -1.0),
+        );
+        assert_vec3_eq(m.transform_point(vec3(2.0, 1.0, 11.0)), vec3(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn look_at_moves_target_onto_positive_z() {
+        let eye = vec3(0.5, 0.2, -3.0);
+        let view = Mat4::look_at(eye, Vec3::ZERO, Vec3::Y);
+
+        assert_vec3_eq(view.transform_point(eye), Vec3::ZERO);
+        let target = view.transform_point(Vec3::ZERO);
+        assert_vec3_eq(target, vec3(0.0, 0.0, eye.length()));
+    }
+
+    #[test]
+    fn uniform_conversion_is_column_major() {
+        let m = Mat4::from_translation(vec3(1.0, 2.0, 3.0));
+        match m.as_uniform_value() {
+            UniformValue::Mat4(cols) => assert_eq!(cols[3], [1.0, 2.0, 3.0, 1.0]),
+            _ => panic!("expected a mat4 uniform"),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use std::f32::consts::PI;
+use std::rc::Rc;
+
+use glium::backend::Facade;
+use glium::index::PrimitiveType;
+use glium::uniforms::Uniforms;
+use glium::DrawParameters;
+use glium::IndexBuffer;
+use glium::Program;
+use glium::Surface;
+use glium::VertexBuffer;
+
+use crate::bounds::Aabb;
+use crate::instancing::Instance;
+use crate::material::Material;
+use crate::math::vec3;
+use crate::math::Vec3;
+use crate::teapot;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub tex_coords: [f32; 2],
+    /// Direction of increasing U, with the sign of the bitangent `cross(normal, tangent)` in W.
+    pub tangent: [f32; 4],
+}
+
+implement_vertex!(Vertex, position, normal, tex_coords, tangent);
+
+/// Triangle list geometry kept on the CPU, before it is uploaded.
+#[derive(Clone, Debug, Default)]
+pub struct MeshData {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+}
+
+impl MeshData {
+    /// A 2x2 square in the XY plane, facing -Z.
+    pub fn quad() -> Self {
+        let vertex = |x: f32, y: f32| Vertex {
+            position: [x, y, 0.0],
+            normal: [0.0, 0.0, -1.0],
+            tex_coords: [(x + 1.0) / 2.0, (y + 1.0) / 2.0],
+            tangent: [0.0; 4],
+        };
+
+        let mut data = MeshData {
+            vertices: vec![
+                vertex(-1.0, 1.0),
+                vertex(1.0, 1.0),
+                vertex(-1.0, -1.0),
+                vertex(1.0, -1.0),
+            ],
+            indices: vec![0, 1, 2, 2, 1, 3],
+        };
+        data.compute_tangents();
+        data
+    }
+
+    /// The Utah teapot from `teapot.rs`, centered on the origin and scaled to roughly the size
+    /// of the quad.
+    ///
+    /// The original model has no texture coordinates, so they are generated with a cylindrical
+    /// projection around the Y axis.
+    pub fn teapot() -> Self {
+        let positions: Vec<Vec3> = teapot::VERTICES
+            .iter()
+            .map(|v| vec3(v.position.0, v.position.1, v.position.2))
+            .collect();
+
+        // Skip the dummy first vertex when measuring the model
+        let (min, max) = positions[1..]
+            .iter()
+            .fold((positions[1], positions[1]), |(min, max), &p| {
+                (min.min(p), max.max(p))
+            });
+        let center = (min + max) / 2.0;
+        let extent = max - min;
+        let scale = 2.0 / extent.x.max(extent.y).max(extent.z);
+
+        let vertices = positions
+            .iter()
+            .zip(teapot::NORMALS.iter())
+            .map(|(&p, n)| {
+                let p = (p - center) * scale;
+                let u = p.z.atan2(p.x) / (2.0 * PI) + 0.5;
+                let v = p.y / (extent.y * scale) + 0.5;
+                Vertex {
+                    position: p.to_array(),
+                    normal: [n.normal.0, n.normal.1, n.normal.2],
+                    tex_coords: [u, v],
+                    tangent: [0.0; 4],
+                }
+            })
+            .collect();
+
+        let mut data = MeshData {
+            vertices,
+            indices: teapot::INDICES.iter().map(|&i| i as u32).collect(),
+        };
+        data.compute_tangents();
+        data
+    }
+
+    /// Replaces the normals with smooth ones averaged from the faces around each vertex,
+    /// weighted by face area.
+    pub fn compute_normals(&mut self) {
+        let mut normals = vec![Vec3::ZERO; self.vertices.len()];
+        for triangle in self.indices.chunks_exact(3) {
+            let [a, b, c] =
+                [0, 1, 2].map(|i| Vec3::from(self.vertices[triangle[i] as usize].position));
+            let face_normal = (b - a).cross(c - a);
+            for &i in triangle {
+                normals[i as usize] += face_normal;
+            }
+        }
+
+        for (vertex, normal) in self.vertices.iter_mut().zip(normals) {
+            vertex.normal = if normal.length() > 0.0 {
+                normal.normalize().to_array()
+            } else {
+                [0.0, 1.0, 0.0]
+            };
+        }
+    }
+
+    /// The box around all vertices. Empty meshes get a box of zero size at the origin.
+    pub fn aabb(&self) -> Aabb {
+        Aabb::from_points(self.vertices.iter().map(|v| Vec3::from(v.position))).unwrap_or(Aabb {
+            min: Vec3::ZERO,
+            max: Vec3::ZERO,
+        })
+    }
+
+    /// A sphere around all vertices, as its center and radius. The center is the middle of the
+    /// bounding box, which is close to the smallest sphere for most models.
+    pub fn bounding_sphere(&self) -> (Vec3, f32) {
+        let center = self.aabb().center();
+        let radius = self
+            .vertices
+            .iter()
+            .map(|v| (Vec3::from(v.position) - center).length())
+            .fold(0.0, f32::max);
+        (center, radius)
+    }
+
+    /// Computes per-vertex tangents from the texture coordinates, for normal mapping.
+    ///
+    /// Like MikkTSpace, the tangent of each triangle is accumulated on its vertices and then
+    /// made orthogonal to the vertex normal, and the bitangent's direction is kept as a sign.
+    /// Vertices without usable texture coordinates get an arbitrary tangent perpendicular to
+    /// the normal.
+    pub fn compute_tangents(&mut self) {
+        let mut tangents = vec![Vec3::ZERO; self.vertices.len()];
+        let mut bitangents = vec![Vec3::ZERO; self.vertices.len()];
+
+        for triangle in self.indices.chunks_exact(3) {
+            let [v0, v1, v2] = [0, 1, 2].map(|i| self.vertices[triangle[i] as usize]);
+            let e1 = Vec3::from(v1.position) - Vec3::from(v0.position);
+            let e2 = Vec3::from(v2.position) - Vec3::from(v0.position);
+            let (du1, dv1) = (
+                v1.tex_coords[0] - v0.tex_coords[0],
+                v1.tex_coords[1] - v0.tex_coords[1],
+            );
+            let (du2, dv2) = (
+                v2.tex_coords[0] - v0.tex_coords[0],
+                v2.tex_coords[1] - v0.tex_coords[1],
+            );
+
+            let det = du1 * dv2 - du2 * dv1;
+            if det.abs() < f32::EPSILON {
+                continue;
+            }
+            let tangent = (e1 * dv2 - e2 * dv1) / det;
+            let bitangent = (e2 * du1 - e1 * du2) / det;
+            for &i in triangle {
+                tangents[i as usize] += tangent;
+                bitangents[i as usize] += bitangent;
+            }
+        }
+
+        for (i, vertex) in self.vertices.iter_mut().enumerate() {
+            let normal = Vec3::from(vertex.normal);
+            let tangent = tangents[i] - normal * normal.dot(tangents[i]);
+            let tangent = if tangent.length() > 1e-6 {
+                tangent.normalize()
+            } else {
+                // Any direction in the surface will do
+                let axis = if normal.x.abs() < 0.9 {
+                    Vec3::X
+                } else {
+                    Vec3::Y
+                };
+                normal.cross(axis).normalize()
+            };
+            let handedness = if normal.cross(tangent).dot(bitangents[i]) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            vertex.tangent = tangent.extend(handedness).to_array();
+        }
+    }
+}
+
+/// Geometry uploaded to the GPU, ready to be drawn.
+pub struct Mesh {
+    vertices: VertexBuffer<Vertex>,
+    indices: IndexBuffer<u32>,
+    /// Overrides the renderer's default material when set.
+    pub material: Option<Rc<Material>>,
+    aabb: Aabb,
+    bounding_sphere: (Vec3, f32),
+}
+
+impl Mesh {
+    pub fn new<F: Facade>(facade: &F, data: &MeshData) -> Self {
+        Mesh {
+            vertices: VertexBuffer::new(facade, &data.vertices).unwrap(),
+            indices: IndexBuffer::new(facade, PrimitiveType::TrianglesList, &data.indices).unwrap(),
+            material: None,
+            aabb: data.aabb(),
+            bounding_sphere: data.bounding_sphere(),
+        }
+    }
+
+    /// The box around the mesh, in its own coordinates.
+    pub fn aabb(&self) -> Aabb {
+        self.aabb
+    }
+
+    /// Center and radius of a sphere containing the mesh, in its own coordinates.
+    pub fn bounding_sphere(&self) -> (Vec3, f32) {
+        self.bounding_sphere
+    }
+
+    pub fn draw<S: Surface, U: Uniforms>(
+        &self,
+        target: &mut S,
+        program: &Program,
+        uniforms: &U,
+        params: &DrawParameters,
+    ) {
+        target
+            .draw(&self.vertices, &self.indices, program, uniforms, params)
+            .unwrap();
+    }
+
+    /// Draws a copy of the mesh for each element of `instances`, whose attributes the program
+    /// reads alongside the mesh's own.
+    pub fn draw_instanced<S: Surface, U: Uniforms>(
+        &self,
+        target: &mut S,
+        program: &Program,
+        instances: &VertexBuffer<Instance>,
+        uniforms: &U,
+        params: &DrawParameters,
+    ) {
+        let vertices = (&self.vertices, instances.per_instance().unwrap());
+        target
+            .draw(vertices, &self.indices, program, uniforms, params)
+            .unwrap();
+    }
+}
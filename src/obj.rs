@@ -0,0 +1,552 @@
+// Wavefront OBJ/MTL loader producing the renderer's `Vertex` layout.
+//
+// Supported: `v`, `vt`, `vn`, `f` (any polygon, fan triangulated, negative indices), `o`/`g`,
+// `usemtl` and `mtllib`; in MTL files `newmtl`, `Kd`, `Ke`, `map_Kd`, a normal map given by
+// `map_Bump`, `bump` or `norm`, and `Pr`/`Pm` from the PBR extension. Anything else is ignored.
+// Faces without normals get smooth normals computed from the surrounding faces, faces without
+// texture coordinates get (0, 0). Tangents are always computed.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::math::vec3;
+use crate::math::Vec3;
+use crate::mesh::MeshData;
+use crate::mesh::Vertex;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjMaterial {
+    pub name: String,
+    pub diffuse_color: [f32; 3],
+    /// Path of the diffuse texture, relative to the MTL file until resolved by `load`.
+    pub diffuse_map: Option<PathBuf>,
+    /// Path of the tangent-space normal map, resolved like `diffuse_map`.
+    pub normal_map: Option<PathBuf>,
+    pub emissive_color: [f32; 3],
+    /// Given by `Pr`; a material with roughness or metalness is meant for PBR shading.
+    pub roughness: Option<f32>,
+    /// Given by `Pm`.
+    pub metallic: Option<f32>,
+}
+
+impl ObjMaterial {
+    /// Whether the material uses the PBR extension rather than plain colors.
+    pub fn is_pbr(&self) -> bool {
+        self.roughness.is_some() || self.metallic.is_some()
+    }
+}
+
+/// The faces of one object/group that share a material.
+#[derive(Clone, Debug)]
+pub struct ObjGroup {
+    pub name: String,
+    pub material: Option<String>,
+    pub data: MeshData,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ObjModel {
+    pub groups: Vec<ObjGroup>,
+    pub materials: Vec<ObjMaterial>,
+    /// Names of the MTL files referenced by `mtllib`.
+    pub material_libraries: Vec<String>,
+}
+
+impl ObjModel {
+    pub fn material(&self, name: &str) -> Option<&ObjMaterial> {
+        self.materials.iter().find(|m| m.name == name)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, ParseError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            LoadError::Parse(path, e) => write!(f, "{}:{}: {}", path.display(), e.line, e.message),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// Loads an OBJ file together with the MTL files it references, which are looked up next to
+/// it. Texture paths in the materials are resolved relative to their MTL file.
+pub fn load(path: &Path) -> Result<ObjModel, LoadError> {
+    let read = |path: &Path| {
+        std::fs::read_to_string(path).map_err(|e| LoadError::Io(path.to_path_buf(), e))
+    };
+
+    let source = read(path)?;
+    let mut model = parse(&source).map_err(|e| LoadError::Parse(path.to_path_buf(), e))?;
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    for library in &model.material_libraries {
+        let mtl_path = dir.join(library);
+        let source = read(&mtl_path)?;
+        let materials = parse_mtl(&source).map_err(|e| LoadError::Parse(mtl_path.clone(), e))?;
+
+        let mtl_dir = mtl_path.parent().unwrap_or(Path::new(""));
+        model
+            .materials
+            .extend(materials.into_iter().map(|mut material| {
+                material.diffuse_map = material.diffuse_map.map(|map| mtl_dir.join(map));
+                material.normal_map = material.normal_map.map(|map| mtl_dir.join(map));
+                material
+            }));
+    }
+
+    Ok(model)
+}
+
+// Indices of a face corner into the position, texture coordinate and normal lists
+type Corner = (usize, Option<usize>, Option<usize>);
+
+// Faces collected for the current group, before they are turned into vertices
+struct GroupBuilder {
+    name: String,
+    material: Option<String>,
+    triangles: Vec<[Corner; 3]>,
+}
+
+impl GroupBuilder {
+    fn new(name: String, material: Option<String>) -> Self {
+        GroupBuilder {
+            name,
+            material,
+            triangles: Vec::new(),
+        }
+    }
+}
+
+/// Parses the contents of an OBJ file. `mtllib` statements are recorded but not followed.
+pub fn parse(source: &str) -> Result<ObjModel, ParseError> {
+    let mut positions = Vec::new();
+    let mut tex_coords = Vec::new();
+    let mut normals = Vec::new();
+
+    let mut model = ObjModel::default();
+    let mut groups = vec![GroupBuilder::new("default".to_string(), None)];
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let error = |message: String| ParseError {
+            line: line_number,
+            message,
+        };
+
+        let line = line.split('#').next().unwrap().trim();
+        let mut words = line.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+
+        match keyword {
+            "v" => positions.push(parse_floats::<3>(words, 3).map_err(error)?),
+            "vt" => {
+                let uv = parse_floats::<2>(words, 1).map_err(error)?;
+                tex_coords.push(uv);
+            }
+            "vn" => normals.push(parse_floats::<3>(words, 3).map_err(error)?),
+            "f" => {
+                let corners = words
+                    .map(|word| {
+                        parse_corner(word, positions.len(), tex_coords.len(), normals.len())
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(error)?;
+                if corners.len() < 3 {
+                    return Err(error(format!(
+                        "face needs at least 3 vertices, found {}",
+                        corners.len()
+                    )));
+                }
+
+                // Fan triangulation, which is correct for the convex polygons exporters emit
+                let group = groups.last_mut().unwrap();
+                for k in 1..corners.len() - 1 {
+                    group
+                        .triangles
+                        .push([corners[0], corners[k], corners[k + 1]]);
+                }
+            }
+            "o" | "g" => {
+                let name = words.collect::<Vec<_>>().join(" ");
+                let material = groups.last().unwrap().material.clone();
+                groups.push(GroupBuilder::new(name, material));
+            }
+            "usemtl" => {
+                let material = words
+                    .next()
+                    .ok_or_else(|| error("usemtl without a material name".to_string()))?;
+                let name = groups.last().unwrap().name.clone();
+                groups.push(GroupBuilder::new(name, Some(material.to_string())));
+            }
+            "mtllib" => model.material_libraries.extend(words.map(str::to_string)),
+            // Smoothing groups, lines, points and free-form geometry
+            _ => (),
+        }
+    }
+
+    model.groups = groups
+        .into_iter()
+        .filter(|group| !group.triangles.is_empty())
+        .map(|group| ObjGroup {
+            data: build_mesh(&group.triangles, &positions, &tex_coords, &normals),
+            name: group.name,
+            material: group.material,
+        })
+        .collect();
+
+    Ok(model)
+}
+
+/// Parses the contents of an MTL file.
+pub fn parse_mtl(source: &str) -> Result<Vec<ObjMaterial>, ParseError> {
+    let mut materials: Vec<ObjMaterial> = Vec::new();
+
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let error = |message: String| ParseError {
+            line: line_number,
+            message,
+        };
+
+        let line = line.split('#').next().unwrap().trim();
+        let mut words = line.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+
+        if keyword == "newmtl" {
+            let name = words
+                .next()
+                .ok_or_else(|| error("newmtl without a material name".to_string()))?;
+            materials.push(ObjMaterial {
+                name: name.to_string(),
+                diffuse_color: [1.0, 1.0, 1.0],
+                diffuse_map: None,
+                normal_map: None,
+                emissive_color: [0.0, 0.0, 0.0],
+                roughness: None,
+                metallic: None,
+            });
+            continue;
+        }
+
+        let Some(material) = materials.last_mut() else {
+            return Err(error(format!("{} before any newmtl", keyword)));
+        };
+        match keyword {
+            "Kd" => material.diffuse_color = parse_floats::<3>(words, 3).map_err(error)?,
+            "Ke" => material.emissive_color = parse_floats::<3>(words, 3).map_err(error)?,
+            "Pr" => material.roughness = Some(parse_floats::<1>(words, 1).map_err(error)?[0]),
+            "Pm" => material.metallic = Some(parse_floats::<1>(words, 1).map_err(error)?[0]),
+            "map_Kd" => {
+                // Options such as `-bm 1.0` come before the file name
+                let file = words
+                    .last()
+                    .ok_or_else(|| error("map_Kd without a file name".to_string()))?;
+                material.diffuse_map = Some(PathBuf::from(file));
+            }
+            "map_Bump" | "map_bump" | "bump" | "norm" => {
+                let file = words
+                    .last()
+                    .ok_or_else(|| error(format!("{} without a file name", keyword)))?;
+                material.normal_map = Some(PathBuf::from(file));
+            }
+            _ => (),
+        }
+    }
+
+    Ok(materials)
+}
+
+// Parses up to N floats, requiring at least `required` of them. Missing ones are zero.
+fn parse_floats<'a, const N: usize>(
+    words: impl Iterator<Item = &'a str>,
+    required: usize,
+) -> Result<[f32; N], String> {
+    let mut values = [0.0; N];
+    let mut count = 0;
+    for word in words.take(N) {
+        values[count] = word
+            .parse()
+            .map_err(|_| format!("invalid number {:?}", word))?;
+        count += 1;
+    }
+    if count < required {
+        return Err(format!("expected {} numbers, found {}", required, count));
+    }
+    Ok(values)
+}
+
+// Parses `v`, `v/vt`, `v//vn` or `v/vt/vn` into zero-based indices
+fn parse_corner(
+    word: &str,
+    position_count: usize,
+    tex_coord_count: usize,
+    normal_count: usize,
+) -> Result<Corner, String> {
+    let mut parts = word.split('/');
+    let position = parse_index(parts.next().unwrap(), position_count, "vertex")?;
+    let tex_coords = match parts.next() {
+        None | Some("") => None,
+        Some(index) => Some(parse_index(index, tex_coord_count, "texture coordinate")?),
+    };
+    let normal = match parts.next() {
+        None | Some("") => None,
+        Some(index) => Some(parse_index(index, normal_count, "normal")?),
+    };
+    if parts.next().is_some() {
+        return Err(format!("invalid face vertex {:?}", word));
+    }
+    Ok((position, tex_coords, normal))
+}
+
+// OBJ indices start at 1; negative ones count back from the most recent element
+fn parse_index(word: &str, count: usize, kind: &str) -> Result<usize, String> {
+    let index: i64 = word
+        .parse()
+        .map_err(|_| format!("invalid {} index {:?}", kind, word))?;
+    let resolved = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+    if index == 0 || resolved < 0 || resolved >= count as i64 {
+        return Err(format!(
+            "{} index {} out of range ({} defined so far)",
+            kind, index, count
+        ));
+    }
+    Ok(resolved as usize)
+}
+
+fn build_mesh(
+    triangles: &[[Corner; 3]],
+    positions: &[[f32; 3]],
+    tex_coords: &[[f32; 2]],
+    normals: &[[f32; 3]],
+) -> MeshData {
+    let mut data = MeshData::default();
+    let mut lookup: HashMap<Corner, u32> = HashMap::new();
+
+    // Face normals summed per position, for corners that don't specify a normal. Larger faces
+    // contribute more since the cross product isn't normalized.
+    let mut smooth_normals: HashMap<usize, Vec3> = HashMap::new();
+    for triangle in triangles {
+        let [a, b, c] = triangle.map(|(p, _, _)| Vec3::from(positions[p]));
+        let face_normal = (b - a).cross(c - a);
+        for &(p, _, n) in triangle {
+            if n.is_none() {
+                *smooth_normals.entry(p).or_default() += face_normal;
+            }
+        }
+    }
+
+    for triangle in triangles {
+        for &corner in triangle {
+            let index = *lookup.entry(corner).or_insert_with(|| {
+                let (p, t, n) = corner;
+                let normal = match n {
+                    Some(n) => normals[n],
+                    None => {
+                        let sum = smooth_normals[&p];
+                        if sum.length() > 0.0 {
+                            sum.normalize().to_array()
+                        } else {
+                            vec3(0.0, 1.0, 0.0).to_array()
+                        }
+                    }
+                };
+                data.vertices.push(Vertex {
+                    position: positions[p],
+                    normal,
+                    tex_coords: t.map_or([0.0, 0.0], |t| tex_coords[t]),
+                    tangent: [0.0; 4],
+                });
+                data.vertices.len() as u32 - 1
+            });
+            data.indices.push(index);
+        }
+    }
+
+    data.compute_tangents();
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "
+        # a unit square in the XY plane
+        v 0 0 0
+        v 1 0 0
+        v 1 1 0
+        v 0 1 0
+        vt 0 0
+        vt 1 0
+        vt 1 1
+        vt 0 1
+        vn 0 0 1
+        f 1/1/1 2/2/1 3/3/1 4/4/1
+    ";
+
+    #[test]
+    fn quad_is_triangulated() {
+        let model = parse(SQUARE).unwrap();
+        assert_eq!(model.groups.len(), 1);
+
+        let data = &model.groups[0].data;
+        assert_eq!(data.vertices.len(), 4);
+        assert_eq!(data.indices, vec![0, 1, 2, 0, 2, 3]);
+        assert_eq!(data.vertices[2].tex_coords, [1.0, 1.0]);
+        assert_eq!(data.vertices[2].normal, [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn negative_indices_count_from_the_end() {
+        let source = "
+            v 0 0 0
+            v 1 0 0
+            v 0 1 0
+            f -3 -2 -1
+        ";
+        let data = &parse(source).unwrap().groups[0].data;
+        assert_eq!(data.vertices[0].position, [0.0, 0.0, 0.0]);
+        assert_eq!(data.vertices[2].position, [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn missing_normals_and_tex_coords_are_filled_in() {
+        let source = "
+            v 0 0 0
+            v 1 0 0
+            v 0 1 0
+            v 1 1 0
+            f 1 2 3
+            f 2 4 3
+        ";
+        let data = &parse(source).unwrap().groups[0].data;
+        for vertex in &data.vertices {
+            assert_eq!(vertex.normal, [0.0, 0.0, 1.0]);
+            assert_eq!(vertex.tex_coords, [0.0, 0.0]);
+        }
+        // Shared corners are only emitted once
+        assert_eq!(data.vertices.len(), 4);
+    }
+
+    #[test]
+    fn materials_split_groups() {
+        let source = "
+            mtllib scene.mtl
+            v 0 0 0
+            v 1 0 0
+            v 0 1 0
+            o thing
+            usemtl red
+            f 1 2 3
+            usemtl blue
+            f 3 2 1
+        ";
+        let model = parse(source).unwrap();
+        assert_eq!(model.material_libraries, vec!["scene.mtl"]);
+
+        let groups: Vec<_> = model
+            .groups
+            .iter()
+            .map(|g| (g.name.as_str(), g.material.as_deref()))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![("thing", Some("red")), ("thing", Some("blue"))]
+        );
+    }
+
+    #[test]
+    fn errors_report_line_numbers() {
+        let source = "v 0 0 0\nv 1 0 0\nf 1 2 3\n";
+        assert_eq!(
+            parse(source).unwrap_err(),
+            ParseError {
+                line: 3,
+                message: "vertex index 3 out of range (2 defined so far)".to_string()
+            }
+        );
+
+        let source = "v 0 zero 0\n";
+        assert_eq!(parse(source).unwrap_err().line, 1);
+
+        let source = "v 0 0 0\nf 1 1\n";
+        assert_eq!(parse(source).unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn mtl_colors_and_maps() {
+        let source = "
+            newmtl brick
+            Kd 0.5 0.25 1.0
+            map_Kd -bm 1.0 textures/brick.png
+            map_Bump textures/brick_normal.png
+            newmtl plain
+        ";
+        let materials = parse_mtl(source).unwrap();
+        assert_eq!(materials.len(), 2);
+        assert_eq!(materials[0].diffuse_color, [0.5, 0.25, 1.0]);
+        assert_eq!(
+            materials[0].diffuse_map,
+            Some(PathBuf::from("textures/brick.png"))
+        );
+        assert_eq!(
+            materials[0].normal_map,
+            Some(PathBuf::from("textures/brick_normal.png"))
+        );
+        assert_eq!(materials[1].diffuse_map, None);
+        assert!(!materials[0].is_pbr());
+
+        assert_eq!(parse_mtl("Kd 1 1 1\n").unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn mtl_pbr_extension() {
+        let source = "
+            newmtl gold
+            Kd 1.0 0.8 0.3
+            Ke 0.1 0.0 0.0
+            Pr 0.3
+            Pm 1
+        ";
+        let materials = parse_mtl(source).unwrap();
+        assert!(materials[0].is_pbr());
+        assert_eq!(materials[0].roughness, Some(0.3));
+        assert_eq!(materials[0].metallic, Some(1.0));
+        assert_eq!(materials[0].emissive_color, [0.1, 0.0, 0.0]);
+
+        assert_eq!(parse_mtl("newmtl a\nPr\n").unwrap_err().line, 2);
+    }
+}
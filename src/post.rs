@@ -0,0 +1,718 @@
+// Post-processing: the scene is drawn into an offscreen HDR texture, and a chain of full-screen
+// passes turns that into the frame.
+//
+// Anti-aliasing happens here too. Multisampling draws the offscreen texture with several
+// samples per pixel and resolves them; FXAA and SMAA are passes added at the end of the chain.
+// The frame itself is never multisampled, so the mode can change while running.
+//
+// Each pass reads the previous one's output from a float texture and writes the next one; a
+// copy then finishes the frame. The lit shaders write linear colors, and colors stay linear
+// until a `gamma` pass encodes them as sRGB. The frame is an sRGB framebuffer, so the copy lets
+// it encode colors the chain left linear, and writes them unchanged once `gamma` encoded them.
+// Passes that work on encoded colors, FXAA's edge detection and the color grading table, check
+// whether that happened yet.
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::path::Path;
+use std::path::PathBuf;
+
+use glium::backend::Facade;
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::texture::DepthTexture2d;
+use glium::texture::DepthTexture2dMultisample;
+use glium::texture::MipmapsOption;
+use glium::texture::Texture2d;
+use glium::texture::Texture2dMultisample;
+use glium::texture::Texture3d;
+use glium::texture::TextureFormat;
+use glium::texture::UncompressedFloatFormat;
+use glium::uniforms::MagnifySamplerFilter;
+use glium::uniforms::MinifySamplerFilter;
+use glium::uniforms::Sampler;
+use glium::uniforms::SamplerWrapFunction;
+use glium::uniforms::Uniforms;
+use glium::BlitTarget;
+use glium::CapabilitiesSource;
+use glium::DrawParameters;
+use glium::Surface;
+use image::RgbaImage;
+
+use crate::assets;
+use crate::assets::AssetManager;
+use crate::mesh::Mesh;
+use crate::mesh::MeshData;
+use crate::shader::ShaderProgram;
+
+/// The color grading table used unless another is given, a warm grade with a little more
+/// contrast and saturation.
+pub const COLOR_GRADE_LUT: &str = "luts/warm.png";
+
+/// Scales the scene's colors before tone mapping them.
+const EXPOSURE: f32 = 1.0;
+/// Colors glow once their brightest channel passes this.
+const BLOOM_THRESHOLD: f32 = 0.8;
+const BLOOM_INTENSITY: f32 = 0.6;
+/// How much darker the corners get.
+const VIGNETTE_STRENGTH: f32 = 0.35;
+
+/// A chain using every pass: bloom on the HDR colors, tone mapping and encoding them, then
+/// grading and smoothing the encoded image, with the vignette on top.
+pub const DEFAULT_CHAIN: [PostPass; 6] = [
+    PostPass::Bloom,
+    PostPass::ToneMap,
+    PostPass::Gamma,
+    PostPass::ColorGrade,
+    PostPass::Fxaa,
+    PostPass::Vignette,
+];
+
+const VERTEX_SHADER: &str = "shaders/post/quad.vert";
+const TONE_MAP_SHADER: &str = "shaders/post/tone_map.frag";
+const GAMMA_SHADER: &str = "shaders/post/gamma.frag";
+const FXAA_SHADER: &str = "shaders/post/fxaa.frag";
+const BLOOM_EXTRACT_SHADER: &str = "shaders/post/bloom_extract.frag";
+const BLUR_SHADER: &str = "shaders/post/blur.frag";
+const BLOOM_SHADER: &str = "shaders/post/bloom.frag";
+const VIGNETTE_SHADER: &str = "shaders/post/vignette.frag";
+const COLOR_GRADE_SHADER: &str = "shaders/post/color_grade.frag";
+const SMAA_EDGES_SHADER: &str = "shaders/post/smaa_edges.frag";
+const SMAA_WEIGHTS_SHADER: &str = "shaders/post/smaa_weights.frag";
+const SMAA_BLEND_SHADER: &str = "shaders/post/smaa_blend.frag";
+const COPY_SHADER: &str = "shaders/post/copy.frag";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostPass {
+    /// Compresses HDR colors into the displayable range.
+    ToneMap,
+    /// Encodes linear colors as sRGB.
+    Gamma,
+    /// Smooths jagged edges by blurring along them.
+    Fxaa,
+    /// Smooths jagged edges by mixing the pixels on either side by how much the silhouette
+    /// behind them covers.
+    Smaa,
+    /// Makes bright areas glow.
+    Bloom,
+    /// Darkens the corners.
+    Vignette,
+    /// Remaps colors through a lookup table.
+    ColorGrade,
+}
+
+impl PostPass {
+    pub const ALL: [PostPass; 7] = [
+        PostPass::ToneMap,
+        PostPass::Gamma,
+        PostPass::Fxaa,
+        PostPass::Smaa,
+        PostPass::Bloom,
+        PostPass::Vignette,
+        PostPass::ColorGrade,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PostPass::ToneMap => "tone_map",
+            PostPass::Gamma => "gamma",
+            PostPass::Fxaa => "fxaa",
+            PostPass::Smaa => "smaa",
+            PostPass::Bloom => "bloom",
+            PostPass::Vignette => "vignette",
+            PostPass::ColorGrade => "color_grade",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<PostPass> {
+        PostPass::ALL.into_iter().find(|pass| pass.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Antialiasing {
+    None,
+    /// Draws the scene with this many samples per pixel.
+    Msaa(u32),
+    Fxaa,
+    Smaa,
+}
+
+impl Antialiasing {
+    /// The mode after this one when switching through them, multisampling with `samples`.
+    pub fn next(self, samples: u32) -> Antialiasing {
+        match self {
+            Antialiasing::None => Antialiasing::Msaa(samples),
+            Antialiasing::Msaa(_) => Antialiasing::Fxaa,
+            Antialiasing::Fxaa => Antialiasing::Smaa,
+            Antialiasing::Smaa => Antialiasing::None,
+        }
+    }
+
+    /// The pass this mode adds to the end of the chain, if any.
+    fn pass(self) -> Option<PostPass> {
+        match self {
+            Antialiasing::Fxaa => Some(PostPass::Fxaa),
+            Antialiasing::Smaa => Some(PostPass::Smaa),
+            Antialiasing::None | Antialiasing::Msaa(_) => None,
+        }
+    }
+
+    fn samples(self) -> u32 {
+        match self {
+            Antialiasing::Msaa(samples) => samples,
+            _ => 0,
+        }
+    }
+}
+
+/// The sample count nearest to `samples` that the scene's HDR texture can be multisampled
+/// with, preferring fewer samples to more. Drivers list the counts they support per format;
+/// software rasterizers often only do 4.
+pub fn supported_samples<F: Facade>(facade: &F, samples: u32) -> u32 {
+    let format = TextureFormat::UncompressedFloat(UncompressedFloatFormat::F16F16F16F16);
+    let capabilities = facade.get_context().get_capabilities();
+    let counts: Vec<u32> = match capabilities
+        .internal_formats_textures
+        .get(&format)
+        .and_then(|infos| infos.multisamples.as_ref())
+    {
+        Some(counts) if !counts.is_empty() => counts.iter().map(|&count| count as u32).collect(),
+        // Not known, so the requested count is tried
+        _ => return samples,
+    };
+    let fewer = counts
+        .iter()
+        .copied()
+        .filter(|&count| count <= samples)
+        .max();
+    fewer.unwrap_or_else(|| counts.iter().copied().min().unwrap())
+}
+
+/// Reads a chain written as pass names separated by commas, such as `tone_map,bloom,gamma`, or
+/// `none` for drawing straight into the frame. Passes may repeat.
+pub fn parse_chain(list: &str) -> Option<Vec<PostPass>> {
+    if list == "none" {
+        return Some(Vec::new());
+    }
+    list.split(',')
+        .map(|name| PostPass::from_name(name.trim()))
+        .collect()
+}
+
+/// The names of the passes in `chain`, the way `parse_chain` reads them.
+pub fn chain_names(chain: &[PostPass]) -> String {
+    if chain.is_empty() {
+        return "none".to_string();
+    }
+    let names: Vec<&str> = chain.iter().map(|pass| pass.name()).collect();
+    names.join(",")
+}
+
+/// A color grading table's entries, indexed by blue, green and red in that order.
+type LutSlices = Vec<Vec<Vec<(u8, u8, u8, u8)>>>;
+
+/// Turns a color grading table stored as an image into the slices of a 3D texture. The image
+/// holds N slices of N by N entries side by side, blue picking the slice, red the column within
+/// it and green the row, as in the strips most grading tools export.
+fn lut_slices(image: &RgbaImage) -> Result<LutSlices, String> {
+    let size = image.height();
+    if size < 2 || image.width() != size * size {
+        return Err(format!(
+            "a color grading table of N entries per side is N*N by N pixels, not {}x{}",
+            image.width(),
+            image.height()
+        ));
+    }
+    let slices = (0..size)
+        .map(|blue| {
+            (0..size)
+                .map(|green| {
+                    (0..size)
+                        .map(|red| {
+                            let [r, g, b, a] = image.get_pixel(blue * size + red, green).0;
+                            (r, g, b, a)
+                        })
+                        .collect()
+                })
+                .collect()
+        })
+        .collect();
+    Ok(slices)
+}
+
+/// Where the scene is drawn before the passes read it.
+enum SceneTarget {
+    Single(DepthTexture2d),
+    /// Resolved into the scene texture after drawing.
+    Multisampled(Texture2dMultisample, DepthTexture2dMultisample),
+}
+
+/// The textures the chain draws into, all the size of the frame except for the bloom ones.
+struct Targets {
+    dimensions: (u32, u32),
+    samples: u32,
+    scene_target: SceneTarget,
+    scene: Texture2d,
+    /// Passes alternate between these, each reading the one the pass before wrote.
+    ping_pong: [Texture2d; 2],
+    /// Half size, for blurring the bright parts of the image in two directions.
+    bloom: [Texture2d; 2],
+    /// The edges SMAA finds, and the weights it mixes the pixels on either side of them with.
+    smaa_edges: Texture2d,
+    smaa_weights: Texture2d,
+}
+
+impl Targets {
+    /// Fails when the driver can't make a texture. Sizes are at least one pixel, so a minimized
+    /// window still gets targets.
+    fn new<F: Facade>(
+        facade: &F,
+        dimensions: (u32, u32),
+        samples: u32,
+    ) -> Result<Self, Box<dyn Error>> {
+        let (width, height) = (dimensions.0.max(1), dimensions.1.max(1));
+        let texture = |format, width: u32, height: u32| {
+            Texture2d::empty_with_format(
+                facade,
+                format,
+                MipmapsOption::NoMipmap,
+                width.max(1),
+                height.max(1),
+            )
+        };
+        let hdr_texture =
+            |width, height| texture(UncompressedFloatFormat::F16F16F16F16, width, height);
+        let ldr_texture = || texture(UncompressedFloatFormat::U8U8U8U8, width, height);
+        let scene_target = if samples > 0 {
+            SceneTarget::Multisampled(
+                Texture2dMultisample::empty_with_format(
+                    facade,
+                    UncompressedFloatFormat::F16F16F16F16,
+                    MipmapsOption::NoMipmap,
+                    width,
+                    height,
+                    samples,
+                )?,
+                DepthTexture2dMultisample::empty(facade, width, height, samples)?,
+            )
+        } else {
+            SceneTarget::Single(DepthTexture2d::empty(facade, width, height)?)
+        };
+        Ok(Targets {
+            dimensions,
+            samples,
+            scene_target,
+            scene: hdr_texture(width, height)?,
+            ping_pong: [hdr_texture(width, height)?, hdr_texture(width, height)?],
+            bloom: [
+                hdr_texture(width / 2, height / 2)?,
+                hdr_texture(width / 2, height / 2)?,
+            ],
+            smaa_edges: ldr_texture()?,
+            smaa_weights: ldr_texture()?,
+        })
+    }
+
+    /// Lets `draw` draw the scene, and leaves the result in `scene`.
+    fn draw_scene<F: Facade, R>(
+        &self,
+        facade: &F,
+        draw: impl FnOnce(&mut SimpleFrameBuffer) -> R,
+    ) -> Result<R, Box<dyn Error>> {
+        match &self.scene_target {
+            SceneTarget::Single(depth) => {
+                let mut framebuffer =
+                    SimpleFrameBuffer::with_depth_buffer(facade, &self.scene, depth)?;
+                Ok(draw(&mut framebuffer))
+            }
+            SceneTarget::Multisampled(color, depth) => {
+                let mut framebuffer = SimpleFrameBuffer::with_depth_buffer(facade, color, depth)?;
+                let result = draw(&mut framebuffer);
+                let resolved = SimpleFrameBuffer::new(facade, &self.scene)?;
+                let whole = BlitTarget {
+                    left: 0,
+                    bottom: 0,
+                    width: self.dimensions.0 as i32,
+                    height: self.dimensions.1 as i32,
+                };
+                framebuffer.blit_whole_color_to(&resolved, &whole, MagnifySamplerFilter::Nearest);
+                Ok(result)
+            }
+        }
+    }
+}
+
+pub struct PostProcess {
+    chain: Vec<PostPass>,
+    antialiasing: Antialiasing,
+    tone_map: ShaderProgram,
+    gamma: ShaderProgram,
+    fxaa: ShaderProgram,
+    bloom_extract: ShaderProgram,
+    blur: ShaderProgram,
+    bloom: ShaderProgram,
+    vignette: ShaderProgram,
+    color_grade: ShaderProgram,
+    smaa_edges: ShaderProgram,
+    smaa_weights: ShaderProgram,
+    smaa_blend: ShaderProgram,
+    copy: ShaderProgram,
+    /// The same copy for colors a `gamma` pass encoded, which the frame mustn't encode again.
+    copy_encoded: ShaderProgram,
+    /// The passes draw over the whole target with it.
+    quad: Mesh,
+    lut: Texture3d,
+    /// The file `lut` was loaded from.
+    lut_path: PathBuf,
+    /// Made when first drawing, and again when the frame's size changes.
+    targets: RefCell<Option<Targets>>,
+}
+
+impl PostProcess {
+    /// Loads the passes' programs and the color grading table at `lut`. The chain starts out
+    /// empty, without anti-aliasing.
+    pub fn new<F: Facade>(
+        facade: &F,
+        assets: &mut AssetManager,
+        lut: &str,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut load =
+            |fragment: &str| ShaderProgram::load(facade, assets, VERTEX_SHADER, fragment);
+        let tone_map = load(TONE_MAP_SHADER)?;
+        let gamma = load(GAMMA_SHADER)?;
+        let fxaa = load(FXAA_SHADER)?;
+        let bloom_extract = load(BLOOM_EXTRACT_SHADER)?;
+        let blur = load(BLUR_SHADER)?;
+        let bloom = load(BLOOM_SHADER)?;
+        let vignette = load(VIGNETTE_SHADER)?;
+        let color_grade = load(COLOR_GRADE_SHADER)?;
+        let smaa_edges = load(SMAA_EDGES_SHADER)?;
+        let smaa_weights = load(SMAA_WEIGHTS_SHADER)?;
+        let smaa_blend = load(SMAA_BLEND_SHADER)?;
+        let copy = load(COPY_SHADER)?;
+        let copy_encoded = ShaderProgram::load_encoded(facade, assets, VERTEX_SHADER, COPY_SHADER)?;
+        let lut_path = assets.resolve(lut);
+        assets.watch(&lut_path);
+        Ok(PostProcess {
+            chain: Vec::new(),
+            antialiasing: Antialiasing::None,
+            tone_map,
+            gamma,
+            fxaa,
+            bloom_extract,
+            blur,
+            bloom,
+            vignette,
+            color_grade,
+            smaa_edges,
+            smaa_weights,
+            smaa_blend,
+            copy,
+            copy_encoded,
+            quad: Mesh::new(facade, &MeshData::quad()),
+            lut: load_lut(facade, &lut_path)?,
+            lut_path,
+            targets: RefCell::new(None),
+        })
+    }
+
+    pub fn chain(&self) -> &[PostPass] {
+        &self.chain
+    }
+
+    /// Sets the passes to apply, in order.
+    pub fn set_chain(&mut self, chain: Vec<PostPass>) {
+        self.chain = chain;
+    }
+
+    pub fn antialiasing(&self) -> Antialiasing {
+        self.antialiasing
+    }
+
+    pub fn set_antialiasing(&mut self, antialiasing: Antialiasing) {
+        self.antialiasing = antialiasing;
+    }
+
+    /// Whether frames have to be drawn offscreen first. Otherwise the scene can be drawn
+    /// straight into the frame.
+    pub fn is_active(&self) -> bool {
+        !self.chain.is_empty() || self.antialiasing != Antialiasing::None
+    }
+
+    /// The chain, followed by the anti-aliasing pass unless the chain already has it.
+    fn passes(&self) -> Vec<PostPass> {
+        let mut passes = self.chain.clone();
+        if let Some(pass) = self.antialiasing.pass() {
+            if !passes.contains(&pass) {
+                passes.push(pass);
+            }
+        }
+        passes
+    }
+
+    /// Replaces the color grading table by the one in the image at `path`.
+    pub fn set_lut<F: Facade>(
+        &mut self,
+        facade: &F,
+        assets: &mut AssetManager,
+        path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = assets.resolve(path);
+        self.lut = load_lut(facade, &path)?;
+        assets.watch(&path);
+        self.lut_path = path;
+        Ok(())
+    }
+
+    /// Reloads the color grading table from its file.
+    pub fn reload_lut<F: Facade>(&mut self, facade: &F) -> Result<(), Box<dyn Error>> {
+        self.lut = load_lut(facade, &self.lut_path)?;
+        Ok(())
+    }
+
+    pub fn lut_path(&self) -> &Path {
+        &self.lut_path
+    }
+
+    pub fn programs(&self) -> [&ShaderProgram; 13] {
+        [
+            &self.tone_map,
+            &self.gamma,
+            &self.fxaa,
+            &self.bloom_extract,
+            &self.blur,
+            &self.bloom,
+            &self.vignette,
+            &self.color_grade,
+            &self.smaa_edges,
+            &self.smaa_weights,
+            &self.smaa_blend,
+            &self.copy,
+            &self.copy_encoded,
+        ]
+    }
+
+    /// The passes' programs, for reloading them when their files change.
+    pub fn programs_mut(&mut self) -> [&mut ShaderProgram; 13] {
+        [
+            &mut self.tone_map,
+            &mut self.gamma,
+            &mut self.fxaa,
+            &mut self.bloom_extract,
+            &mut self.blur,
+            &mut self.bloom,
+            &mut self.vignette,
+            &mut self.color_grade,
+            &mut self.smaa_edges,
+            &mut self.smaa_weights,
+            &mut self.smaa_blend,
+            &mut self.copy,
+            &mut self.copy_encoded,
+        ]
+    }
+
+    /// Lets `draw_scene` draw into the HDR texture, a framebuffer the size of `target`, then
+    /// runs the chain from there into `target`. Returns what `draw_scene` returned, or why the
+    /// offscreen targets couldn't be made.
+    pub fn apply<F: Facade, S: Surface, R>(
+        &self,
+        facade: &F,
+        target: &mut S,
+        draw_scene: impl FnOnce(&mut SimpleFrameBuffer) -> R,
+    ) -> Result<R, Box<dyn Error>> {
+        let dimensions = target.get_dimensions();
+        let mut targets = self.targets.borrow_mut();
+        let samples = self.antialiasing.samples();
+        let fits = |t: &Targets| t.dimensions == dimensions && t.samples == samples;
+        if !targets.as_ref().is_some_and(fits) {
+            *targets = Some(Targets::new(facade, dimensions, samples)?);
+        }
+        let targets = targets.as_ref().unwrap();
+        let result = targets.draw_scene(facade, draw_scene)?;
+
+        let mut source = &targets.scene;
+        let mut srgb = false;
+        for (i, &pass) in self.passes().iter().enumerate() {
+            let output = &targets.ping_pong[i % 2];
+            let mut framebuffer = SimpleFrameBuffer::new(facade, output)?;
+            self.run(facade, pass, source, &mut framebuffer, targets, srgb)?;
+            source = output;
+            srgb |= pass == PostPass::Gamma;
+        }
+        // Encoded once, by the `gamma` pass or else by the frame
+        let copy = if srgb { &self.copy_encoded } else { &self.copy };
+        self.draw_fullscreen(target, copy, &uniform! { source: filtered(source) });
+        Ok(result)
+    }
+
+    /// Applies `pass` to `source`, writing the result to `target`. `srgb` tells whether the
+    /// source is sRGB encoded.
+    fn run<F: Facade, S: Surface>(
+        &self,
+        facade: &F,
+        pass: PostPass,
+        source: &Texture2d,
+        target: &mut S,
+        targets: &Targets,
+        srgb: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let texel_size = [1.0 / source.width() as f32, 1.0 / source.height() as f32];
+        let source = filtered(source);
+        match pass {
+            PostPass::ToneMap => self.draw_fullscreen(
+                target,
+                &self.tone_map,
+                &uniform! { source: source, exposure: EXPOSURE },
+            ),
+            PostPass::Gamma => {
+                self.draw_fullscreen(target, &self.gamma, &uniform! { source: source })
+            }
+            PostPass::Fxaa => self.draw_fullscreen(
+                target,
+                &self.fxaa,
+                &uniform! { source: source, texel_size: texel_size, srgb_input: srgb },
+            ),
+            PostPass::Smaa => {
+                self.draw_fullscreen(
+                    &mut SimpleFrameBuffer::new(facade, &targets.smaa_edges)?,
+                    &self.smaa_edges,
+                    &uniform! { source: source, srgb_input: srgb },
+                );
+                self.draw_fullscreen(
+                    &mut SimpleFrameBuffer::new(facade, &targets.smaa_weights)?,
+                    &self.smaa_weights,
+                    &uniform! { edges: targets.smaa_edges.sampled() },
+                );
+                self.draw_fullscreen(
+                    target,
+                    &self.smaa_blend,
+                    &uniform! { source: source, weights: targets.smaa_weights.sampled() },
+                );
+            }
+            PostPass::Bloom => {
+                let [bright, blurred] = &targets.bloom;
+                let step = [1.0 / bright.width() as f32, 1.0 / bright.height() as f32];
+                self.draw_fullscreen(
+                    &mut SimpleFrameBuffer::new(facade, bright)?,
+                    &self.bloom_extract,
+                    &uniform! { source: source, threshold: BLOOM_THRESHOLD },
+                );
+                self.draw_fullscreen(
+                    &mut SimpleFrameBuffer::new(facade, blurred)?,
+                    &self.blur,
+                    &uniform! { source: filtered(bright), direction: [step[0], 0.0] },
+                );
+                self.draw_fullscreen(
+                    &mut SimpleFrameBuffer::new(facade, bright)?,
+                    &self.blur,
+                    &uniform! { source: filtered(blurred), direction: [0.0, step[1]] },
+                );
+                self.draw_fullscreen(
+                    target,
+                    &self.bloom,
+                    &uniform! { source: source, bloom: filtered(bright),
+                    intensity: BLOOM_INTENSITY },
+                );
+            }
+            PostPass::Vignette => self.draw_fullscreen(
+                target,
+                &self.vignette,
+                &uniform! { source: source, strength: VIGNETTE_STRENGTH },
+            ),
+            PostPass::ColorGrade => {
+                let lut = self
+                    .lut
+                    .sampled()
+                    .minify_filter(MinifySamplerFilter::Linear)
+                    .magnify_filter(MagnifySamplerFilter::Linear)
+                    .wrap_function(SamplerWrapFunction::Clamp);
+                self.draw_fullscreen(
+                    target,
+                    &self.color_grade,
+                    &uniform! { source: source, lut: lut, lut_size: self.lut.width() as f32,
+                    srgb_input: srgb },
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn draw_fullscreen<S: Surface, U: Uniforms>(
+        &self,
+        target: &mut S,
+        program: &ShaderProgram,
+        uniforms: &U,
+    ) {
+        let params = DrawParameters::default();
+        self.quad.draw(target, program.program(), uniforms, &params);
+    }
+}
+
+/// Samples `texture` with linear filtering and without wrapping around at the edges.
+fn filtered(texture: &Texture2d) -> Sampler<'_, Texture2d> {
+    texture
+        .sampled()
+        .minify_filter(MinifySamplerFilter::Linear)
+        .magnify_filter(MagnifySamplerFilter::Linear)
+        .wrap_function(SamplerWrapFunction::Clamp)
+}
+
+fn load_lut<F: Facade>(facade: &F, path: &Path) -> Result<Texture3d, Box<dyn Error>> {
+    let image = assets::load_image(path)?.to_rgba8();
+    let slices = lut_slices(&image).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(Texture3d::new(facade, slices)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use image::Rgba;
+
+    #[test]
+    fn a_minimized_window_still_gets_targets() {
+        let context = crate::headless::create_context((1, 1)).unwrap();
+        for samples in [0, 4] {
+            let targets = Targets::new(&context, (0, 0), samples).unwrap();
+            assert_eq!(targets.dimensions, (0, 0));
+            assert_eq!(targets.scene.dimensions(), (1, 1));
+            assert_eq!(targets.bloom[0].dimensions(), (1, 1));
+        }
+    }
+
+    #[test]
+    fn chains_are_read_from_pass_names() {
+        assert_eq!(
+            parse_chain("tone_map,bloom, gamma"),
+            Some(vec![PostPass::ToneMap, PostPass::Bloom, PostPass::Gamma])
+        );
+        assert_eq!(parse_chain("none"), Some(Vec::new()));
+        assert_eq!(parse_chain("tone_map,sharpen"), None);
+        assert_eq!(parse_chain(""), None);
+        for pass in PostPass::ALL {
+            assert_eq!(
+                parse_chain(&chain_names(&[pass, pass])),
+                Some(vec![pass, pass])
+            );
+        }
+        assert_eq!(chain_names(&[]), "none");
+    }
+
+    #[test]
+    fn lut_images_are_strips_of_slices() {
+        // Two slices of 2x2 entries, each pixel holding its own coordinates
+        let mut image = RgbaImage::new(4, 2);
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            *pixel = Rgba([x as u8 % 2, y as u8, x as u8 / 2, 255]);
+        }
+        let slices = lut_slices(&image).unwrap();
+        for (b, slice) in slices.iter().enumerate() {
+            for (g, row) in slice.iter().enumerate() {
+                for (r, &entry) in row.iter().enumerate() {
+                    assert_eq!(entry, (r as u8, g as u8, b as u8, 255));
+                }
+            }
+        }
+        assert!(lut_slices(&RgbaImage::new(8, 2)).is_err());
+    }
+}
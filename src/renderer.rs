@@ -0,0 +1,1368 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::PI;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use glium::backend::Context;
+use glium::backend::Facade;
+use glium::texture::SrgbTexture2d;
+use glium::texture::Texture2d;
+use glium::uniforms::Uniforms;
+use glium::Depth;
+use glium::DepthTest;
+use glium::DrawParameters;
+use glium::Rect;
+use glium::Surface;
+
+use crate::assets::AssetManager;
+use crate::assets::Mipmaps;
+use crate::assets::ModelFormat;
+use crate::bounds;
+use crate::bounds::Frustum;
+use crate::camera::Camera;
+use crate::environment::Environment;
+use crate::gltf_scene;
+use crate::gltf_scene::SceneCamera;
+use crate::gltf_scene::TextureRef;
+use crate::instancing::Instance;
+use crate::instancing::InstancedMesh;
+use crate::light;
+use crate::light::Light;
+use crate::light::LightUniforms;
+use crate::material::Material;
+use crate::material::MaterialProgram;
+use crate::material::MaterialTexture;
+use crate::material::ProgramHandle;
+use crate::material::ShadingModel;
+use crate::material::StandardInputs;
+use crate::math::vec3;
+use crate::math::Mat4;
+use crate::math::Quat;
+use crate::math::Vec3;
+use crate::mesh::Mesh;
+use crate::mesh::MeshData;
+use crate::obj;
+use crate::post;
+use crate::post::Antialiasing;
+use crate::post::PostPass;
+use crate::post::PostProcess;
+use crate::scene::NodeId;
+use crate::scene::Scene;
+use crate::scene::Transform;
+use crate::scene_file;
+use crate::scene_file::Background;
+use crate::scene_file::LightDescription;
+use crate::scene_file::MaterialDescription;
+use crate::scene_file::NodeDescription;
+use crate::scene_file::SceneDescription;
+use crate::scene_file::TextureDescription;
+use crate::shader::ShaderProgram;
+use crate::shadow::ShadowMaps;
+use crate::shadow::ShadowSettings;
+use crate::texture;
+
+/// The transform of a node at a time in seconds.
+pub type Animation = fn(f32) -> Transform;
+
+struct Model {
+    name: String,
+    scene: Scene,
+    /// Holds the rest of the model, and turns it on a turntable.
+    root: NodeId,
+    /// Nodes whose transform is a function of the time since start.
+    animations: Vec<(NodeId, Animation)>,
+    /// Copies of meshes placed relative to a node, drawn in one call each.
+    instanced: Vec<(NodeId, InstancedMesh)>,
+    /// Overrides the default view when the model brings its own camera.
+    camera: Option<(Mat4, SceneCamera)>,
+    /// Files the model was loaded from, the model or scene file first. Empty for built-in models.
+    files: Vec<PathBuf>,
+    /// The description a scene was built from, and the file it was read from, to build it
+    /// again when one of `files` changes. `None` for models that are not scenes.
+    source: Option<(SceneDescription, Option<PathBuf>)>,
+}
+
+impl Model {
+    /// A model whose root node draws `meshes`.
+    fn new(name: &str, meshes: Vec<Mesh>) -> Self {
+        let mut scene = Scene::new();
+        let root = scene.add(None, name, Transform::IDENTITY);
+        scene.node_mut(root).meshes = meshes.into_iter().map(Rc::new).collect();
+        Model {
+            name: name.to_string(),
+            scene,
+            root,
+            animations: vec![(root, turntable)],
+            instanced: Vec::new(),
+            camera: None,
+            files: Vec::new(),
+            source: None,
+        }
+    }
+}
+
+/// The animations nodes of scene files can be given, by name.
+pub const ANIMATIONS: [(&str, Animation); 2] = [("turntable", turntable), ("carousel", carousel)];
+
+// The demo's original animation, swinging the model back and forth around the Y axis
+fn turntable(t: f32) -> Transform {
+    Transform::from_rotation(Quat::from_axis_angle(Vec3::Y, -(t * 2.0).sin()))
+}
+
+fn carousel(t: f32) -> Transform {
+    Transform {
+        translation: vec3(0.0, -0.45, 0.0),
+        ..Transform::from_rotation(Quat::from_axis_angle(Vec3::Y, t))
+    }
+}
+
+// Textures used by meshes that don't bring their own unless others are set, looked up in the
+// asset directory
+pub const DIFFUSE_TEXTURE: &str = "diffuse.jpg";
+pub const NORMAL_MAP: &str = "normal.png";
+const VERTEX_SHADER: &str = "shaders/phong.vert";
+const INSTANCED_VERTEX_SHADER: &str = "shaders/instanced.vert";
+const FRAGMENT_SHADER: &str = "shaders/phong.frag";
+const PBR_FRAGMENT_SHADER: &str = "shaders/pbr.frag";
+/// Equirectangular panorama lighting the scene and drawn behind it, unless a scene brings its
+/// own.
+pub const ENVIRONMENT: &str = "environment.hdr";
+/// The crowd model is a square of this many teapots on each side.
+const CROWD_SIDE: usize = 32;
+
+/// Where the tangent frame used for normal mapping comes from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TangentMode {
+    /// Derived per pixel from screen-space derivatives of position and texture coordinates.
+    ScreenSpace,
+    /// Precomputed per vertex on the CPU, which stays stable on curved meshes.
+    Vertex,
+}
+
+/// What happened to the meshes of the current model in a frame.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Meshes drawn, counting each instanced copy.
+    pub drawn: usize,
+    /// Meshes skipped because they were outside the view frustum.
+    pub culled: usize,
+    pub draw_calls: usize,
+}
+
+/// Everything needed to draw the demo scene, independent of where the frame ends up.
+pub struct Renderer {
+    /// Kept for creating framebuffers while drawing.
+    context: Rc<Context>,
+    models: Vec<Model>,
+    current_model: usize,
+    /// Used by meshes without a material of their own, built from `default_inputs` with the
+    /// program for `default_shading`.
+    default_material: Material,
+    default_inputs: StandardInputs,
+    default_shading: ShadingModel,
+    /// The asset paths of the default material's textures, for reloading them.
+    default_textures: (String, String),
+    phong_program: ProgramHandle,
+    pbr_program: ProgramHandle,
+    /// Whether instanced meshes are drawn in one call, rather than one call per copy.
+    instancing: bool,
+    tangent_mode: TangentMode,
+    lights: Vec<Light>,
+    shadows: ShadowMaps,
+    environment: Environment,
+    post: PostProcess,
+    /// The asset path `environment` was loaded from.
+    environment_path: String,
+    /// Drawn behind the scene instead of the environment when set.
+    clear_color: Option<[f32; 3]>,
+    /// Why the reloaded programs don't fit the uniforms they are drawn with.
+    uniform_error: Option<String>,
+    assets: AssetManager,
+}
+
+impl Renderer {
+    pub fn new<F: Facade>(facade: &F, mut assets: AssetManager) -> Result<Self, Box<dyn Error>> {
+        let models = vec![
+            Model::new("quad", vec![Mesh::new(facade, &MeshData::quad())]),
+            Model::new("teapot", vec![Mesh::new(facade, &MeshData::teapot())]),
+        ];
+
+        let phong_program = load_material_program(facade, &mut assets, FRAGMENT_SHADER)?;
+        let pbr_program = load_material_program(facade, &mut assets, PBR_FRAGMENT_SHADER)?;
+
+        let diffuse_texture = assets.srgb_texture(facade, DIFFUSE_TEXTURE)?;
+        let normal_map = assets.linear_texture(facade, NORMAL_MAP)?;
+        let default_inputs = StandardInputs::new(facade, diffuse_texture, normal_map);
+        let default_material =
+            standard_material(&phong_program, ShadingModel::BlinnPhong, &default_inputs)?;
+        let shadows = ShadowMaps::new(facade, &mut assets, ShadowSettings::default())?;
+        let environment = Environment::load(facade, &mut assets, ENVIRONMENT)?;
+        let post = PostProcess::new(facade, &mut assets, post::COLOR_GRADE_LUT)?;
+
+        let mut renderer = Renderer {
+            context: facade.get_context().clone(),
+            models,
+            current_model: 0,
+            default_material,
+            default_inputs,
+            default_shading: ShadingModel::BlinnPhong,
+            default_textures: (DIFFUSE_TEXTURE.to_string(), NORMAL_MAP.to_string()),
+            phong_program,
+            pbr_program,
+            instancing: true,
+            tangent_mode: TangentMode::ScreenSpace,
+            lights: light::default_lights(),
+            shadows,
+            environment,
+            post,
+            environment_path: ENVIRONMENT.to_string(),
+            clear_color: None,
+            uniform_error: None,
+            assets,
+        };
+        renderer.check_programs()?;
+        let teapots = renderer.teapots_model(facade)?;
+        renderer.models.push(teapots);
+        renderer.models.push(crowd_model(facade));
+        Ok(renderer)
+    }
+
+    /// A built-in model composed of several teapots and quads, some of them circling the
+    /// others.
+    fn teapots_model<F: Facade>(&self, facade: &F) -> Result<Model, Box<dyn Error>> {
+        let teapot = Rc::new(Mesh::new(facade, &MeshData::teapot()));
+        let quad = Rc::new(Mesh::new(facade, &MeshData::quad()));
+        let flat_normal_map = Rc::new(texture::flat_normal_map(facade));
+        let material = |color: [f32; 3], metallic: f32, roughness: f32| {
+            let base_color = Rc::new(texture::solid_srgb_texture(facade, color));
+            let mut inputs = StandardInputs::new(facade, base_color, flat_normal_map.clone());
+            inputs.metallic_factor = metallic;
+            inputs.roughness_factor = roughness;
+            let material = self.standard_material(ShadingModel::Pbr, &inputs)?;
+            Ok::<_, Box<dyn Error>>(Some(Rc::new(material)))
+        };
+
+        let mut model = Model::new("teapots", Vec::new());
+        let scene = &mut model.scene;
+        let root = Some(model.root);
+
+        let floor = scene.add(
+            root,
+            "floor",
+            Transform {
+                translation: vec3(0.0, -0.6, 0.0),
+                rotation: Quat::from_axis_angle(Vec3::X, FRAC_PI_2),
+                scale: vec3(1.6, 1.6, 1.0),
+            },
+        );
+        scene.node_mut(floor).meshes.push(quad);
+        scene.node_mut(floor).material = material([0.5, 0.5, 0.5], 0.0, 0.8)?;
+
+        // Drawn with the default material, like the built-in teapot
+        let center = scene.add(
+            root,
+            "center",
+            Transform {
+                scale: Vec3::ONE * 0.5,
+                ..Transform::IDENTITY
+            },
+        );
+        scene.node_mut(center).meshes.push(teapot.clone());
+
+        let ring = scene.add(root, "carousel", carousel(0.0));
+        model.animations.push((ring, carousel));
+        let riders = [
+            ([1.0, 0.78, 0.34], 1.0, 0.3),
+            ([0.8, 0.1, 0.1], 0.0, 0.4),
+            ([0.2, 0.35, 0.9], 0.0, 0.9),
+        ];
+        for (i, (color, metallic, roughness)) in riders.into_iter().enumerate() {
+            let angle = i as f32 * 2.0 * PI / riders.len() as f32;
+            let arm = scene.add(
+                Some(ring),
+                "arm",
+                Transform::from_rotation(Quat::from_axis_angle(Vec3::Y, angle)),
+            );
+            let rider = scene.add(
+                Some(arm),
+                "rider",
+                Transform {
+                    scale: Vec3::ONE * 0.25,
+                    ..Transform::from_translation(vec3(1.1, 0.15, 0.0))
+                },
+            );
+            scene.node_mut(rider).meshes.push(teapot.clone());
+            scene.node_mut(rider).material = material(color, metallic, roughness)?;
+        }
+        Ok(model)
+    }
+
+    /// Poses the current model as it is `t` seconds after start.
+    pub fn update(&mut self, t: f32) {
+        let model = &mut self.models[self.current_model];
+        for &(node, animation) in &model.animations {
+            model.scene.set_transform(node, animation(t));
+        }
+    }
+
+    /// Prints the nodes of the current model as a tree, with the world space bounds of each
+    /// subtree.
+    pub fn print_scene_graph(&self) {
+        let model = &self.models[self.current_model];
+        let mut stack = vec![(model.root, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            let node = model.scene.node(id);
+            let bounds = match model.scene.bounds(id) {
+                Some((center, radius)) => {
+                    format!("bounds {:.2?} radius {:.2}", center.to_array(), radius)
+                }
+                None => "no meshes".to_string(),
+            };
+            println!(
+                "{}{} at {:.2?}, {} meshes, {}",
+                "  ".repeat(depth),
+                node.name,
+                node.transform().translation.to_array(),
+                node.meshes.len(),
+                bounds
+            );
+            stack.extend(
+                node.children()
+                    .iter()
+                    .rev()
+                    .map(|&child| (child, depth + 1)),
+            );
+        }
+    }
+
+    /// Clears `target` and draws the current model as posed by `update`, seen through `camera`
+    /// unless the model brings its own, then applies the post-processing chain and anti-aliasing.
+    pub fn draw<S: Surface>(&self, target: &mut S, camera: &Camera) -> FrameStats {
+        let stats = if self.post.is_active() {
+            match self
+                .post
+                .apply(&self.context, target, |hdr| self.draw_scene(hdr, camera))
+            {
+                Ok(stats) => stats,
+                // The frame is still drawn, just without post-processing
+                Err(e) => {
+                    println!("Could not post-process the frame ({})", e);
+                    self.draw_scene(target, camera)
+                }
+            }
+        } else {
+            self.draw_scene(target, camera)
+        };
+        if self.shader_error().is_some() {
+            draw_error_frame(target);
+        }
+        stats
+    }
+
+    /// Draws the current model into `target`. Meshes outside the view are skipped.
+    fn draw_scene<S: Surface>(&self, target: &mut S, camera: &Camera) -> FrameStats {
+        let current = &self.models[self.current_model];
+        let meshes: Vec<(Mat4, &Mesh)> = current
+            .scene
+            .meshes()
+            .map(|(node, mesh)| (node.world_transform(), mesh))
+            .collect();
+
+        // The shadow maps are drawn first, they cover all meshes of the model
+        let shadow_matrices = match current.scene.bounds(current.root) {
+            Some(bounds) => self
+                .shadows
+                .render(&self.context, &self.lights, &meshes, bounds),
+            None => Vec::new(),
+        };
+
+        let (width, height) = target.get_dimensions();
+        let aspect_ratio = width as f32 / height as f32;
+        let (view, perspective) = match &current.camera {
+            Some((view, camera)) => (*view, camera.projection_matrix(aspect_ratio)),
+            None => (camera.view_matrix(), camera.projection_matrix(aspect_ratio)),
+        };
+
+        target.clear_depth(1.0);
+        match self.clear_color {
+            Some([r, g, b]) => target.clear_color(r, g, b, 1.0),
+            None => self.environment.draw_skybox(target, view, perspective),
+        }
+
+        let params = DrawParameters {
+            depth: Depth {
+                test: DepthTest::IfLess,
+                write: true,
+                ..Default::default()
+            },
+            // DISABLED FOR NOW because:
+            // You can look through holes and not see anything inside.
+            // 3D models are usually entirely closed, but not our teapot.
+            // backface_culling: glium::draw_parameters::BackfaceCullingMode::CullClockwise,
+            ..Default::default()
+        };
+
+        // Spheres are tested first as they are cheaper, boxes catch what they miss
+        let frustum = Frustum::from_matrix(&(perspective * view));
+        let mut stats = FrameStats::default();
+        for (node, mesh) in current.scene.meshes() {
+            let world_transform = node.world_transform();
+            let (center, radius) =
+                bounds::transform_sphere(&world_transform, mesh.bounding_sphere());
+            if !frustum.intersects_sphere(center, radius)
+                || !frustum.intersects_aabb(&mesh.aabb().transform(&world_transform))
+            {
+                stats.culled += 1;
+                continue;
+            }
+            stats.drawn += 1;
+            stats.draw_calls += 1;
+
+            let material = node.material_for(mesh).unwrap_or(&self.default_material);
+            mesh.draw(
+                target,
+                material.program().borrow().single.program(),
+                &self.mesh_uniforms(
+                    material,
+                    world_transform,
+                    [1.0, 1.0, 1.0],
+                    view,
+                    perspective,
+                    &shadow_matrices,
+                ),
+                &params,
+            );
+        }
+
+        // Instanced meshes are culled as a whole
+        for (node, instanced) in &current.instanced {
+            let world_transform = current.scene.node(*node).world_transform();
+            let count = instanced.instances().len();
+            let (center, radius) =
+                bounds::transform_sphere(&world_transform, instanced.bounding_sphere());
+            if !frustum.intersects_sphere(center, radius) {
+                stats.culled += count;
+                continue;
+            }
+            stats.drawn += count;
+
+            let mesh = &instanced.mesh;
+            let material = instanced
+                .material
+                .as_deref()
+                .or(mesh.material.as_deref())
+                .unwrap_or(&self.default_material);
+            if self.instancing {
+                stats.draw_calls += 1;
+                mesh.draw_instanced(
+                    target,
+                    material.program().borrow().instanced.program(),
+                    instanced.buffer(),
+                    &self.mesh_uniforms(
+                        material,
+                        world_transform,
+                        [1.0, 1.0, 1.0],
+                        view,
+                        perspective,
+                        &shadow_matrices,
+                    ),
+                    &params,
+                );
+            } else {
+                stats.draw_calls += count;
+                for instance in instanced.instances() {
+                    mesh.draw(
+                        target,
+                        material.program().borrow().single.program(),
+                        &self.mesh_uniforms(
+                            material,
+                            world_transform * instance.model(),
+                            instance.instance_tint,
+                            view,
+                            perspective,
+                            &shadow_matrices,
+                        ),
+                        &params,
+                    );
+                }
+            }
+        }
+        stats
+    }
+
+    /// Everything the lit programs read when drawing a mesh with `material`.
+    fn mesh_uniforms<'a>(
+        &'a self,
+        material: &'a Material,
+        model: Mat4,
+        tint: [f32; 3],
+        view: Mat4,
+        perspective: Mat4,
+        shadow_matrices: &'a [Option<Mat4>],
+    ) -> impl Uniforms + 'a {
+        let shadow_settings = self.shadows.settings();
+        LightUniforms::new(
+            &self.lights,
+            shadow_matrices,
+            view,
+            material.uniforms(self.environment.uniforms(
+                view,
+                uniform! { model: model, tint: tint, view: view, perspective: perspective,
+                vertex_tangents: self.tangent_mode == TangentMode::Vertex,
+                shadow_map_0: self.shadows.sampler(0), shadow_map_1: self.shadows.sampler(1),
+                shadow_bias: [shadow_settings.constant_bias, shadow_settings.slope_bias],
+                pcf_radius: shadow_settings.pcf_radius },
+            )),
+        )
+    }
+
+    /// The program drawing materials with the given shading model.
+    fn program_for(&self, shading: ShadingModel) -> &ProgramHandle {
+        match shading {
+            ShadingModel::BlinnPhong => &self.phong_program,
+            ShadingModel::Pbr => &self.pbr_program,
+        }
+    }
+
+    /// A material drawn with the program for `shading`, from the demo's usual inputs.
+    fn standard_material(
+        &self,
+        shading: ShadingModel,
+        inputs: &StandardInputs,
+    ) -> Result<Material, Box<dyn Error>> {
+        standard_material(self.program_for(shading), shading, inputs)
+    }
+
+    /// Checks that each lit program gets a value of the right type for all of its uniforms.
+    fn check_programs(&self) -> Result<(), Box<dyn Error>> {
+        for shading in [ShadingModel::BlinnPhong, ShadingModel::Pbr] {
+            let material = self.standard_material(shading, &self.default_inputs)?;
+
+            let identity = Mat4::IDENTITY;
+            let uniforms =
+                self.mesh_uniforms(&material, identity, [1.0; 3], identity, identity, &[]);
+            let programs = material.program().borrow();
+            for program in [&programs.single, &programs.instanced] {
+                program.check_uniforms(&uniforms).map_err(|e| {
+                    format!(
+                        "{} + {}: {}",
+                        program.files()[0].display(),
+                        program.files()[1].display(),
+                        e
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Selects a model that is already loaded by name, or loads `model` from disk if it is the
+    /// path of an OBJ or glTF file, in the asset directory or relative to the working directory.
+    pub fn show_model<F: Facade>(&mut self, facade: &F, model: &str) -> Result<(), Box<dyn Error>> {
+        if self.select_model(model) {
+            return Ok(());
+        }
+
+        let path = self.assets.resolve(model);
+        if !path.exists() {
+            return Err(format!("unknown model {}", model).into());
+        }
+        let mut loaded = self.load_model(facade, &path)?;
+        loaded.name = model.to_string();
+        self.models.push(loaded);
+        self.current_model = self.models.len() - 1;
+        Ok(())
+    }
+
+    /// Builds a model from a scene description and shows it, listed as `name`. The scene's
+    /// lights and background replace the current ones, and stay when switching to other models.
+    /// The model is built again when a mesh or texture file the scene uses changes, or `file`,
+    /// the scene file it was read from if any.
+    pub fn show_scene<F: Facade>(
+        &mut self,
+        facade: &F,
+        name: &str,
+        scene: &SceneDescription,
+        file: Option<&Path>,
+    ) -> Result<(), Box<dyn Error>> {
+        let model = self.build_scene(facade, name, scene, file)?;
+        self.use_scene_lighting(facade, scene)?;
+        self.models.retain(|m| m.name != name);
+        self.models.push(model);
+        self.current_model = self.models.len() - 1;
+        Ok(())
+    }
+
+    // Builds the model for a scene, remembering the files it reads. `file` is where the scene
+    // was read from, if anywhere.
+    fn build_scene<F: Facade>(
+        &mut self,
+        facade: &F,
+        name: &str,
+        scene: &SceneDescription,
+        file: Option<&Path>,
+    ) -> Result<Model, Box<dyn Error>> {
+        // Material and animation names below are looked up without checks
+        scene.validate()?;
+        let mut model = Model::new(name, Vec::new());
+        model.animations.clear();
+        model.source = Some((scene.clone(), file.map(Path::to_path_buf)));
+        if let Some(file) = file {
+            self.assets.watch(file);
+            model.files.push(file.to_path_buf());
+        }
+
+        let mut materials = HashMap::new();
+        for (material_name, description) in &scene.materials {
+            let material = self
+                .scene_material(facade, description)
+                .map_err(|e| format!("materials.{}: {}", material_name, e))?;
+            materials.insert(material_name.as_str(), Rc::new(material));
+            for (_, texture) in description.textures() {
+                model.files.push(self.assets.resolve(&texture.path));
+                if let Mipmaps::Files(levels) = texture.mipmaps() {
+                    model
+                        .files
+                        .extend(levels.iter().map(|level| self.assets.resolve(level)));
+                }
+            }
+        }
+
+        let mut built_in_meshes = HashMap::new();
+        let mut stack: Vec<(String, NodeId, &NodeDescription)> = scene
+            .nodes
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, node)| (format!("nodes[{}]", i), model.root, node))
+            .collect();
+        while let Some((location, parent, description)) = stack.pop() {
+            let id = model
+                .scene
+                .add(Some(parent), &description.name, description.transform());
+            let material = description
+                .material
+                .as_ref()
+                .map(|material| materials[material.as_str()].clone());
+            model.scene.node_mut(id).material = material.clone();
+            if let Some(animation) = &description.animation {
+                let &(_, animation) = ANIMATIONS
+                    .iter()
+                    .find(|(name, _)| name == animation)
+                    .unwrap();
+                model.animations.push((id, animation));
+            }
+
+            match description.mesh.as_deref() {
+                Some(built_in @ ("quad" | "teapot")) => {
+                    let mesh = built_in_meshes.entry(built_in).or_insert_with(|| {
+                        let data = match built_in {
+                            "quad" => MeshData::quad(),
+                            _ => MeshData::teapot(),
+                        };
+                        Rc::new(Mesh::new(facade, &data))
+                    });
+                    model.scene.node_mut(id).meshes.push(mesh.clone());
+                }
+                Some(file) => {
+                    let path = self.assets.resolve(file);
+                    let loaded = self
+                        .load_model(facade, &path)
+                        .map_err(|e| format!("{}.mesh: {}", location, e))?;
+                    model.files.extend_from_slice(&loaded.files);
+                    let copy = model
+                        .scene
+                        .add_subtree(Some(id), &loaded.scene, loaded.root);
+                    // The material covers the meshes from the file as well
+                    let mut copied = vec![copy];
+                    while let Some(node) = copied.pop() {
+                        if material.is_some() {
+                            model.scene.node_mut(node).material = material.clone();
+                        }
+                        copied.extend_from_slice(model.scene.node(node).children());
+                    }
+                }
+                None => {}
+            }
+
+            stack.extend(
+                description
+                    .children
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .map(|(i, child)| (format!("{}.children[{}]", location, i), id, child)),
+            );
+        }
+
+        Ok(model)
+    }
+
+    /// Uploads the textures of a material from a scene description.
+    fn scene_material<F: Facade>(
+        &mut self,
+        facade: &F,
+        description: &MaterialDescription,
+    ) -> Result<Material, Box<dyn Error>> {
+        let mut inputs = StandardInputs::new(
+            facade,
+            Rc::new(texture::solid_srgb_texture(facade, [1.0, 1.0, 1.0])),
+            Rc::new(texture::flat_normal_map(facade)),
+        );
+        inputs.base_color_factor = description.base_color_factor;
+        inputs.metallic_factor = description.metallic;
+        inputs.roughness_factor = description.roughness;
+        inputs.emissive_factor = description.emissive_factor;
+        if let Some(texture) = &description.base_color {
+            inputs.base_color = self.scene_srgb_texture(facade, texture)?;
+        }
+        if let Some(texture) = &description.normal_map {
+            inputs.normal_map = self.scene_linear_texture(facade, texture)?;
+        }
+        if let Some(texture) = &description.metallic_roughness {
+            inputs.metallic_roughness = self.scene_linear_texture(facade, texture)?;
+        }
+        if let Some(texture) = &description.occlusion {
+            inputs.occlusion = self.scene_linear_texture(facade, texture)?;
+        }
+        if let Some(texture) = &description.emissive {
+            inputs.emissive = self.scene_srgb_texture(facade, texture)?;
+        }
+        self.standard_material(description.shading.into(), &inputs)
+    }
+
+    fn scene_srgb_texture<F: Facade>(
+        &mut self,
+        facade: &F,
+        description: &TextureDescription,
+    ) -> Result<MaterialTexture<SrgbTexture2d>, Box<dyn Error>> {
+        Ok(MaterialTexture {
+            texture: self.assets.srgb_texture_with_mipmaps(
+                facade,
+                &description.path,
+                &description.mipmaps(),
+            )?,
+            sampler: description.sampler(),
+        })
+    }
+
+    fn scene_linear_texture<F: Facade>(
+        &mut self,
+        facade: &F,
+        description: &TextureDescription,
+    ) -> Result<MaterialTexture<Texture2d>, Box<dyn Error>> {
+        Ok(MaterialTexture {
+            texture: self.assets.linear_texture_with_mipmaps(
+                facade,
+                &description.path,
+                &description.mipmaps(),
+            )?,
+            sampler: description.sampler(),
+        })
+    }
+
+    /// Draws `background` behind the scene. An environment map also lights it; a flat color
+    /// leaves the lighting of the current environment in place.
+    pub fn set_background<F: Facade>(
+        &mut self,
+        facade: &F,
+        background: &Background,
+    ) -> Result<(), Box<dyn Error>> {
+        match background {
+            Background::Environment(path) => {
+                if *path != self.environment_path {
+                    self.environment = Environment::load(facade, &mut self.assets, path)?;
+                    self.environment_path = path.clone();
+                }
+                self.clear_color = None;
+            }
+            Background::Color(color) => self.clear_color = Some(*color),
+        }
+        Ok(())
+    }
+
+    /// Reloads the default textures and any models whose files changed on disk since they were
+    /// loaded. Cheap enough to call every frame; the files are only checked a few times a second.
+    pub fn reload_changed_assets<F: Facade>(&mut self, facade: &F) {
+        let changed = self.assets.poll();
+        if changed.is_empty() {
+            return;
+        }
+        for path in &changed {
+            println!("{} changed", path.display());
+        }
+
+        let mut reloaded = false;
+        {
+            let mut phong = self.phong_program.borrow_mut();
+            let mut pbr = self.pbr_program.borrow_mut();
+            let (phong, pbr) = (&mut *phong, &mut *pbr);
+            let lit_programs = [
+                &mut phong.single,
+                &mut pbr.single,
+                &mut phong.instanced,
+                &mut pbr.instanced,
+                self.shadows.program_mut(),
+            ];
+            for program in lit_programs.into_iter().chain(self.post.programs_mut()) {
+                if program
+                    .files()
+                    .iter()
+                    .any(|f| changed.iter().any(|c| c == f))
+                    && program.reload(facade, &mut self.assets)
+                {
+                    println!("Reloaded shaders");
+                    reloaded = true;
+                }
+            }
+        }
+        // A shader that compiles can still expect uniforms the renderer doesn't set
+        if reloaded {
+            self.uniform_error = self.check_programs().err().map(|e| e.to_string());
+            if let Some(error) = &self.uniform_error {
+                eprintln!("{}", error);
+            }
+        }
+
+        if self.environment.files().iter().any(|f| changed.contains(f)) {
+            let path = &self.environment_path;
+            match Environment::load(facade, &mut self.assets, path) {
+                Ok(environment) => {
+                    self.environment = environment;
+                    println!("Reloaded {}", path);
+                }
+                Err(e) => println!("Could not reload {} ({})", path, e),
+            }
+        }
+
+        if changed.iter().any(|c| c == self.post.lut_path()) {
+            match self.post.reload_lut(facade) {
+                Ok(()) => println!("Reloaded {}", self.post.lut_path().display()),
+                Err(e) => println!("Could not reload the color grading table ({})", e),
+            }
+        }
+
+        // Both are still cached unless their file is one of the changed ones
+        let (diffuse, normal) = &self.default_textures;
+        match self.assets.srgb_texture(facade, diffuse) {
+            Ok(texture) => self.default_inputs.base_color.texture = texture,
+            Err(e) => println!("Could not reload {} ({})", diffuse, e),
+        }
+        match self.assets.linear_texture(facade, normal) {
+            Ok(texture) => self.default_inputs.normal_map.texture = texture,
+            Err(e) => println!("Could not reload {} ({})", normal, e),
+        }
+        if let Err(e) = self.update_default_material() {
+            println!("Could not update the default material ({})", e);
+        }
+
+        for i in 0..self.models.len() {
+            if !self.models[i].files.iter().any(|f| changed.contains(f)) {
+                continue;
+            }
+            // A half-saved file fails to load; keep showing the old model until the next save
+            let path = self.models[i].files[0].clone();
+            let name = self.models[i].name.clone();
+            let reloaded = match self.models[i].source.clone() {
+                Some((scene, file)) => self.reload_scene(facade, &name, scene, file.as_deref()),
+                None => self.load_model(facade, &path),
+            };
+            match reloaded {
+                Ok(mut model) => {
+                    model.name = name;
+                    self.models[i] = model;
+                    println!("Reloaded {}", path.display());
+                }
+                Err(e) => println!("Could not reload {} ({})", path.display(), e),
+            }
+        }
+    }
+
+    // Builds a scene again, reading its file again if it has one. Its background and lights
+    // replace the current ones, as when it was first shown.
+    fn reload_scene<F: Facade>(
+        &mut self,
+        facade: &F,
+        name: &str,
+        scene: SceneDescription,
+        file: Option<&Path>,
+    ) -> Result<Model, Box<dyn Error>> {
+        let scene = match file {
+            Some(file) => scene_file::load(file)?,
+            None => scene,
+        };
+        let model = self.build_scene(facade, name, &scene, file)?;
+        self.use_scene_lighting(facade, &scene)?;
+        Ok(model)
+    }
+
+    fn use_scene_lighting<F: Facade>(
+        &mut self,
+        facade: &F,
+        scene: &SceneDescription,
+    ) -> Result<(), Box<dyn Error>> {
+        self.set_background(facade, &scene.background)?;
+        self.lights = scene.lights.iter().map(LightDescription::light).collect();
+        Ok(())
+    }
+
+    /// Loads a model file, telling OBJ from glTF by its contents.
+    fn load_model<F: Facade>(&mut self, facade: &F, path: &Path) -> Result<Model, Box<dyn Error>> {
+        let model = match ModelFormat::of_file(path)? {
+            ModelFormat::Obj => self.load_obj_model(facade, path)?,
+            ModelFormat::Gltf | ModelFormat::Glb => self.load_gltf_model(facade, path)?,
+        };
+        for file in &model.files {
+            self.assets.watch(file);
+        }
+        Ok(model)
+    }
+
+    fn load_obj_model<F: Facade>(
+        &mut self,
+        facade: &F,
+        path: &Path,
+    ) -> Result<Model, Box<dyn Error>> {
+        let obj = obj::load(path)?;
+        println!("Loaded {}", path.display());
+
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let mut files = vec![path.to_path_buf()];
+        files.extend(obj.material_libraries.iter().map(|l| dir.join(l)));
+
+        let mut model = Model::new(&path.display().to_string(), Vec::new());
+        for group in &obj.groups {
+            println!(
+                "  {}: {} triangles",
+                group.name,
+                group.data.indices.len() / 3
+            );
+            let mut mesh = Mesh::new(facade, &group.data);
+            if let Some(material) = group.material.as_ref().and_then(|m| obj.material(m)) {
+                let base_color = match &material.diffuse_map {
+                    Some(map) => {
+                        files.push(self.assets.resolve(map));
+                        self.assets.srgb_texture(facade, map)?
+                    }
+                    None => Rc::new(texture::solid_srgb_texture(facade, material.diffuse_color)),
+                };
+                let normal_map = match &material.normal_map {
+                    Some(map) => {
+                        files.push(self.assets.resolve(map));
+                        self.assets.linear_texture(facade, map)?
+                    }
+                    None => Rc::new(texture::flat_normal_map(facade)),
+                };
+                let shading = if material.is_pbr() {
+                    ShadingModel::Pbr
+                } else {
+                    ShadingModel::BlinnPhong
+                };
+                let mut inputs = StandardInputs::new(facade, base_color, normal_map);
+                if let Some(metallic) = material.metallic {
+                    inputs.metallic_factor = metallic;
+                }
+                if let Some(roughness) = material.roughness {
+                    inputs.roughness_factor = roughness;
+                }
+                inputs.emissive_factor = material.emissive_color;
+                mesh.material = Some(Rc::new(self.standard_material(shading, &inputs)?));
+            }
+            let node = model
+                .scene
+                .add(Some(model.root), &group.name, Transform::IDENTITY);
+            model.scene.node_mut(node).meshes.push(Rc::new(mesh));
+        }
+        model.files = files;
+        Ok(model)
+    }
+
+    /// Loads a glTF scene. If it contains a camera, the model is viewed through the first one.
+    fn load_gltf_model<F: Facade>(
+        &mut self,
+        facade: &F,
+        path: &Path,
+    ) -> Result<Model, Box<dyn Error>> {
+        let scene = gltf_scene::load(path)?;
+        println!("Loaded {}", path.display());
+        scene.print_summary();
+
+        // Materials are shared by the primitives using them. The images are uploaded once each,
+        // in the color space of their first use
+        let mut srgb_images = HashMap::new();
+        let mut srgb_image = |image: usize| -> Rc<SrgbTexture2d> {
+            srgb_images
+                .entry(image)
+                .or_insert_with(|| {
+                    Rc::new(texture::srgb_texture(facade, scene.images[image].clone()))
+                })
+                .clone()
+        };
+        let mut linear_images = HashMap::new();
+        let mut linear_image = |image: usize| -> Rc<Texture2d> {
+            linear_images
+                .entry(image)
+                .or_insert_with(|| {
+                    Rc::new(texture::linear_texture(facade, scene.images[image].clone()))
+                })
+                .clone()
+        };
+        let materials = scene
+            .materials
+            .iter()
+            .map(|material| {
+                let mut srgb = |texture: TextureRef| MaterialTexture {
+                    texture: srgb_image(texture.image),
+                    sampler: texture.sampler,
+                };
+                let mut linear = |texture: TextureRef| MaterialTexture {
+                    texture: linear_image(texture.image),
+                    sampler: texture.sampler,
+                };
+                let mut inputs = StandardInputs::new(
+                    facade,
+                    Rc::new(texture::solid_srgb_texture(facade, [1.0, 1.0, 1.0])),
+                    Rc::new(texture::flat_normal_map(facade)),
+                );
+                inputs.base_color_factor = material.base_color_factor;
+                inputs.metallic_factor = material.metallic_factor;
+                inputs.roughness_factor = material.roughness_factor;
+                inputs.emissive_factor = material.emissive_factor;
+                if let Some(texture) = material.base_color_texture {
+                    inputs.base_color = srgb(texture);
+                }
+                if let Some(texture) = material.normal_texture {
+                    inputs.normal_map = linear(texture);
+                }
+                if let Some(texture) = material.metallic_roughness_texture {
+                    inputs.metallic_roughness = linear(texture);
+                }
+                if let Some(texture) = material.occlusion_texture {
+                    inputs.occlusion = linear(texture);
+                }
+                if let Some(texture) = material.emissive_texture {
+                    inputs.emissive = srgb(texture);
+                }
+                Ok(Rc::new(self.standard_material(ShadingModel::Pbr, &inputs)?))
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+
+        // Like materials, meshes are uploaded once and shared by the nodes using them
+        let meshes: Vec<Vec<Rc<Mesh>>> = scene
+            .meshes
+            .iter()
+            .map(|mesh| {
+                mesh.primitives
+                    .iter()
+                    .map(|primitive| {
+                        let mut mesh = Mesh::new(facade, &primitive.data);
+                        mesh.material = primitive.material.map(|m| materials[m].clone());
+                        Rc::new(mesh)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut model = Model::new(&path.display().to_string(), Vec::new());
+        // Parents come before their children in both node lists
+        let mut ids = Vec::with_capacity(scene.nodes.len());
+        for (i, node) in scene.nodes.iter().enumerate() {
+            let parent = node.parent.map_or(model.root, |parent| ids[parent]);
+            let name = node.name.clone().unwrap_or_else(|| format!("node {}", i));
+            let id = model.scene.add(Some(parent), &name, node.transform);
+            if let Some(mesh) = node.mesh {
+                model.scene.node_mut(id).meshes = meshes[mesh].clone();
+            }
+            ids.push(id);
+        }
+
+        let camera = scene.first_camera().map(|(node, camera)| {
+            (
+                SceneCamera::view_matrix(&node.world_transform),
+                camera.clone(),
+            )
+        });
+
+        model.camera = camera;
+        model.files = vec![path.to_path_buf()];
+        model.files.extend(scene.external_files);
+        Ok(model)
+    }
+
+    /// The error that stopped the shaders from being reloaded, while the previous ones are used,
+    /// or the uniforms the reloaded ones are missing.
+    pub fn shader_error(&self) -> Option<String> {
+        let phong = self.phong_program.borrow();
+        let pbr = self.pbr_program.borrow();
+        let error = [&phong.single, &phong.instanced, &pbr.single, &pbr.instanced]
+            .into_iter()
+            .chain([self.shadows.program()])
+            .chain(self.post.programs())
+            .find_map(|p| p.error())
+            .or(self.uniform_error.as_deref())
+            .map(str::to_string);
+        error
+    }
+
+    /// Replaces the textures of meshes without a material of their own. The paths are looked up
+    /// like other assets, and the textures reload when their files change.
+    pub fn set_default_textures<F: Facade>(
+        &mut self,
+        facade: &F,
+        diffuse: &str,
+        normal_map: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        self.default_inputs.base_color.texture = self.assets.srgb_texture(facade, diffuse)?;
+        self.default_inputs.normal_map.texture = self.assets.linear_texture(facade, normal_map)?;
+        self.default_textures = (diffuse.to_string(), normal_map.to_string());
+        self.update_default_material()
+    }
+
+    /// The lighting model of meshes without a material of their own, such as the built-in ones.
+    pub fn default_shading(&self) -> ShadingModel {
+        self.default_shading
+    }
+
+    pub fn set_default_shading(&mut self, shading: ShadingModel) -> Result<(), Box<dyn Error>> {
+        self.default_material = self.standard_material(shading, &self.default_inputs)?;
+        self.default_shading = shading;
+        Ok(())
+    }
+
+    // Rebuilds the default material after its inputs or shading model changed
+    fn update_default_material(&mut self) -> Result<(), Box<dyn Error>> {
+        self.default_material =
+            self.standard_material(self.default_shading, &self.default_inputs)?;
+        Ok(())
+    }
+
+    pub fn shadow_settings(&self) -> ShadowSettings {
+        self.shadows.settings()
+    }
+
+    pub fn set_shadow_settings(&mut self, settings: ShadowSettings) -> Result<(), Box<dyn Error>> {
+        self.shadows.set_settings(&self.context, settings)
+    }
+
+    pub fn lights(&self) -> &[Light] {
+        &self.lights
+    }
+
+    /// Replaces the lights of the scene, given in world space. Only the first
+    /// `light::MAX_LIGHTS` are used.
+    pub fn set_lights(&mut self, lights: Vec<Light>) {
+        self.lights = lights;
+    }
+
+    pub fn post_chain(&self) -> &[PostPass] {
+        self.post.chain()
+    }
+
+    /// Sets the post-processing passes, in the order they are applied. With none and without
+    /// anti-aliasing, the scene is drawn straight into the frame.
+    pub fn set_post_chain(&mut self, chain: Vec<PostPass>) {
+        self.post.set_chain(chain);
+    }
+
+    pub fn antialiasing(&self) -> Antialiasing {
+        self.post.antialiasing()
+    }
+
+    /// Multisampling applies to the scene, FXAA and SMAA to the image after the other
+    /// post-processing passes. A sample count the driver doesn't support is replaced by one
+    /// it does.
+    pub fn set_antialiasing(&mut self, antialiasing: Antialiasing) {
+        let antialiasing = match antialiasing {
+            Antialiasing::Msaa(samples) => {
+                let supported = post::supported_samples(&self.context, samples);
+                if supported != samples {
+                    println!(
+                        "{} samples per pixel requested, using {}",
+                        samples, supported
+                    );
+                }
+                Antialiasing::Msaa(supported)
+            }
+            other => other,
+        };
+        self.post.set_antialiasing(antialiasing);
+    }
+
+    /// Grades colors with the table in the image at `path`, looked up like other assets, in the
+    /// `color_grade` pass.
+    pub fn set_color_grade_lut<F: Facade>(
+        &mut self,
+        facade: &F,
+        path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        self.post.set_lut(facade, &mut self.assets, path)
+    }
+
+    pub fn instancing(&self) -> bool {
+        self.instancing
+    }
+
+    pub fn set_instancing(&mut self, instancing: bool) {
+        self.instancing = instancing;
+    }
+
+    pub fn tangent_mode(&self) -> TangentMode {
+        self.tangent_mode
+    }
+
+    pub fn set_tangent_mode(&mut self, mode: TangentMode) {
+        self.tangent_mode = mode;
+    }
+
+    pub fn model_name(&self) -> &str {
+        &self.models[self.current_model].name
+    }
+
+    /// Switches to the next model, wrapping around after the last one.
+    pub fn next_model(&mut self) {
+        self.current_model = (self.current_model + 1) % self.models.len();
+    }
+
+    /// Switches to the model called `name`. Returns false if there is no such model.
+    pub fn select_model(&mut self, name: &str) -> bool {
+        match self.models.iter().position(|m| m.name == name) {
+            Some(i) => {
+                self.current_model = i;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Loads `fragment` behind the vertex shaders for single and instanced meshes.
+fn load_material_program<F: Facade>(
+    facade: &F,
+    assets: &mut AssetManager,
+    fragment: &str,
+) -> Result<ProgramHandle, Box<dyn Error>> {
+    Ok(Rc::new(RefCell::new(MaterialProgram {
+        single: ShaderProgram::load(facade, assets, VERTEX_SHADER, fragment)?,
+        instanced: ShaderProgram::load(facade, assets, INSTANCED_VERTEX_SHADER, fragment)?,
+    })))
+}
+
+/// A material drawn with `program`, the program for `shading`, from the demo's usual inputs.
+/// Errors name the fragment shader the inputs don't fit.
+fn standard_material(
+    program: &ProgramHandle,
+    shading: ShadingModel,
+    inputs: &StandardInputs,
+) -> Result<Material, Box<dyn Error>> {
+    Material::new(program.clone(), inputs.parameters(shading)).map_err(|e| {
+        let fragment = program.borrow().single.files()[1].display().to_string();
+        format!("{}: {}", fragment, e).into()
+    })
+}
+
+/// A built-in model of `CROWD_SIDE` squared tinted teapots in a grid, for stress testing.
+fn crowd_model<F: Facade>(facade: &F) -> Model {
+    const SPACING: f32 = 0.3;
+    let teapot = Rc::new(Mesh::new(facade, &MeshData::teapot()));
+    let offset = (CROWD_SIDE - 1) as f32 * SPACING / 2.0;
+    let mut instances = Vec::with_capacity(CROWD_SIDE * CROWD_SIDE);
+    for row in 0..CROWD_SIDE {
+        for column in 0..CROWD_SIDE {
+            let position = vec3(
+                column as f32 * SPACING - offset,
+                -0.3,
+                row as f32 * SPACING - offset,
+            );
+            let transform = Mat4::from_trs(
+                position,
+                Quat::from_axis_angle(Vec3::Y, (row * 7 + column * 3) as f32),
+                Vec3::ONE * 0.1,
+            );
+            // Shades from red to green across and to blue from front to back
+            let u = column as f32 / (CROWD_SIDE - 1) as f32;
+            let v = row as f32 / (CROWD_SIDE - 1) as f32;
+            instances.push(Instance::new(transform, [1.0 - u, u, v]));
+        }
+    }
+
+    let mut model = Model::new("crowd", Vec::new());
+    let crowd = InstancedMesh::new(facade, teapot, instances);
+    model.instanced.push((model.root, crowd));
+    model
+}
+
+// Outlines the frame in red, so a failed shader reload is noticed even with stderr out of sight
+fn draw_error_frame<S: Surface>(target: &mut S) {
+    const WIDTH: u32 = 4;
+    let (width, height) = target.get_dimensions();
+    let edges = [
+        (0, 0, width, WIDTH),
+        (0, height.saturating_sub(WIDTH), width, WIDTH),
+        (0, 0, WIDTH, height),
+        (width.saturating_sub(WIDTH), 0, WIDTH, height),
+    ];
+    for (left, bottom, width, height) in edges {
+        let rect = Rect {
+            left,
+            bottom,
+            width,
+            height,
+        };
+        target.clear(Some(&rect), Some((1.0, 0.0, 0.0, 1.0)), false, None, None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use glium::framebuffer::SimpleFrameBuffer;
+    use glium::texture::DepthTexture2d;
+
+    use crate::headless;
+
+    #[test]
+    fn meshes_outside_the_view_are_culled() {
+        let context = headless::create_context((64, 64)).unwrap();
+        let assets = AssetManager::new(AssetManager::default_root());
+        let mut renderer = Renderer::new(&context, assets).unwrap();
+        assert!(renderer.select_model("teapots"));
+        renderer.update(1.0);
+
+        let color = SrgbTexture2d::empty(&context, 64, 64).unwrap();
+        let depth = DepthTexture2d::empty(&context, 64, 64).unwrap();
+        let mut target = SimpleFrameBuffer::with_depth_buffer(&context, &color, &depth).unwrap();
+
+        // The floor, the teapot in the middle and the three circling it
+        let stats = renderer.draw(&mut target, &Camera::default());
+        assert_eq!(
+            stats,
+            FrameStats {
+                drawn: 5,
+                culled: 0,
+                draw_calls: 5
+            }
+        );
+
+        let away = Camera::looking_at(vec3(0.0, 0.0, -3.0), vec3(0.0, 0.0, -4.0));
+        let stats = renderer.draw(&mut target, &away);
+        assert_eq!(
+            stats,
+            FrameStats {
+                drawn: 0,
+                culled: 5,
+                draw_calls: 0
+            }
+        );
+
+        // Close up on the middle teapot, the others are out of view
+        let close = Camera::looking_at(vec3(0.0, 0.6, -0.9), vec3(0.0, 0.0, 0.0));
+        let stats = renderer.draw(&mut target, &close);
+        assert_eq!(stats.drawn + stats.culled, 5);
+        assert!(stats.culled > 0, "{:?}", stats);
+    }
+}
@@ -0,0 +1,271 @@
+// Scene graph: a tree of nodes, each placed relative to its parent by a translation, rotation and
+// scale, and optionally drawing meshes.
+//
+// Nodes cache their world matrix. Changing a node's transform updates the cached matrices of the
+// node and everything below it, so moving a node moves its whole subtree while the children keep
+// their own transforms.
+
+use std::rc::Rc;
+
+use crate::bounds;
+use crate::material::Material;
+use crate::math::Mat4;
+use crate::math::Quat;
+use crate::math::Vec3;
+use crate::mesh::Mesh;
+
+/// Placement of a node relative to its parent, applied to points in the order scale, rotation,
+/// translation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+        scale: Vec3::ONE,
+    };
+
+    pub fn from_translation(translation: Vec3) -> Self {
+        Transform {
+            translation,
+            ..Transform::IDENTITY
+        }
+    }
+
+    pub fn from_rotation(rotation: Quat) -> Self {
+        Transform {
+            rotation,
+            ..Transform::IDENTITY
+        }
+    }
+
+    pub fn matrix(&self) -> Mat4 {
+        Mat4::from_trs(self.translation, self.rotation, self.scale)
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+/// Refers to a node of the scene it was added to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+pub struct Node {
+    pub name: String,
+    transform: Transform,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    /// The node's transform combined with those of all its ancestors.
+    world_transform: Mat4,
+    /// Drawn with the node's world transform. Meshes can be shared between nodes.
+    pub meshes: Vec<Rc<Mesh>>,
+    /// Replaces the materials of the node's meshes when set.
+    pub material: Option<Rc<Material>>,
+}
+
+impl Node {
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    pub fn children(&self) -> &[NodeId] {
+        &self.children
+    }
+
+    pub fn world_transform(&self) -> Mat4 {
+        self.world_transform
+    }
+
+    /// The material `mesh`, one of the node's meshes, is drawn with, if it has one.
+    pub fn material_for<'a>(&'a self, mesh: &'a Mesh) -> Option<&'a Material> {
+        self.material.as_deref().or(mesh.material.as_deref())
+    }
+}
+
+#[derive(Default)]
+pub struct Scene {
+    /// Parents always come before their children.
+    nodes: Vec<Node>,
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Scene::default()
+    }
+
+    /// Adds an empty node below `parent`, or as a root if `parent` is `None`.
+    pub fn add(&mut self, parent: Option<NodeId>, name: &str, transform: Transform) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        let parent_transform = match parent {
+            Some(parent) => {
+                self.nodes[parent.0].children.push(id);
+                self.nodes[parent.0].world_transform
+            }
+            None => Mat4::IDENTITY,
+        };
+        self.nodes.push(Node {
+            name: name.to_string(),
+            transform,
+            parent,
+            children: Vec::new(),
+            world_transform: parent_transform * transform.matrix(),
+            meshes: Vec::new(),
+            material: None,
+        });
+        id
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+
+    /// The node, for changing its meshes and material. Its transform is set with
+    /// `set_transform`, which keeps the world transforms below it up to date.
+    pub fn node_mut(&mut self, id: NodeId) -> &mut Node {
+        &mut self.nodes[id.0]
+    }
+
+    /// Moves a node, and with it all of its descendants.
+    pub fn set_transform(&mut self, id: NodeId, transform: Transform) {
+        self.nodes[id.0].transform = transform;
+
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id.0];
+            let parent_transform = match node.parent {
+                Some(parent) => self.nodes[parent.0].world_transform,
+                None => Mat4::IDENTITY,
+            };
+            let world_transform = parent_transform * node.transform.matrix();
+            let node = &mut self.nodes[id.0];
+            node.world_transform = world_transform;
+            stack.extend_from_slice(&node.children);
+        }
+    }
+
+    /// Copies the node `root` of `other`, and everything below it, to below `parent`. The copies
+    /// share their meshes and materials with the originals. Returns the copy of `root`.
+    pub fn add_subtree(&mut self, parent: Option<NodeId>, other: &Scene, root: NodeId) -> NodeId {
+        let source = other.node(root);
+        let id = self.add(parent, &source.name, source.transform);
+        self.nodes[id.0].meshes = source.meshes.clone();
+        self.nodes[id.0].material = source.material.clone();
+        for &child in &source.children {
+            self.add_subtree(Some(id), other, child);
+        }
+        id
+    }
+
+    /// Every mesh in the scene with the node drawing it.
+    pub fn meshes(&self) -> impl Iterator<Item = (&Node, &Mesh)> {
+        self.nodes
+            .iter()
+            .flat_map(|node| node.meshes.iter().map(move |mesh| (node, &**mesh)))
+    }
+
+    /// A world space sphere, as center and radius, around the meshes of the node and its
+    /// descendants. `None` if there are no meshes below the node.
+    pub fn bounds(&self, id: NodeId) -> Option<(Vec3, f32)> {
+        let node = &self.nodes[id.0];
+        let own = node
+            .meshes
+            .iter()
+            .map(|mesh| bounds::transform_sphere(&node.world_transform, mesh.bounding_sphere()));
+        let children = node.children.iter().filter_map(|&child| self.bounds(child));
+        own.chain(children).reduce(bounds::merge_spheres)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::headless;
+    use crate::math::vec3;
+    use crate::mesh::MeshData;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn children_follow_their_parents() {
+        let mut scene = Scene::new();
+        let root = scene.add(
+            None,
+            "root",
+            Transform::from_translation(vec3(1.0, 0.0, 0.0)),
+        );
+        let arm = scene.add(
+            Some(root),
+            "arm",
+            Transform::from_rotation(Quat::from_axis_angle(Vec3::Y, std::f32::consts::FRAC_PI_2)),
+        );
+        let hand = scene.add(Some(arm), "hand", Transform::from_translation(Vec3::Z));
+
+        let position = |scene: &Scene| {
+            scene
+                .node(hand)
+                .world_transform()
+                .transform_point(Vec3::ZERO)
+        };
+        // Turning +Z a quarter turn around Y points it along +X
+        assert_vec3_eq(position(&scene), vec3(2.0, 0.0, 0.0));
+
+        scene.set_transform(root, Transform::from_translation(vec3(0.0, 3.0, 0.0)));
+        assert_vec3_eq(position(&scene), vec3(1.0, 3.0, 0.0));
+        assert_eq!(
+            scene.node(hand).transform(),
+            Transform::from_translation(Vec3::Z)
+        );
+        assert_eq!(scene.node(root).children(), &[arm]);
+    }
+
+    #[test]
+    fn bounds_cover_the_subtree() {
+        let context = headless::create_context((1, 1)).unwrap();
+        let quad = Rc::new(Mesh::new(&context, &MeshData::quad()));
+
+        let mut scene = Scene::new();
+        let root = scene.add(None, "root", Transform::IDENTITY);
+        let empty = scene.add(Some(root), "empty", Transform::IDENTITY);
+        assert_eq!(scene.bounds(root), None);
+
+        for x in [-3.0, 3.0] {
+            let node = scene.add(
+                Some(root),
+                "quad",
+                Transform::from_translation(vec3(x, 0.0, 0.0)),
+            );
+            scene.node_mut(node).meshes.push(quad.clone());
+        }
+        let (center, radius) = scene.bounds(root).unwrap();
+        assert_vec3_eq(center, Vec3::ZERO);
+        assert!((radius - (3.0 + 2f32.sqrt())).abs() < 1e-4);
+        assert_eq!(scene.bounds(empty), None);
+        assert_eq!(scene.meshes().count(), 2);
+
+        // A copy placed elsewhere has the same size
+        let mut copy = Scene::new();
+        let offset = copy.add(
+            None,
+            "offset",
+            Transform::from_translation(vec3(0.0, 5.0, 0.0)),
+        );
+        let copied = copy.add_subtree(Some(offset), &scene, root);
+        assert_eq!(copy.node(copied).children().len(), 3);
+        assert_eq!(copy.meshes().count(), 2);
+        let (center, copied_radius) = copy.bounds(copied).unwrap();
+        assert_vec3_eq(center, vec3(0.0, 5.0, 0.0));
+        assert_eq!(copied_radius, radius);
+    }
+}
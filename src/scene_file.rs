@@ -0,0 +1,1063 @@
+// Scenes described in JSON files: the nodes with their meshes and materials, the lights, the
+// camera and what is drawn behind everything.
+//
+// The description is plain data. It is checked on its own first, so mistakes are reported with
+// where in the file they are, such as `nodes[0].children[2].material`, before anything is
+// uploaded; the renderer then builds a model from it. A description built in code can be saved
+// back out with `save`.
+//
+// Paths to meshes and textures are looked up like other assets: in the asset directory first,
+// then relative to the working directory. A texture is written as just its path when it is read
+// the usual way, or as an object with the path and its sampler and mipmap settings.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use glium::uniforms::MagnifySamplerFilter;
+use glium::uniforms::MinifySamplerFilter;
+use glium::uniforms::SamplerBehavior;
+use glium::uniforms::SamplerWrapFunction;
+use serde::de;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::assets::Mipmaps;
+use crate::camera::Camera;
+use crate::light;
+use crate::light::Light;
+use crate::light::LightKind;
+use crate::material::ShadingModel;
+use crate::math::Quat;
+use crate::math::Vec3;
+use crate::renderer;
+use crate::scene::Transform;
+use crate::texture;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SceneDescription {
+    /// The view the scene starts with; the demo's usual one if not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera: Option<CameraDescription>,
+    #[serde(default)]
+    pub background: Background,
+    /// All lights of the scene; without any, it is lit by the environment alone.
+    #[serde(default)]
+    pub lights: Vec<LightDescription>,
+    /// Materials by name, for the nodes to refer to.
+    #[serde(default)]
+    pub materials: BTreeMap<String, MaterialDescription>,
+    pub nodes: Vec<NodeDescription>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CameraDescription {
+    pub eye: [f32; 3],
+    pub target: [f32; 3],
+    /// Vertical field of view in degrees.
+    pub fov_y: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl CameraDescription {
+    /// An orbit camera at `eye`, turning around `target`.
+    pub fn camera(&self) -> Camera {
+        let mut camera = Camera::looking_at(self.eye.into(), self.target.into());
+        camera.fov_y = self.fov_y.to_radians();
+        camera.near = self.near;
+        camera.far = self.far;
+        camera
+    }
+}
+
+impl From<&Camera> for CameraDescription {
+    fn from(camera: &Camera) -> Self {
+        CameraDescription {
+            eye: camera.position().into(),
+            target: camera.target().into(),
+            fov_y: camera.fov_y.to_degrees(),
+            near: camera.near,
+            far: camera.far,
+        }
+    }
+}
+
+/// What fills the parts of the frame no mesh covers.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Background {
+    /// An equirectangular panorama, which also lights the scene.
+    Environment(String),
+    /// A flat sRGB color. The scene is still lit by the renderer's environment map.
+    Color([f32; 3]),
+}
+
+impl Default for Background {
+    fn default() -> Self {
+        Background::Environment(renderer::ENVIRONMENT.to_string())
+    }
+}
+
+/// A light, with angles in degrees. Point and spot lights fade out by `range`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum LightDescription {
+    Directional {
+        direction: [f32; 3],
+        color: [f32; 3],
+        intensity: f32,
+        #[serde(default)]
+        casts_shadows: bool,
+    },
+    Point {
+        position: [f32; 3],
+        range: f32,
+        color: [f32; 3],
+        intensity: f32,
+    },
+    Spot {
+        position: [f32; 3],
+        direction: [f32; 3],
+        range: f32,
+        inner_angle: f32,
+        outer_angle: f32,
+        color: [f32; 3],
+        intensity: f32,
+        #[serde(default)]
+        casts_shadows: bool,
+    },
+}
+
+impl LightDescription {
+    pub fn light(&self) -> Light {
+        match *self {
+            LightDescription::Directional {
+                direction,
+                color,
+                intensity,
+                casts_shadows,
+            } => Light {
+                casts_shadows,
+                ..Light::directional(direction.into(), color, intensity)
+            },
+            LightDescription::Point {
+                position,
+                range,
+                color,
+                intensity,
+            } => Light::point(position.into(), range, color, intensity),
+            LightDescription::Spot {
+                position,
+                direction,
+                range,
+                inner_angle,
+                outer_angle,
+                color,
+                intensity,
+                casts_shadows,
+            } => Light {
+                casts_shadows,
+                ..Light::spot(
+                    position.into(),
+                    direction.into(),
+                    range,
+                    inner_angle.to_radians(),
+                    outer_angle.to_radians(),
+                    color,
+                    intensity,
+                )
+            },
+        }
+    }
+}
+
+impl From<&Light> for LightDescription {
+    /// Describes `light`. The range of point and spot lights is worked out from their
+    /// attenuation; lights made with a range get it back.
+    fn from(light: &Light) -> Self {
+        match light.kind {
+            LightKind::Directional { direction } => LightDescription::Directional {
+                direction: direction.into(),
+                color: light.color,
+                intensity: light.intensity,
+                casts_shadows: light.casts_shadows,
+            },
+            LightKind::Point {
+                position,
+                attenuation,
+            } => LightDescription::Point {
+                position: position.into(),
+                range: attenuation.range(),
+                color: light.color,
+                intensity: light.intensity,
+            },
+            LightKind::Spot {
+                position,
+                direction,
+                attenuation,
+                inner_angle,
+                outer_angle,
+            } => LightDescription::Spot {
+                position: position.into(),
+                direction: direction.into(),
+                range: attenuation.range(),
+                inner_angle: inner_angle.to_degrees(),
+                outer_angle: outer_angle.to_degrees(),
+                color: light.color,
+                intensity: light.intensity,
+                casts_shadows: light.casts_shadows,
+            },
+        }
+    }
+}
+
+/// A material; textures that aren't given are white, or flat for the normal map.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaterialDescription {
+    pub shading: Shading,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_color: Option<TextureDescription>,
+    pub base_color_factor: [f32; 4],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normal_map: Option<TextureDescription>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metallic_roughness: Option<TextureDescription>,
+    pub metallic: f32,
+    pub roughness: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub occlusion: Option<TextureDescription>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emissive: Option<TextureDescription>,
+    pub emissive_factor: [f32; 3],
+}
+
+impl MaterialDescription {
+    /// The material's textures that are given, with the names of their fields.
+    pub fn textures(&self) -> impl Iterator<Item = (&'static str, &TextureDescription)> {
+        [
+            ("base_color", &self.base_color),
+            ("normal_map", &self.normal_map),
+            ("metallic_roughness", &self.metallic_roughness),
+            ("occlusion", &self.occlusion),
+            ("emissive", &self.emissive),
+        ]
+        .into_iter()
+        .filter_map(|(name, texture)| Some((name, texture.as_ref()?)))
+    }
+}
+
+impl Default for MaterialDescription {
+    fn default() -> Self {
+        MaterialDescription {
+            shading: Shading::Pbr,
+            base_color: None,
+            base_color_factor: [1.0, 1.0, 1.0, 1.0],
+            normal_map: None,
+            metallic_roughness: None,
+            metallic: 0.0,
+            roughness: 0.5,
+            occlusion: None,
+            emissive: None,
+            emissive_factor: [0.0, 0.0, 0.0],
+        }
+    }
+}
+
+/// An image file used by a material, and how it is sampled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(remote = "Self", deny_unknown_fields)]
+pub struct TextureDescription {
+    pub path: String,
+    #[serde(default)]
+    pub wrap: Wrap,
+    /// How the texture is filtered where it is drawn smaller than its size.
+    #[serde(default)]
+    pub min_filter: MinFilter,
+    /// How the texture is filtered where it is drawn larger than its size.
+    #[serde(default)]
+    pub mag_filter: MagFilter,
+    /// Up to how many samples are blended where the surface is seen at a grazing angle, keeping
+    /// the texture sharp along the receding direction. 1 turns anisotropic filtering off; the
+    /// driver lowers counts it doesn't support.
+    #[serde(default = "no_anisotropy")]
+    pub anisotropy: u16,
+    #[serde(default)]
+    pub mipmaps: MipmapsDescription,
+}
+
+fn no_anisotropy() -> u16 {
+    1
+}
+
+impl TextureDescription {
+    /// The image at `path`, read the usual way.
+    pub fn new(path: &str) -> Self {
+        TextureDescription {
+            path: path.to_string(),
+            wrap: Wrap::default(),
+            min_filter: MinFilter::default(),
+            mag_filter: MagFilter::default(),
+            anisotropy: no_anisotropy(),
+            mipmaps: MipmapsDescription::default(),
+        }
+    }
+
+    pub fn sampler(&self) -> SamplerBehavior {
+        let wrap = self.wrap.into();
+        SamplerBehavior {
+            wrap_function: (wrap, wrap, wrap),
+            minify_filter: self.min_filter.into(),
+            magnify_filter: self.mag_filter.into(),
+            max_anisotropy: self.anisotropy,
+            ..texture::DEFAULT_SAMPLER
+        }
+    }
+
+    pub fn mipmaps(&self) -> Mipmaps {
+        match &self.mipmaps {
+            MipmapsDescription::Generated => Mipmaps::Generated,
+            MipmapsDescription::None => Mipmaps::None,
+            MipmapsDescription::Files(files) => {
+                Mipmaps::Files(files.iter().map(PathBuf::from).collect())
+            }
+        }
+    }
+}
+
+// A path alone stands for a texture with the default settings, both when reading and writing
+impl Serialize for TextureDescription {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if *self == TextureDescription::new(&self.path) {
+            serializer.serialize_str(&self.path)
+        } else {
+            TextureDescription::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TextureDescription {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PathOrObject;
+
+        impl<'de> Visitor<'de> for PathOrObject {
+            type Value = TextureDescription;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a path, or an object with a path")
+            }
+
+            fn visit_str<E: de::Error>(self, path: &str) -> Result<Self::Value, E> {
+                Ok(TextureDescription::new(path))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                TextureDescription::deserialize(MapAccessDeserializer::new(map))
+            }
+        }
+
+        deserializer.deserialize_any(PathOrObject)
+    }
+}
+
+/// What happens to texture coordinates outside the 0 to 1 range.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Wrap {
+    #[default]
+    Repeat,
+    /// Repeats, flipping every other copy.
+    Mirror,
+    /// Stretches the edge texels.
+    Clamp,
+}
+
+impl From<Wrap> for SamplerWrapFunction {
+    fn from(wrap: Wrap) -> Self {
+        match wrap {
+            Wrap::Repeat => SamplerWrapFunction::Repeat,
+            Wrap::Mirror => SamplerWrapFunction::Mirror,
+            Wrap::Clamp => SamplerWrapFunction::Clamp,
+        }
+    }
+}
+
+/// The texel closest to the sample or a blend of the four around it, and, after `_mipmap_`,
+/// the same within the closest mipmap level or blended between the two around the sample's
+/// size.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MinFilter {
+    Nearest,
+    Linear,
+    NearestMipmapNearest,
+    LinearMipmapNearest,
+    NearestMipmapLinear,
+    #[default]
+    LinearMipmapLinear,
+}
+
+impl From<MinFilter> for MinifySamplerFilter {
+    fn from(filter: MinFilter) -> Self {
+        match filter {
+            MinFilter::Nearest => MinifySamplerFilter::Nearest,
+            MinFilter::Linear => MinifySamplerFilter::Linear,
+            MinFilter::NearestMipmapNearest => MinifySamplerFilter::NearestMipmapNearest,
+            MinFilter::LinearMipmapNearest => MinifySamplerFilter::LinearMipmapNearest,
+            MinFilter::NearestMipmapLinear => MinifySamplerFilter::NearestMipmapLinear,
+            MinFilter::LinearMipmapLinear => MinifySamplerFilter::LinearMipmapLinear,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MagFilter {
+    Nearest,
+    #[default]
+    Linear,
+}
+
+impl From<MagFilter> for MagnifySamplerFilter {
+    fn from(filter: MagFilter) -> Self {
+        match filter {
+            MagFilter::Nearest => MagnifySamplerFilter::Nearest,
+            MagFilter::Linear => MagnifySamplerFilter::Linear,
+        }
+    }
+}
+
+/// `Mipmaps` as written in scene files: `"generated"`, `"none"`, or `{ "files": [...] }` with
+/// an image for each level after the first.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MipmapsDescription {
+    #[default]
+    Generated,
+    None,
+    Files(Vec<String>),
+}
+
+/// `ShadingModel` as written in scene files.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Shading {
+    BlinnPhong,
+    Pbr,
+}
+
+impl From<ShadingModel> for Shading {
+    fn from(shading: ShadingModel) -> Self {
+        match shading {
+            ShadingModel::BlinnPhong => Shading::BlinnPhong,
+            ShadingModel::Pbr => Shading::Pbr,
+        }
+    }
+}
+
+impl From<Shading> for ShadingModel {
+    fn from(shading: Shading) -> Self {
+        match shading {
+            Shading::BlinnPhong => ShadingModel::BlinnPhong,
+            Shading::Pbr => ShadingModel::Pbr,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NodeDescription {
+    pub name: String,
+    #[serde(default = "zero")]
+    pub translation: [f32; 3],
+    /// Quaternion, as x, y, z and w.
+    #[serde(default = "no_rotation")]
+    pub rotation: [f32; 4],
+    #[serde(default = "unit_scale")]
+    pub scale: [f32; 3],
+    /// `quad`, `teapot` or the path of an OBJ or glTF file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mesh: Option<String>,
+    /// The name of a material replacing those of the node's meshes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub material: Option<String>,
+    /// One of the renderer's animations, replacing the transform while the scene runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animation: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<NodeDescription>,
+}
+
+fn zero() -> [f32; 3] {
+    [0.0; 3]
+}
+
+fn no_rotation() -> [f32; 4] {
+    [0.0, 0.0, 0.0, 1.0]
+}
+
+fn unit_scale() -> [f32; 3] {
+    [1.0; 3]
+}
+
+impl NodeDescription {
+    /// A node without meshes, children or a transform.
+    pub fn new(name: &str) -> Self {
+        NodeDescription {
+            name: name.to_string(),
+            translation: zero(),
+            rotation: no_rotation(),
+            scale: unit_scale(),
+            mesh: None,
+            material: None,
+            animation: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn transform(&self) -> Transform {
+        let [x, y, z, w] = self.rotation;
+        Transform {
+            translation: self.translation.into(),
+            rotation: Quat { x, y, z, w }.normalize(),
+            scale: self.scale.into(),
+        }
+    }
+}
+
+/// A mistake in a scene description, and where it is.
+#[derive(Debug, PartialEq)]
+pub struct ValidationError {
+    /// The path to the offending value, such as `lights[1].range`.
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+impl Error for ValidationError {}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(PathBuf, io::Error),
+    /// Not JSON, or not shaped like a scene. The error knows the line and column.
+    Parse(PathBuf, serde_json::Error),
+    Invalid(PathBuf, ValidationError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            LoadError::Parse(path, e) => write!(f, "{}: {}", path.display(), e),
+            LoadError::Invalid(path, e) => write!(f, "{}: {}", path.display(), e),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+/// Reads and validates the scene file at `path`.
+pub fn load(path: &Path) -> Result<SceneDescription, LoadError> {
+    let source = std::fs::read_to_string(path).map_err(|e| LoadError::Io(path.to_path_buf(), e))?;
+    let description = parse(&source).map_err(|e| LoadError::Parse(path.to_path_buf(), e))?;
+    description
+        .validate()
+        .map_err(|e| LoadError::Invalid(path.to_path_buf(), e))?;
+    Ok(description)
+}
+
+/// Reads a scene description without validating it.
+pub fn parse(source: &str) -> Result<SceneDescription, serde_json::Error> {
+    serde_json::from_str(source)
+}
+
+impl SceneDescription {
+    /// The demo as it starts: `model`, a built-in mesh or a model file, swinging on the
+    /// turntable. Its meshes are drawn with `material` when one is given.
+    pub fn single_model(
+        model: &str,
+        material: Option<MaterialDescription>,
+        lights: &[Light],
+        camera: &Camera,
+    ) -> Self {
+        let mut node = NodeDescription::new(model);
+        node.mesh = Some(model.to_string());
+        node.animation = Some("turntable".to_string());
+        let mut materials = BTreeMap::new();
+        if let Some(material) = material {
+            node.material = Some(model.to_string());
+            materials.insert(model.to_string(), material);
+        }
+        SceneDescription {
+            camera: Some(camera.into()),
+            background: Background::default(),
+            lights: lights.iter().map(LightDescription::from).collect(),
+            materials,
+            nodes: vec![node],
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_json() + "\n")
+    }
+
+    /// Checks the values the file format alone can't rule out, such as references to
+    /// materials that don't exist. Files are only looked at when the scene is built.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let fail = |location: String, message: &str| {
+            Err(ValidationError {
+                location,
+                message: message.to_string(),
+            })
+        };
+
+        if let Some(camera) = &self.camera {
+            if camera.eye == camera.target {
+                return fail("camera.target".to_string(), "is the same point as the eye");
+            }
+            if !(camera.fov_y > 0.0 && camera.fov_y < 180.0) {
+                return fail(
+                    "camera.fov_y".to_string(),
+                    "must be between 0 and 180 degrees",
+                );
+            }
+            if !(camera.near > 0.0 && camera.near < camera.far) {
+                return fail(
+                    "camera.near".to_string(),
+                    "must be above 0 and less than far",
+                );
+            }
+        }
+
+        if let Background::Color(color) = self.background {
+            if !color.iter().all(|c| (0.0..=1.0).contains(c)) {
+                return fail("background.color".to_string(), "must be between 0 and 1");
+            }
+        }
+
+        if self.lights.len() > light::MAX_LIGHTS {
+            return fail(
+                format!("lights[{}]", light::MAX_LIGHTS),
+                &format!("only {} lights are supported", light::MAX_LIGHTS),
+            );
+        }
+        for (i, description) in self.lights.iter().enumerate() {
+            let location = |field: &str| format!("lights[{}].{}", i, field);
+            let light = description.light();
+            if !light.color.iter().all(|c| *c >= 0.0) {
+                return fail(location("color"), "must not be negative");
+            }
+            if light.intensity < 0.0 {
+                return fail(location("intensity"), "must not be negative");
+            }
+            match description {
+                LightDescription::Directional { direction, .. } if is_zero(*direction) => {
+                    return fail(location("direction"), "must not be zero");
+                }
+                LightDescription::Point { range, .. } if *range <= 0.0 => {
+                    return fail(location("range"), "must be above 0");
+                }
+                LightDescription::Spot {
+                    direction,
+                    range,
+                    inner_angle,
+                    outer_angle,
+                    ..
+                } => {
+                    if is_zero(*direction) {
+                        return fail(location("direction"), "must not be zero");
+                    }
+                    if *range <= 0.0 {
+                        return fail(location("range"), "must be above 0");
+                    }
+                    if !(*outer_angle > 0.0 && *outer_angle < 90.0) {
+                        return fail(location("outer_angle"), "must be between 0 and 90 degrees");
+                    }
+                    if !(*inner_angle >= 0.0 && inner_angle <= outer_angle) {
+                        return fail(location("inner_angle"), "must be between 0 and outer_angle");
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (name, material) in &self.materials {
+            let location = |field: &str| format!("materials.{}.{}", name, field);
+            if !material.base_color_factor.iter().all(|c| *c >= 0.0) {
+                return fail(location("base_color_factor"), "must not be negative");
+            }
+            if !(0.0..=1.0).contains(&material.metallic) {
+                return fail(location("metallic"), "must be between 0 and 1");
+            }
+            if !(0.0..=1.0).contains(&material.roughness) {
+                return fail(location("roughness"), "must be between 0 and 1");
+            }
+            if !material.emissive_factor.iter().all(|c| *c >= 0.0) {
+                return fail(location("emissive_factor"), "must not be negative");
+            }
+            for (field, texture) in material.textures() {
+                let location = |setting: &str| location(&format!("{}.{}", field, setting));
+                if texture.path.is_empty() {
+                    return fail(location("path"), "is empty");
+                }
+                if texture.anisotropy == 0 {
+                    return fail(location("anisotropy"), "must be at least 1");
+                }
+                if texture.mipmaps == MipmapsDescription::Files(Vec::new()) {
+                    return fail(location("mipmaps"), "lists no files");
+                }
+            }
+        }
+
+        if self.nodes.is_empty() {
+            return fail("nodes".to_string(), "the scene has no nodes");
+        }
+        let mut stack: Vec<(String, &NodeDescription)> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (format!("nodes[{}]", i), node))
+            .collect();
+        while let Some((location, node)) = stack.pop() {
+            let location = |field: &str| format!("{}.{}", location, field);
+            if node.scale.contains(&0.0) {
+                return fail(location("scale"), "must not be zero along any axis");
+            }
+            if node.rotation == [0.0; 4] {
+                return fail(location("rotation"), "must not be zero");
+            }
+            if node.mesh.as_deref() == Some("") {
+                return fail(location("mesh"), "is empty");
+            }
+            if let Some(material) = &node.material {
+                if !self.materials.contains_key(material) {
+                    return fail(
+                        location("material"),
+                        &format!("there is no material called {:?}", material),
+                    );
+                }
+            }
+            if let Some(animation) = &node.animation {
+                if !renderer::ANIMATIONS
+                    .iter()
+                    .any(|(name, _)| name == animation)
+                {
+                    let names: Vec<&str> = renderer::ANIMATIONS.iter().map(|(n, _)| *n).collect();
+                    return fail(
+                        location("animation"),
+                        &format!(
+                            "there is no animation called {:?}; there are {}",
+                            animation,
+                            names.join(", ")
+                        ),
+                    );
+                }
+            }
+            stack.extend(
+                node.children
+                    .iter()
+                    .enumerate()
+                    .map(|(i, child)| (location(&format!("children[{}]", i)), child)),
+            );
+        }
+        Ok(())
+    }
+}
+
+fn is_zero(v: [f32; 3]) -> bool {
+    Vec3::from(v).length() == 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::light::Attenuation;
+
+    const EXAMPLE: &str = r#"{
+        "camera": { "eye": [0, 1, -3], "target": [0, 0, 0], "fov_y": 45, "near": 0.1, "far": 50 },
+        "background": { "color": [0.1, 0.1, 0.15] },
+        "lights": [
+            { "type": "directional", "direction": [1, -1, 1], "color": [1, 1, 1], "intensity": 1,
+              "casts_shadows": true },
+            { "type": "point", "position": [0, 2, 0], "range": 5, "color": [1, 0.5, 0.2],
+              "intensity": 2 }
+        ],
+        "materials": {
+            "gold": { "base_color_factor": [1, 0.78, 0.34, 1], "metallic": 1, "roughness": 0.3 }
+        },
+        "nodes": [
+            { "name": "floor", "mesh": "quad", "scale": [2, 2, 1] },
+            { "name": "spinner", "animation": "turntable", "children": [
+                { "name": "pot", "mesh": "teapot", "material": "gold", "translation": [1, 0, 0] }
+            ] }
+        ]
+    }"#;
+
+    fn example() -> SceneDescription {
+        let scene = parse(EXAMPLE).unwrap();
+        scene.validate().unwrap();
+        scene
+    }
+
+    /// Where validation fails after `change` is made to the example.
+    fn invalid(change: impl FnOnce(&mut SceneDescription)) -> ValidationError {
+        let mut scene = example();
+        change(&mut scene);
+        scene.validate().unwrap_err()
+    }
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-4, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn missing_fields_get_defaults() {
+        let scene = example();
+        assert_eq!(scene.nodes[0].translation, [0.0; 3]);
+        assert_eq!(scene.nodes[0].rotation, [0.0, 0.0, 0.0, 1.0]);
+        let gold = &scene.materials["gold"];
+        assert_eq!(gold.shading, Shading::Pbr);
+        assert_eq!(gold.normal_map, None);
+        assert_eq!(gold.emissive_factor, [0.0; 3]);
+        assert!(scene.lights[0].light().casts_shadows);
+        assert!(!scene.lights[1].light().casts_shadows);
+
+        let minimal = parse(r#"{ "nodes": [{ "name": "pot", "mesh": "teapot" }] }"#).unwrap();
+        assert_eq!(minimal.camera, None);
+        assert_eq!(minimal.background, Background::default());
+        assert!(minimal.lights.is_empty());
+        assert!(minimal.materials.is_empty());
+    }
+
+    #[test]
+    fn saved_scenes_load_unchanged() {
+        let scene = example();
+        assert_eq!(parse(&scene.to_json()).unwrap(), scene);
+
+        let lights = light::lamp_lights();
+        let camera = Camera::default();
+        let built = SceneDescription::single_model(
+            "teapot",
+            Some(MaterialDescription::default()),
+            &lights,
+            &camera,
+        );
+        built.validate().unwrap();
+        let loaded = parse(&built.to_json()).unwrap();
+        assert_eq!(loaded, built);
+
+        // Lights and cameras survive the trip through degrees and ranges
+        let Light { kind, .. } = loaded.lights[2].light();
+        let LightKind::Spot {
+            attenuation,
+            inner_angle,
+            outer_angle,
+            ..
+        } = kind
+        else {
+            panic!("{:?} is not a spot light", kind);
+        };
+        assert_close(
+            attenuation.quadratic,
+            Attenuation::with_range(10.0).quadratic,
+        );
+        assert_close(inner_angle, 0.2);
+        assert_close(outer_angle, 0.35);
+
+        let camera_description = loaded.camera.unwrap();
+        assert_close(camera_description.fov_y.to_radians(), camera.fov_y);
+        let eye = camera_description.camera().position();
+        assert!((eye - camera.position()).length() < 1e-5, "{:?}", eye);
+    }
+
+    #[test]
+    fn lights_without_quadratic_falloff_are_saved_with_a_finite_range() {
+        let light = |attenuation| Light {
+            kind: LightKind::Point {
+                position: Vec3::ZERO,
+                attenuation,
+            },
+            color: [1.0; 3],
+            intensity: 1.0,
+            casts_shadows: false,
+        };
+        let lights = [
+            light(Attenuation {
+                constant: 1.0,
+                linear: 0.5,
+                quadratic: 0.0,
+            }),
+            light(Attenuation {
+                constant: 1.0,
+                linear: 0.0,
+                quadratic: 0.0,
+            }),
+        ];
+        let built = SceneDescription::single_model("teapot", None, &lights, &Camera::default());
+        let loaded = parse(&built.to_json()).unwrap();
+        loaded.validate().unwrap();
+        assert_eq!(loaded, built);
+        let ranges: Vec<f32> = loaded
+            .lights
+            .iter()
+            .map(|light| match light {
+                LightDescription::Point { range, .. } => *range,
+                _ => panic!("{:?} is not a point light", light),
+            })
+            .collect();
+        assert_eq!(ranges, [198.0, light::UNLIMITED_RANGE]);
+    }
+
+    #[test]
+    fn textures_are_paths_or_settings() {
+        let material: MaterialDescription = serde_json::from_str(
+            r#"{
+                "base_color": "bricks.png",
+                "normal_map": { "path": "bricks_normal.png", "wrap": "clamp", "anisotropy": 8,
+                                "mipmaps": { "files": ["bricks_normal_1.png"] } },
+                "emissive": { "path": "glow.png", "min_filter": "nearest", "mipmaps": "none" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            material.base_color,
+            Some(TextureDescription::new("bricks.png"))
+        );
+        let normal_map = material.normal_map.as_ref().unwrap();
+        assert_eq!(normal_map.mag_filter, MagFilter::Linear);
+        assert_eq!(
+            normal_map.mipmaps(),
+            Mipmaps::Files(vec![PathBuf::from("bricks_normal_1.png")])
+        );
+        let sampler = normal_map.sampler();
+        assert_eq!(sampler.wrap_function.1, SamplerWrapFunction::Clamp);
+        assert_eq!(sampler.max_anisotropy, 8);
+        let emissive = material.emissive.as_ref().unwrap();
+        assert_eq!(
+            emissive.sampler().minify_filter,
+            MinifySamplerFilter::Nearest
+        );
+        assert_eq!(emissive.mipmaps(), Mipmaps::None);
+        assert_eq!(
+            TextureDescription::new("a.png").sampler(),
+            texture::DEFAULT_SAMPLER
+        );
+
+        // Textures with the usual settings are saved as their path
+        let json = serde_json::to_value(&material).unwrap();
+        assert_eq!(json["base_color"], "bricks.png");
+        assert_eq!(json["normal_map"]["wrap"], "clamp");
+        assert_eq!(
+            serde_json::from_value::<MaterialDescription>(json).unwrap(),
+            material
+        );
+
+        let error = serde_json::from_str::<MaterialDescription>(
+            r#"{ "base_color": { "path": "a.png", "filter": "nearest" } }"#,
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `filter`"),
+            "{}",
+            error
+        );
+        let error =
+            serde_json::from_str::<MaterialDescription>(r#"{ "base_color": 3 }"#).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("a path, or an object with a path"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn syntax_errors_have_a_line_and_column() {
+        let error = parse("{\n  \"nodes\": [\n    { \"name\": \"pot\", }\n  ]\n}").unwrap_err();
+        assert_eq!((error.line(), error.column()), (3, 22));
+
+        let error = parse(r#"{ "nodes": [], "fog": true }"#).unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `fog`"),
+            "{}",
+            error
+        );
+        let error = parse(r#"{ "nodes": [], "lights": [{ "type": "area" }] }"#).unwrap_err();
+        assert!(
+            error.to_string().contains("unknown variant `area`"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn mistakes_are_located() {
+        let error = invalid(|s| s.nodes[1].children[0].material = Some("silver".to_string()));
+        assert_eq!(
+            error.to_string(),
+            "nodes[1].children[0].material: there is no material called \"silver\""
+        );
+
+        let location = |change: fn(&mut SceneDescription)| invalid(change).location;
+        assert_eq!(
+            location(|s| s.nodes[1].animation = Some("bounce".to_string())),
+            "nodes[1].animation"
+        );
+        assert_eq!(location(|s| s.nodes[0].scale[2] = 0.0), "nodes[0].scale");
+        assert_eq!(location(|s| s.nodes.clear()), "nodes");
+        assert_eq!(
+            location(|s| s.materials.get_mut("gold").unwrap().roughness = 2.0),
+            "materials.gold.roughness"
+        );
+        assert_eq!(
+            location(|s| {
+                let mut texture = TextureDescription::new("gold.png");
+                texture.anisotropy = 0;
+                s.materials.get_mut("gold").unwrap().occlusion = Some(texture);
+            }),
+            "materials.gold.occlusion.anisotropy"
+        );
+        assert_eq!(
+            location(|s| {
+                let mut texture = TextureDescription::new("gold.png");
+                texture.mipmaps = MipmapsDescription::Files(Vec::new());
+                s.materials.get_mut("gold").unwrap().base_color = Some(texture);
+            }),
+            "materials.gold.base_color.mipmaps"
+        );
+        assert_eq!(
+            location(|s| s.lights[1] = (&Light::point(Vec3::ZERO, 1.0, [1.0; 3], -1.0)).into()),
+            "lights[1].intensity"
+        );
+        assert_eq!(
+            location(|s| s.camera.as_mut().unwrap().far = 0.05),
+            "camera.near"
+        );
+        assert_eq!(
+            location(|s| s.background = Background::Color([2.0, 0.0, 0.0])),
+            "background.color"
+        );
+    }
+}
@@ -0,0 +1,528 @@
+// Shader programs built from `.vert`/`.frag` files in the asset directory.
+//
+// Compiler and linker logs are rewritten to point at `file:line`, and a program that fails to
+// rebuild after an edit keeps drawing with the last version that compiled.
+//
+// A line `#include "file"` is replaced by the contents of `file`, found next to the including
+// shader. Code shared by several programs, such as the lights, lives in such files.
+//
+// glium quietly skips values for uniforms a program doesn't have and leaves the ones nobody set
+// at zero, so the uniforms meant for a program can be checked against the ones it declares.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use glium::backend::Facade;
+use glium::program::ProgramCreationError;
+use glium::program::ProgramCreationInput;
+use glium::program::ShaderType;
+use glium::uniforms::UniformType;
+use glium::uniforms::UniformValue;
+use glium::uniforms::Uniforms;
+use glium::Program;
+
+use crate::assets::AssetManager;
+
+/// How deeply `#include`s may nest, which also stops files that include themselves.
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+pub struct ShaderProgram {
+    vertex_path: PathBuf,
+    fragment_path: PathBuf,
+    /// Every file the program was built from, the included ones after the two shaders.
+    files: Vec<PathBuf>,
+    program: Program,
+    /// Whether the fragment shader writes sRGB encoded colors, which sRGB framebuffers then
+    /// store as they are.
+    outputs_srgb: bool,
+    /// Why the files on disk can't be used, while the last good program stands in for them.
+    error: Option<String>,
+}
+
+impl ShaderProgram {
+    /// Builds a program from a vertex and a fragment shader file. Unlike `reload`, this fails
+    /// on errors since there is no earlier program to fall back to.
+    pub fn load<F: Facade>(
+        facade: &F,
+        assets: &mut AssetManager,
+        vertex: impl AsRef<Path>,
+        fragment: impl AsRef<Path>,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::load_with_output(facade, assets, vertex, fragment, false)
+    }
+
+    /// Like `load`, for a fragment shader that writes colors sRGB encoded already. Drawing
+    /// into an sRGB framebuffer, which would encode linear colors, leaves them as they are.
+    pub fn load_encoded<F: Facade>(
+        facade: &F,
+        assets: &mut AssetManager,
+        vertex: impl AsRef<Path>,
+        fragment: impl AsRef<Path>,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::load_with_output(facade, assets, vertex, fragment, true)
+    }
+
+    fn load_with_output<F: Facade>(
+        facade: &F,
+        assets: &mut AssetManager,
+        vertex: impl AsRef<Path>,
+        fragment: impl AsRef<Path>,
+        outputs_srgb: bool,
+    ) -> Result<Self, Box<dyn Error>> {
+        let vertex_path = assets.resolve(vertex);
+        let fragment_path = assets.resolve(fragment);
+        let (program, files) = compile(facade, assets, &vertex_path, &fragment_path, outputs_srgb)?;
+        Ok(ShaderProgram {
+            vertex_path,
+            fragment_path,
+            files,
+            program,
+            outputs_srgb,
+            error: None,
+        })
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// The error from the last reload, if it failed.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Checks `uniforms`, everything a draw call passes to the program, against the uniforms
+    /// the program declares: each one has to be given a value of a fitting type. Values for
+    /// uniforms the program doesn't have are allowed, as a draw call may pass a set shared by
+    /// several programs. Array elements may be left out, since shaders only read as many as a
+    /// count uniform says.
+    pub fn check_uniforms<U: Uniforms>(&self, uniforms: &U) -> Result<(), UniformError> {
+        let mut given = HashSet::new();
+        let mut error = None;
+        uniforms.visit_values(|name, value| {
+            if self.program.get_uniform(name).is_some() && error.is_none() {
+                error = check_value(&self.program, name, &value).err();
+            }
+            given.insert(name.to_string());
+        });
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        let mut missing: Vec<_> = self
+            .program
+            .uniforms()
+            .map(|(name, _)| name)
+            .filter(|name| !name.contains('[') && !given.contains(*name))
+            .collect();
+        missing.sort();
+        match missing.first() {
+            Some(name) => Err(UniformError::Missing(name.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// Rebuilds the program from its files. On failure the error is printed to stderr and kept
+    /// for `error`, and the previous program stays in use. Returns whether the rebuild worked.
+    pub fn reload<F: Facade>(&mut self, facade: &F, assets: &mut AssetManager) -> bool {
+        match compile(
+            facade,
+            assets,
+            &self.vertex_path,
+            &self.fragment_path,
+            self.outputs_srgb,
+        ) {
+            Ok((program, files)) => {
+                self.program = program;
+                self.files = files;
+                self.error = None;
+                true
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                self.error = Some(e.to_string());
+                false
+            }
+        }
+    }
+}
+
+/// A value that doesn't fit the uniforms a program declares.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UniformError {
+    /// The program uses a uniform that wasn't given a value, so it would read zero.
+    Missing(String),
+    /// A value was given for a uniform the program doesn't have, such as a misspelled one.
+    Unknown(String),
+    /// The value can't be used for the uniform of this declared type.
+    WrongType(String, UniformType),
+}
+
+impl fmt::Display for UniformError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UniformError::Missing(name) => write!(f, "uniform {} is not set", name),
+            UniformError::Unknown(name) => write!(f, "there is no uniform {}", name),
+            UniformError::WrongType(name, ty) => {
+                write!(f, "uniform {} is declared as {:?}", name, ty)
+            }
+        }
+    }
+}
+
+impl Error for UniformError {}
+
+/// Checks that `program` has a uniform `name` that `value` can be used for.
+pub fn check_value(
+    program: &Program,
+    name: &str,
+    value: &UniformValue,
+) -> Result<(), UniformError> {
+    match program.get_uniform(name) {
+        None => Err(UniformError::Unknown(name.to_string())),
+        Some(uniform) if !value.is_usable_with(&uniform.ty) => {
+            Err(UniformError::WrongType(name.to_string(), uniform.ty))
+        }
+        Some(_) => Ok(()),
+    }
+}
+
+/// Builds the program, returning it with the files it was built from.
+fn compile<F: Facade>(
+    facade: &F,
+    assets: &mut AssetManager,
+    vertex_path: &Path,
+    fragment_path: &Path,
+    outputs_srgb: bool,
+) -> Result<(Program, Vec<PathBuf>), Box<dyn Error>> {
+    let mut vertex_sources = Vec::new();
+    let line_offset = line_directive_offset(&assets.text(vertex_path)?);
+    let vertex_src = preprocess(assets, vertex_path, &mut vertex_sources, line_offset, 0)?;
+    let mut fragment_sources = Vec::new();
+    let line_offset = line_directive_offset(&assets.text(fragment_path)?);
+    let fragment_src = preprocess(assets, fragment_path, &mut fragment_sources, line_offset, 0)?;
+
+    let input = ProgramCreationInput::SourceCode {
+        vertex_shader: &vertex_src,
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        fragment_shader: &fragment_src,
+        transform_feedback_varyings: None,
+        outputs_srgb,
+        uses_point_size: false,
+    };
+    let program = Program::new(facade, input)
+        .map_err(|e| describe_error(&e, &vertex_sources, &fragment_sources))?;
+
+    let mut files = vec![vertex_path.to_path_buf(), fragment_path.to_path_buf()];
+    for file in vertex_sources.into_iter().chain(fragment_sources) {
+        if !files.contains(&file) {
+            files.push(file);
+        }
+    }
+    Ok((program, files))
+}
+
+/// Reads the shader at `path` and replaces its `#include` lines by the included files. Every
+/// file read is appended to `sources`, and `#line` directives number the lines by their index
+/// there, so compiler logs can be traced back to the right file. `line_offset` comes from
+/// `line_directive_offset`.
+fn preprocess(
+    assets: &mut AssetManager,
+    path: &Path,
+    sources: &mut Vec<PathBuf>,
+    line_offset: usize,
+    depth: usize,
+) -> Result<String, Box<dyn Error>> {
+    let index = sources.len();
+    sources.push(path.to_path_buf());
+    let text = assets.text(path)?;
+
+    let mut source = String::new();
+    for (number, line) in text.lines().enumerate() {
+        let Some(name) = parse_include(line) else {
+            source.push_str(line);
+            source.push('\n');
+            continue;
+        };
+        let error = |message: String| format!("{}:{}: {}", path.display(), number + 1, message);
+        if depth == MAX_INCLUDE_DEPTH {
+            return Err(error(format!(
+                "includes nested more than {} deep",
+                MAX_INCLUDE_DEPTH
+            ))
+            .into());
+        }
+        let included = path.parent().unwrap_or(Path::new("")).join(name);
+        let included_index = sources.len();
+        let text = preprocess(assets, &included, sources, line_offset, depth + 1)
+            .map_err(|e| error(e.to_string()))?;
+
+        source.push_str(&format!("#line {} {}\n", 1 - line_offset, included_index));
+        source.push_str(&text);
+        source.push_str(&format!("#line {} {}\n", number + 2 - line_offset, index));
+    }
+    Ok(source)
+}
+
+/// What to subtract from the number in a `#line` directive for the following line to get that
+/// number. Before GLSL 3.30 the line after `#line n` was numbered n + 1.
+fn line_directive_offset(source: &str) -> usize {
+    let version = source
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("#version"))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|version| version.parse::<u32>().ok())
+        .unwrap_or(110);
+    if version < 330 {
+        1
+    } else {
+        0
+    }
+}
+
+/// The file named by an `#include "file"` line.
+fn parse_include(line: &str) -> Option<&str> {
+    let rest = line
+        .trim()
+        .strip_prefix('#')?
+        .trim_start()
+        .strip_prefix("include")?;
+    rest.trim().strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Describes a program creation error, with the compiler log rewritten to point into the
+/// shader files. `vertex_sources` and `fragment_sources` are the files each shader was built
+/// from, numbered as in its `#line` directives.
+fn describe_error(
+    error: &ProgramCreationError,
+    vertex_sources: &[PathBuf],
+    fragment_sources: &[PathBuf],
+) -> String {
+    match error {
+        ProgramCreationError::CompilationError(log, ShaderType::Vertex) => {
+            locate_log(log, vertex_sources)
+        }
+        ProgramCreationError::CompilationError(log, ShaderType::Fragment) => {
+            locate_log(log, fragment_sources)
+        }
+        ProgramCreationError::LinkingError(log) => format!(
+            "{} + {}: link error: {}",
+            vertex_sources[0].display(),
+            fragment_sources[0].display(),
+            log.trim()
+        ),
+        other => other.to_string(),
+    }
+}
+
+/// Prefixes each line of a GLSL info log with `path:line`, or just the path of the first source
+/// when the line doesn't refer to a line of the source.
+fn locate_log(log: &str, sources: &[PathBuf]) -> String {
+    log.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| match parse_log_line(line) {
+            Some((source, number, message)) => {
+                let path = sources.get(source).unwrap_or(&sources[0]);
+                format!("{}:{}: {}", path.display(), number, message)
+            }
+            None => format!("{}: {}", sources[0].display(), line),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Splits a line of an info log into the source string and line it refers to and the message.
+/// Drivers disagree on the format:
+///
+/// - Mesa: `0:12(5): error: ...`
+/// - NVIDIA: `0(12) : error C1008: ...`
+/// - AMD and Intel on Windows: `ERROR: 0:12: ...`
+///
+/// The leading number is the index of the source string, as set by `#line` directives.
+fn parse_log_line(line: &str) -> Option<(usize, u32, String)> {
+    let (severity, rest) = match line.split_once(": ") {
+        Some((severity @ ("ERROR" | "WARNING"), rest)) => (Some(severity), rest),
+        _ => (None, line),
+    };
+
+    let after_source = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+    if after_source.len() == rest.len() {
+        return None;
+    }
+    let source = rest[..rest.len() - after_source.len()].parse().ok()?;
+
+    let (number, message) = if let Some(rest) = after_source.strip_prefix(':') {
+        let end = rest.find(|c: char| !c.is_ascii_digit())?;
+        let number = rest[..end].parse().ok()?;
+        let rest = &rest[end..];
+        // Skip Mesa's column
+        let rest = match rest.strip_prefix('(') {
+            Some(column) => &column[column.find(')')? + 1..],
+            None => rest,
+        };
+        (number, rest.strip_prefix(':')?)
+    } else if let Some(rest) = after_source.strip_prefix('(') {
+        let (number, rest) = rest.split_once(')')?;
+        (number.parse().ok()?, rest.trim_start().strip_prefix(':')?)
+    } else {
+        return None;
+    };
+
+    let message = message.trim();
+    Some(match severity {
+        Some(severity) => (
+            source,
+            number,
+            format!("{}: {}", severity.to_lowercase(), message),
+        ),
+        None => (source, number, message.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::headless;
+
+    const VERTEX: &str = "#version 150\nin vec3 position;\nvoid main() {\n    gl_Position = vec4(position, 1.0);\n}\n";
+    const FRAGMENT: &str =
+        "#version 150\nout vec4 color;\nvoid main() {\n    color = vec4(1.0);\n}\n";
+
+    #[test]
+    fn mesa_log_lines() {
+        assert_eq!(
+            parse_log_line("0:12(5): error: `foo' undeclared"),
+            Some((0, 12, "error: `foo' undeclared".to_string()))
+        );
+    }
+
+    #[test]
+    fn nvidia_log_lines() {
+        assert_eq!(
+            parse_log_line("0(7) : error C1008: undefined variable \"foo\""),
+            Some((0, 7, "error C1008: undefined variable \"foo\"".to_string()))
+        );
+    }
+
+    #[test]
+    fn amd_log_lines() {
+        assert_eq!(
+            parse_log_line("ERROR: 2:3: 'foo' : undeclared identifier"),
+            Some((2, 3, "error: 'foo' : undeclared identifier".to_string()))
+        );
+    }
+
+    #[test]
+    fn other_log_lines_keep_just_the_path() {
+        assert_eq!(parse_log_line("error: linking failed"), None);
+        assert_eq!(parse_log_line("ERROR: 1 compilation errors."), None);
+
+        let log = "0:2(1): error: syntax error\n\nERROR: 1 compilation errors.\n";
+        assert_eq!(
+            locate_log(log, &[PathBuf::from("shaders/a.frag")]),
+            "shaders/a.frag:2: error: syntax error\nshaders/a.frag: ERROR: 1 compilation errors."
+        );
+    }
+
+    #[test]
+    fn failed_reload_keeps_the_last_good_program() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/shader-tests");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("test.vert"), VERTEX).unwrap();
+        std::fs::write(dir.join("test.frag"), FRAGMENT).unwrap();
+
+        let context = headless::create_context((1, 1)).unwrap();
+        let mut assets = AssetManager::new(&dir);
+        let mut shader =
+            ShaderProgram::load(&context, &mut assets, "test.vert", "test.frag").unwrap();
+        assert_eq!(shader.error(), None);
+
+        std::fs::write(
+            dir.join("test.frag"),
+            FRAGMENT.replace("vec4(1.0)", "undefined_value"),
+        )
+        .unwrap();
+        assert!(!shader.reload(&context, &mut assets));
+        let error = shader.error().unwrap();
+        let expected = format!("{}:4: ", dir.join("test.frag").display());
+        assert!(error.starts_with(&expected), "{}", error);
+        assert!(shader.program().get_frag_data_location("color").is_some());
+
+        std::fs::write(dir.join("test.frag"), FRAGMENT).unwrap();
+        assert!(shader.reload(&context, &mut assets));
+        assert_eq!(shader.error(), None);
+    }
+
+    #[test]
+    fn include_lines() {
+        assert_eq!(
+            parse_include("#include \"lights.glsl\""),
+            Some("lights.glsl")
+        );
+        assert_eq!(
+            parse_include("  #  include \"a/b.glsl\"  "),
+            Some("a/b.glsl")
+        );
+        assert_eq!(parse_include("#include <lights.glsl>"), None);
+        assert_eq!(parse_include("// #include \"lights.glsl\""), None);
+    }
+
+    #[test]
+    fn errors_point_into_included_files() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("target/shader-include-tests");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("test.vert"), VERTEX).unwrap();
+        std::fs::write(
+            dir.join("test.frag"),
+            "#version 150\n#include \"white.glsl\"\nout vec4 color;\nvoid main() {\n    color = white;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("white.glsl"),
+            "// Shared\nconst vec4 white = vec4(1.0);\n",
+        )
+        .unwrap();
+
+        let context = headless::create_context((1, 1)).unwrap();
+        let mut assets = AssetManager::new(&dir);
+        let mut shader =
+            ShaderProgram::load(&context, &mut assets, "test.vert", "test.frag").unwrap();
+        assert!(shader.files().contains(&dir.join("white.glsl")));
+
+        std::fs::write(
+            dir.join("white.glsl"),
+            "// Shared\nconst vec4 white = 1.0;\n",
+        )
+        .unwrap();
+        assert!(!shader.reload(&context, &mut assets));
+        let error = shader.error().unwrap();
+        let expected = format!("{}:2: ", dir.join("white.glsl").display());
+        assert!(error.starts_with(&expected), "{}", error);
+
+        // Lines after the include keep their numbers
+        std::fs::write(dir.join("white.glsl"), "const vec4 white = vec4(1.0);\n").unwrap();
+        let fragment = std::fs::read_to_string(dir.join("test.frag")).unwrap();
+        std::fs::write(
+            dir.join("test.frag"),
+            fragment.replace("= white", "= black"),
+        )
+        .unwrap();
+        assert!(!shader.reload(&context, &mut assets));
+        let error = shader.error().unwrap();
+        let expected = format!("{}:5: ", dir.join("test.frag").display());
+        assert!(error.starts_with(&expected), "{}", error);
+    }
+}
@@ -0,0 +1,259 @@
+// Shadow maps for directional and spot lights.
+//
+// Before the main pass, the scene's depth is drawn from each shadow-casting light into a depth
+// texture. The main fragment shader compares its own distance to the light with that texture,
+// averaging several texels (percentage-closer filtering) to soften the edges.
+
+use std::error::Error;
+use std::f32::consts::PI;
+
+use glium::backend::Facade;
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::texture::DepthTexture2d;
+use glium::uniforms::DepthTextureComparison;
+use glium::uniforms::MagnifySamplerFilter;
+use glium::uniforms::MinifySamplerFilter;
+use glium::uniforms::Sampler;
+use glium::uniforms::SamplerWrapFunction;
+use glium::Depth;
+use glium::DepthTest;
+use glium::DrawParameters;
+use glium::Surface;
+
+use crate::assets::AssetManager;
+use crate::light::Light;
+use crate::light::LightKind;
+use crate::math::vec3;
+use crate::math::Mat4;
+use crate::math::Vec3;
+use crate::mesh::Mesh;
+use crate::shader::ShaderProgram;
+
+/// Number of shadow maps the shaders can sample; further shadow-casting lights are unshadowed.
+pub const MAX_SHADOW_MAPS: usize = 2;
+
+const VERTEX_SHADER: &str = "shaders/shadow.vert";
+const FRAGMENT_SHADER: &str = "shaders/shadow.frag";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ShadowSettings {
+    /// Width and height of each shadow map in texels.
+    pub map_size: u32,
+    /// How much closer to the light a surface has to be than the shadow map says before it
+    /// counts as lit, in shadow map depth units (0 to 1). Too little gives shadow acne, too much
+    /// detaches shadows from their casters.
+    pub constant_bias: f32,
+    /// Extra bias for surfaces at grazing angles to the light, where acne is worst.
+    pub slope_bias: f32,
+    /// Texels on each side of the center that are averaged; 0 takes a single sample.
+    pub pcf_radius: i32,
+}
+
+impl Default for ShadowSettings {
+    fn default() -> Self {
+        ShadowSettings {
+            map_size: 2048,
+            constant_bias: 0.002,
+            slope_bias: 0.005,
+            pcf_radius: 1,
+        }
+    }
+}
+
+/// View and projection of `light` onto its shadow map, covering the sphere at `center` with
+/// `radius`. Point lights shine in every direction and can't be covered by a single map.
+pub fn light_view_projection(light: &Light, center: Vec3, radius: f32) -> Option<Mat4> {
+    let radius = radius.max(0.01);
+    match light.kind {
+        LightKind::Directional { direction } => {
+            let eye = center - direction * (radius * 2.0);
+            let view = Mat4::look_to(eye, direction, up_for(direction));
+            let projection =
+                Mat4::orthographic(-radius, radius, -radius, radius, radius, radius * 3.0);
+            Some(projection * view)
+        }
+        LightKind::Spot {
+            position,
+            direction,
+            outer_angle,
+            ..
+        } => {
+            let distance = (center - position).length();
+            let far = distance + radius;
+            let near = (distance - radius).max(far / 1000.0);
+            let fov = (outer_angle * 2.0).min(PI * 0.95);
+            let view = Mat4::look_to(position, direction, up_for(direction));
+            Some(Mat4::perspective(fov, 1.0, near, far) * view)
+        }
+        LightKind::Point { .. } => None,
+    }
+}
+
+// Any up vector works as long as it isn't parallel to the light
+fn up_for(direction: Vec3) -> Vec3 {
+    if direction.normalize().y.abs() > 0.99 {
+        Vec3::Z
+    } else {
+        Vec3::Y
+    }
+}
+
+/// Maps clip space, -1 to 1 on every axis, onto shadow map texture coordinates and depth, which
+/// run from 0 to 1.
+fn clip_to_texture() -> Mat4 {
+    Mat4::from_translation(vec3(0.5, 0.5, 0.5)) * Mat4::from_scale(vec3(0.5, 0.5, 0.5))
+}
+
+pub struct ShadowMaps {
+    maps: Vec<DepthTexture2d>,
+    program: ShaderProgram,
+    settings: ShadowSettings,
+}
+
+impl ShadowMaps {
+    pub fn new<F: Facade>(
+        facade: &F,
+        assets: &mut AssetManager,
+        settings: ShadowSettings,
+    ) -> Result<Self, Box<dyn Error>> {
+        Ok(ShadowMaps {
+            maps: create_maps(facade, settings.map_size)?,
+            program: ShaderProgram::load(facade, assets, VERTEX_SHADER, FRAGMENT_SHADER)?,
+            settings,
+        })
+    }
+
+    pub fn settings(&self) -> ShadowSettings {
+        self.settings
+    }
+
+    /// Changes the settings, recreating the maps if their size changed.
+    pub fn set_settings<F: Facade>(
+        &mut self,
+        facade: &F,
+        settings: ShadowSettings,
+    ) -> Result<(), Box<dyn Error>> {
+        if settings.map_size != self.settings.map_size {
+            self.maps = create_maps(facade, settings.map_size)?;
+        }
+        self.settings = settings;
+        Ok(())
+    }
+
+    /// The depth-only program.
+    pub fn program(&self) -> &ShaderProgram {
+        &self.program
+    }
+
+    /// The depth-only program, for reloading it when its files change.
+    pub fn program_mut(&mut self) -> &mut ShaderProgram {
+        &mut self.program
+    }
+
+    /// Draws the depth of `meshes`, each with its model matrix, from the first
+    /// `MAX_SHADOW_MAPS` shadow-casting lights. The maps cover the sphere given by `bounds`.
+    ///
+    /// Returns, for each light, the matrix from world space to the texture coordinates and
+    /// depth of its shadow map, or `None` if it didn't get one.
+    pub fn render<F: Facade>(
+        &self,
+        facade: &F,
+        lights: &[Light],
+        meshes: &[(Mat4, &Mesh)],
+        bounds: (Vec3, f32),
+    ) -> Vec<Option<Mat4>> {
+        let params = DrawParameters {
+            depth: Depth {
+                test: DepthTest::IfLess,
+                write: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut used = 0;
+        lights
+            .iter()
+            .map(|light| {
+                if !light.casts_shadows || used == MAX_SHADOW_MAPS {
+                    return None;
+                }
+                let light_matrix = light_view_projection(light, bounds.0, bounds.1)?;
+
+                let mut target = SimpleFrameBuffer::depth_only(facade, &self.maps[used]).unwrap();
+                target.clear_depth(1.0);
+                for (model, mesh) in meshes {
+                    mesh.draw(
+                        &mut target,
+                        self.program.program(),
+                        &uniform! { model: *model, light_matrix: light_matrix },
+                        &params,
+                    );
+                }
+                used += 1;
+
+                Some(clip_to_texture() * light_matrix)
+            })
+            .collect()
+    }
+
+    /// The shadow map with the given index, set up for depth comparisons in the shader.
+    pub fn sampler(&self, index: usize) -> Sampler<'_, DepthTexture2d> {
+        self.maps[index]
+            .sampled()
+            .magnify_filter(MagnifySamplerFilter::Linear)
+            .minify_filter(MinifySamplerFilter::Linear)
+            .wrap_function(SamplerWrapFunction::Clamp)
+            .depth_texture_comparison(Some(DepthTextureComparison::LessOrEqual))
+    }
+}
+
+fn create_maps<F: Facade>(facade: &F, size: u32) -> Result<Vec<DepthTexture2d>, Box<dyn Error>> {
+    (0..MAX_SHADOW_MAPS)
+        .map(|_| Ok(DepthTexture2d::empty(facade, size, size)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec3_eq(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    // Where a world space point lands in the shadow map, as texture coordinates and depth
+    fn project(light: &Light, point: Vec3) -> Vec3 {
+        let matrix = clip_to_texture() * light_view_projection(light, Vec3::ZERO, 2.0).unwrap();
+        let p = matrix * point.extend(1.0);
+        p.truncate() / p.w
+    }
+
+    #[test]
+    fn directional_map_covers_the_bounds() {
+        let light = Light::directional(vec3(0.0, -1.0, 0.0), [1.0; 3], 1.0);
+
+        // Straight down: the top of the sphere is nearest, the bottom farthest
+        assert_vec3_eq(project(&light, vec3(0.0, 2.0, 0.0)), vec3(0.5, 0.5, 0.0));
+        assert_vec3_eq(project(&light, vec3(0.0, -2.0, 0.0)), vec3(0.5, 0.5, 1.0));
+        // The side of the sphere touches the edge of the map
+        assert_vec3_eq(project(&light, vec3(2.0, 0.0, 0.0)), vec3(1.0, 0.5, 0.5));
+    }
+
+    #[test]
+    fn spot_map_depth_grows_away_from_the_light() {
+        let light = Light::spot(vec3(0.0, 0.0, -5.0), Vec3::Z, 10.0, 0.3, 0.5, [1.0; 3], 1.0);
+
+        let near = project(&light, vec3(0.0, 0.0, -1.0));
+        let far = project(&light, vec3(0.0, 0.0, 1.0));
+        assert_vec3_eq(vec3(near.x, near.y, 0.0), vec3(0.5, 0.5, 0.0));
+        assert!(near.z < far.z);
+        assert!((0.0..=1.0).contains(&near.z) && (0.0..=1.0).contains(&far.z));
+    }
+
+    #[test]
+    fn point_lights_have_no_map() {
+        let light = Light::point(Vec3::ZERO, 5.0, [1.0; 3], 1.0);
+        assert_eq!(light_view_projection(&light, Vec3::ZERO, 1.0), None);
+    }
+}
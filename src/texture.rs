@@ -0,0 +1,172 @@
+use glium::backend::Facade;
+use glium::texture::MipmapsOption;
+use glium::texture::RawImage2d;
+use glium::texture::SrgbTexture2d;
+use glium::texture::Texture2d;
+use glium::uniforms::MagnifySamplerFilter;
+use glium::uniforms::MinifySamplerFilter;
+use glium::uniforms::SamplerBehavior;
+use glium::uniforms::SamplerWrapFunction;
+use glium::Rect;
+
+/// How textures are read unless a material says otherwise: repeated outside the 0 to 1 range,
+/// and blended between texels and between mipmap levels. This is what OpenGL does for a
+/// texture without a sampler object.
+pub const DEFAULT_SAMPLER: SamplerBehavior = SamplerBehavior {
+    wrap_function: (
+        SamplerWrapFunction::Repeat,
+        SamplerWrapFunction::Repeat,
+        SamplerWrapFunction::Repeat,
+    ),
+    minify_filter: MinifySamplerFilter::LinearMipmapLinear,
+    magnify_filter: MagnifySamplerFilter::Linear,
+    depth_texture_comparison: None,
+    max_anisotropy: 1,
+};
+
+/// Where the smaller versions of a texture, read when it is drawn at a distance, come from.
+pub enum MipmapLevels {
+    /// Only the full-size image, which mipmapped filters then read at every distance.
+    None,
+    /// Downscaled from the full-size image when it is uploaded.
+    Generated,
+    /// The levels after the first, each half the size of the one before, rounded down.
+    Precomputed(Vec<image::DynamicImage>),
+}
+
+// OpenGL expects the bottom row first, image files store the top row first
+fn raw_image(image: image::DynamicImage) -> RawImage2d<'static, u8> {
+    let image = image.to_rgba8();
+    let image_dimensions = image.dimensions();
+    RawImage2d::from_raw_rgba_reversed(&image.into_raw(), image_dimensions)
+}
+
+// The option to create a texture with, and the images of the levels to write after creating it
+fn mipmaps_option(
+    image: &image::DynamicImage,
+    mipmaps: MipmapLevels,
+) -> Result<(MipmapsOption, Vec<RawImage2d<'static, u8>>), String> {
+    match mipmaps {
+        MipmapLevels::None => Ok((MipmapsOption::NoMipmap, Vec::new())),
+        MipmapLevels::Generated => Ok((MipmapsOption::AutoGeneratedMipmaps, Vec::new())),
+        MipmapLevels::Precomputed(levels) => {
+            let mut expected = (image.width(), image.height());
+            for (i, level) in levels.iter().enumerate() {
+                if expected == (1, 1) {
+                    return Err(format!(
+                        "{} mipmap levels given, but only {} fit",
+                        levels.len(),
+                        i
+                    ));
+                }
+                expected = ((expected.0 / 2).max(1), (expected.1 / 2).max(1));
+                if (level.width(), level.height()) != expected {
+                    return Err(format!(
+                        "mipmap level {} is {}x{}, but should be {}x{}",
+                        i + 1,
+                        level.width(),
+                        level.height(),
+                        expected.0,
+                        expected.1
+                    ));
+                }
+            }
+            let option = MipmapsOption::EmptyMipmapsMax(levels.len() as u32);
+            Ok((option, levels.into_iter().map(raw_image).collect()))
+        }
+    }
+}
+
+// Creates a texture of type `$texture` from `$image` and writes any precomputed mipmap levels.
+// The color and data texture types share no trait for this, so they share the steps instead.
+macro_rules! upload_with_mipmaps {
+    ($texture:ty, $facade:expr, $image:expr, $mipmaps:expr) => {{
+        let image = $image;
+        let (option, levels) = mipmaps_option(&image, $mipmaps)?;
+        let texture = <$texture>::with_mipmaps($facade, raw_image(image), option)
+            .map_err(|e| e.to_string())?;
+        for (level, data) in (1..).zip(levels) {
+            let mipmap = texture
+                .mipmap(level)
+                .ok_or_else(|| format!("mipmap level {} is missing", level))?;
+            mipmap.write(whole(mipmap.dimensions()), data);
+        }
+        Ok(texture)
+    }};
+}
+
+/// Uploads a color texture whose pixels are sRGB encoded, such as a diffuse map.
+pub fn srgb_texture<F: Facade>(facade: &F, image: image::DynamicImage) -> SrgbTexture2d {
+    srgb_texture_with_mipmaps(facade, image, MipmapLevels::Generated).unwrap()
+}
+
+/// Like `srgb_texture`, with the mipmaps given. Fails if precomputed levels have the wrong size.
+pub fn srgb_texture_with_mipmaps<F: Facade>(
+    facade: &F,
+    image: image::DynamicImage,
+    mipmaps: MipmapLevels,
+) -> Result<SrgbTexture2d, String> {
+    upload_with_mipmaps!(SrgbTexture2d, facade, image, mipmaps)
+}
+
+/// Uploads a texture whose pixels are data rather than colors, such as a normal map.
+pub fn linear_texture<F: Facade>(facade: &F, image: image::DynamicImage) -> Texture2d {
+    linear_texture_with_mipmaps(facade, image, MipmapLevels::Generated).unwrap()
+}
+
+/// Like `linear_texture`, with the mipmaps given. Fails if precomputed levels have the wrong
+/// size.
+pub fn linear_texture_with_mipmaps<F: Facade>(
+    facade: &F,
+    image: image::DynamicImage,
+    mipmaps: MipmapLevels,
+) -> Result<Texture2d, String> {
+    upload_with_mipmaps!(Texture2d, facade, image, mipmaps)
+}
+
+fn whole((width, height): (u32, u32)) -> Rect {
+    Rect {
+        left: 0,
+        bottom: 0,
+        width,
+        height,
+    }
+}
+
+/// A 1x1 texture of a single linear color, for materials that have a color but no image.
+pub fn solid_srgb_texture<F: Facade>(facade: &F, color: [f32; 3]) -> SrgbTexture2d {
+    let pixel = image::Rgb(color.map(|c| (linear_to_srgb(c) * 255.0).round() as u8));
+    let image = image::RgbImage::from_pixel(1, 1, pixel);
+    srgb_texture(facade, image::DynamicImage::ImageRgb8(image))
+}
+
+/// A 1x1 texture holding data rather than a color, such as a neutral metallic-roughness map.
+pub fn solid_linear_texture<F: Facade>(facade: &F, value: [f32; 3]) -> Texture2d {
+    let pixel = image::Rgb(value.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8));
+    let image = image::RgbImage::from_pixel(1, 1, pixel);
+    linear_texture(facade, image::DynamicImage::ImageRgb8(image))
+}
+
+/// A 1x1 normal map that leaves the surface normal unchanged.
+pub fn flat_normal_map<F: Facade>(facade: &F) -> Texture2d {
+    let image = image::RgbImage::from_pixel(1, 1, image::Rgb([128, 128, 255]));
+    linear_texture(facade, image::DynamicImage::ImageRgb8(image))
+}
+
+/// Decodes an sRGB channel value, both from 0 to 1.
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,74 @@
+// Opens the demo window with an OpenGL context.
+//
+// This does what glium's `SimpleWindowBuilder` does, but also sets the swap interval, which the
+// builder can't. The window's framebuffer is not multisampled; the renderer multisamples
+// offscreen, where it can be switched on and off while running.
+
+use std::error::Error;
+use std::num::NonZeroU32;
+
+use glium::backend::glutin::Display;
+use glium::glutin::config::ConfigTemplateBuilder;
+use glium::glutin::context::ContextAttributesBuilder;
+use glium::glutin::display::GetGlDisplay;
+use glium::glutin::prelude::*;
+use glium::glutin::surface::SurfaceAttributesBuilder;
+use glium::glutin::surface::SwapInterval;
+use glium::glutin::surface::WindowSurface;
+use glutin_winit::DisplayBuilder;
+use raw_window_handle::HasRawWindowHandle;
+use winit::dpi::PhysicalSize;
+use winit::event_loop::EventLoop;
+use winit::window::Window;
+use winit::window::WindowBuilder;
+
+pub struct WindowSettings<'a> {
+    pub title: &'a str,
+    pub size: (u32, u32),
+    /// Whether to wait for the display's refresh before showing a frame.
+    pub vsync: bool,
+}
+
+pub fn create<T>(
+    event_loop: &EventLoop<T>,
+    settings: &WindowSettings,
+) -> Result<(Window, Display<WindowSurface>), Box<dyn Error>> {
+    let window_builder = WindowBuilder::new()
+        .with_title(settings.title)
+        .with_inner_size(PhysicalSize::new(settings.size.0, settings.size.1));
+    // The display lists its configs in order of preference
+    let (window, config) = DisplayBuilder::new()
+        .with_window_builder(Some(window_builder))
+        .build(event_loop, ConfigTemplateBuilder::new(), |mut configs| {
+            configs.next().unwrap()
+        })?;
+    let window = window.ok_or("could not open a window")?;
+
+    let (width, height): (u32, u32) = window.inner_size().into();
+    // The renderer leaves encoding its linear colors as sRGB to the framebuffer
+    let surface_attributes = SurfaceAttributesBuilder::<WindowSurface>::new()
+        .with_srgb(Some(true))
+        .build(
+            window.raw_window_handle(),
+            NonZeroU32::new(width).ok_or("window has no width")?,
+            NonZeroU32::new(height).ok_or("window has no height")?,
+        );
+    let display = config.display();
+    let surface = unsafe { display.create_window_surface(&config, &surface_attributes)? };
+    let context_attributes =
+        ContextAttributesBuilder::new().build(Some(window.raw_window_handle()));
+    let context =
+        unsafe { display.create_context(&config, &context_attributes)? }.make_current(&surface)?;
+
+    let interval = if settings.vsync {
+        SwapInterval::Wait(NonZeroU32::MIN)
+    } else {
+        SwapInterval::DontWait
+    };
+    if let Err(e) = surface.set_swap_interval(&context, interval) {
+        println!("Could not set the swap interval ({})", e);
+    }
+
+    let display = Display::from_context_surface(context, surface)?;
+    Ok((window, display))
+}